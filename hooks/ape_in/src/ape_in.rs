@@ -324,10 +324,18 @@ mod ape_in_hook {
             let base_coin_bucket = self.base_coin_vault.take(self.base_coins_per_launch * buyers);
 
             // Buy the launched coin
-            let (coin_bucket, new_hook_argument, event) = hook_badge_bucket.as_ref().unwrap().authorize_with_amount(
+            let (coin_bucket, new_hook_argument, event, graduation) = hook_badge_bucket.as_ref().unwrap().authorize_with_amount(
                     1,
-                    || argument.component.buy(base_coin_bucket)
+                    || argument.component.buy(base_coin_bucket, Decimal::ZERO, None, None)
             );
+            let mut events = vec![event];
+            let mut new_hook_arguments = vec![new_hook_argument];
+
+            // The buy may have made a bonding curve launched coin graduate
+            if let Some((graduation_hook_argument, graduation_event)) = graduation {
+                events.push(graduation_event);
+                new_hook_arguments.push(graduation_hook_argument);
+            }
 
             // Add this coin to the list
             self.last_launch_id += 1;
@@ -341,7 +349,7 @@ mod ape_in_hook {
             );
 
             // Return the hook badge, the BuyEvent and the new argument for the hooks
-            (hook_badge_bucket, None, vec![event], vec![new_hook_argument])
+            (hook_badge_bucket, None, events, new_hook_arguments)
         }
 
         // Round 0, non accepting calls triggered by other hooks
@@ -382,7 +382,7 @@ mod dca {
                     let (base_coin_bucket, new_argument, event) =
                         hook_badge_bucket.as_ref().unwrap().authorize_with_amount(
                             1,
-                            || component.as_mut().unwrap().sell(FungibleBucket(coin1_bucket))
+                            || component.as_mut().unwrap().sell(FungibleBucket(coin1_bucket), Decimal::ZERO, None)
                         );
 
                     events.push(event);
@@ -398,15 +398,21 @@ mod dca {
             let coin2_bucket = match self.base_coin_address == task.coin2_vault.resource_address() {
                 true => base_coin_bucket,
                 false => {
-                    let (coin2_bucket, new_argument, event) =
+                    let (coin2_bucket, new_argument, event, graduation) =
                         hook_badge_bucket.as_ref().unwrap().authorize_with_amount(
                             1,
-                            || argument.component.buy(FungibleBucket(base_coin_bucket))
+                            || argument.component.buy(FungibleBucket(base_coin_bucket), Decimal::ZERO, None, None)
                         );
 
                     events.push(event);
                     hook_arguments.push(new_argument);
 
+                    // The buy may have made a bonding curve launched coin graduate
+                    if let Some((graduation_argument, graduation_event)) = graduation {
+                        events.push(graduation_event);
+                        hook_arguments.push(graduation_argument);
+                    }
+
                     coin2_bucket.into()
                 },
             };
@@ -198,7 +198,7 @@ mod limit_buy_hook {
                     // The order can be filled by buying coins fron the RadixPump component (we
                     // have no hook badge now to talk directly to the pool)
                     let (coin_bucket, mut vec1, mut vec2): (Bucket, Vec<Bucket>, Vec<Bucket>) = 
-                        self.radix_pump_component.call("swap", &(base_coin_bucket, coin_to_buy, 0u64));
+                        self.radix_pump_component.call("swap", &(base_coin_bucket, coin_to_buy, 0u64, Decimal::ZERO, None::<Instant>, None::<Proof>, None::<u64>));
 
                     // Put all of the buckets received by RadixPump into one vector
                     buckets.push(coin_bucket);
@@ -218,7 +218,11 @@ mod limit_buy_hook {
                                     WithdrawStrategy::Rounded(RoundingMode::ToZero)
                                 ),
                                 coin_to_buy,
-                                0u64
+                                0u64,
+                                Decimal::ZERO,
+                                None::<Instant>,
+                                None::<Proof>,
+                                None::<u64>,
                             )
                         );
 
@@ -477,10 +481,16 @@ mod limit_buy_hook {
             );
 
             // Use the hook badge to buy coins at the pool
-            let (coin_bucket, _, event) = hook_badge_bucket.as_ref().unwrap().authorize_with_amount(
+            let (coin_bucket, _, event, graduation) = hook_badge_bucket.as_ref().unwrap().authorize_with_amount(
                 1,
-                || argument.component.buy(base_coin_bucket)
+                || argument.component.buy(base_coin_bucket, Decimal::ZERO, None, None)
             );
+            let mut events = vec![event];
+
+            // The buy may have made a bonding curve launched coin graduate
+            if let Some((_, graduation_event)) = graduation {
+                events.push(graduation_event);
+            }
 
             let bought_price = base_coin_amount_so_far / coin_bucket.amount();
 
@@ -559,7 +569,7 @@ mod limit_buy_hook {
                 }
             );
 
-            (hook_badge_bucket, None, vec![event], vec![])
+            (hook_badge_bucket, None, events, vec![])
         }
 
         // Round 1, accepting calls triggered by other hooks
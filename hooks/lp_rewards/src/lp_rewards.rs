@@ -10,12 +10,26 @@ use scrypto_interface::*;
 // RemoveLiquidity operations are intercepted by this hook so it is advisable to enable the
 // hook for AddLiquidity globally as soon as possible and let coin creators enable/disable the
 // hook for RemoveLiquidity when they start/end campaigns.
+// Only LP NFTs take part in liquidity campaigns: fungible LP units have no id so the hook can't
+// keep track of when and how much liquidity each of them added.
+// RadixPump refuses to switch a pool to fungible LP units while the coin creator has a
+// RemoveLiquidity hook enabled on it, so a running campaign can't be cut off by the switch.
+// LP NFTs locked in the RadixPump liquidity locker keep taking part in liquidity campaigns: the
+// holder of the lock certificate gets the rewards through RadixPump, which calls get_rewards with a
+// proof of the locked LP NFTs.
 
 // This struct contains informations about an LP token
 #[derive(ScryptoSbor)]
 struct LiquidityProvider {
     amount: Decimal,
-    last_rewards_withdraw_time: i64
+    last_rewards_withdraw_time: i64,
+
+    // Rewards accrued before last_rewards_withdraw_time but not paid because the campaign was
+    // out of funds
+    unpaid_rewards: Decimal,
+
+    // The liquidity campaign unpaid_rewards refer to
+    unpaid_campaign_id: u32,
 }
 
 // This struct contains informations about a liquidity campaign
@@ -320,6 +334,10 @@ mod lp_rewards_hook {
         ) -> Bucket {
 
             let lp_address = lp_proof.resource_address();
+            assert!(
+                !lp_address.is_fungible(),
+                "Fungible LP units don't take part in liquidity campaigns",
+            );
 
             // Let's skip the check for now, there are many valids LP addresses
             let checked_proof = lp_proof.skip_checking();
@@ -330,10 +348,8 @@ mod lp_rewards_hook {
             // first one
             let coin_address = non_fungible_vec[0].data().coin_resource_address;
 
-            let mut campaign = self.liquidity_campaigns.get_mut(
-                &self.active_campaign.get(&coin_address).expect("No active campaign for this coin")
-            )
-            .unwrap();
+            let campaign_id = *self.active_campaign.get(&coin_address).expect("No active campaign for this coin");
+            let mut campaign = self.liquidity_campaigns.get_mut(&campaign_id).unwrap();
            
             // Now we can check that the liquidity token is not fake
             assert!(
@@ -371,13 +387,23 @@ mod lp_rewards_hook {
                             (coin_address, non_fungible_id),
                             LiquidityProvider {
                                 amount: non_fungible.data().deposited_coins,
-                                last_rewards_withdraw_time: now
+                                last_rewards_withdraw_time: now,
+                                unpaid_rewards: Decimal::ZERO,
+                                unpaid_campaign_id: 0,
                             }
                         );
 
                         non_fungible.data().date.seconds_since_unix_epoch
                     },
                     Some(ref mut lp) => {
+
+                        // Also pay the rewards that were not paid because the campaign was out
+                        // of funds
+                        if lp.unpaid_campaign_id == campaign_id {
+                            rewards_amount += lp.unpaid_rewards;
+                        }
+                        lp.unpaid_rewards = Decimal::ZERO;
+
                         let last_time = lp.last_rewards_withdraw_time;
                         lp.last_rewards_withdraw_time = now;
                         last_time
@@ -420,6 +446,142 @@ mod lp_rewards_hook {
 
             (non_fungible_data.coin_resource_address, non_fungible_data.lp_token_address)
         }
+
+        // Compute the rewards accrued by the given LP tokens and take them from the active campaign
+        // for the coin (if any)
+        fn withdraw_rewards(
+            &mut self,
+
+            // The coin the LP tokens refer to
+            coin_address: ResourceAddress,
+
+            // Numeric ids of the LP tokens
+            lp_ids: Vec<u64>,
+        ) -> (
+            Option<Bucket>, // The rewards, if they have been paid
+            Decimal, // Rewards accrued but not paid because the campaign is out of funds
+        ) {
+
+            // Is there any active campaign for this coin?
+            // If not just quit
+            let campaign_id = match self.active_campaign.get(&coin_address) {
+                None => return (None, Decimal::ZERO),
+                Some(campaign_id) => *campaign_id,
+            };
+            let mut campaign = self.liquidity_campaigns.get_mut(&campaign_id).unwrap();
+
+            let mut rewards_amount = Decimal::ZERO;
+            let mut unknown_ids: Vec<u64> = vec![];
+            let now = Clock::current_time_rounded_to_seconds().seconds_since_unix_epoch;
+            let reward_per_coin_per_second = campaign.daily_reward_per_coin / SECONDS_PER_DAY;
+
+            // It is possible to remove multiple LP tokens in a single operation, let's loop
+            // amoung them
+            for lp_id in lp_ids.iter() {
+
+                // Search information about che LP toke
+                let lp = self.liquidity_providers.get(
+                    &(coin_address, *lp_id)
+                );
+
+                match lp {
+
+                    // If there are no informations about it there's nothing we can do (it
+                    // has already been burned). Just keep track of the issue
+                    None => unknown_ids.push(*lp_id),
+
+                    // If found, add the rewards for this LP token to the total rewards, including
+                    // the ones not paid earlier
+                    Some(lp) => {
+                        rewards_amount += lp.amount *
+                        reward_per_coin_per_second *
+                        (now - lp.last_rewards_withdraw_time);
+
+                        if lp.unpaid_campaign_id == campaign_id {
+                            rewards_amount += lp.unpaid_rewards;
+                        }
+                    },
+                }
+            }
+
+            // If there are not enough rewards for this user, don't panic, just emit an
+            // event so people know; the accrued rewards are returned so that they can be paid
+            // later
+            if campaign.rewards_vault.amount() < rewards_amount {
+                Runtime::emit_event(
+                    OutOfFundsEvent {
+                        coin_address: coin_address,
+                        lp_ids: lp_ids,
+                    }
+                );
+
+                (None, rewards_amount)
+            } else {
+
+                // Emit the event about the non found LPs
+                if unknown_ids.len() > 0 {
+                    Runtime::emit_event(
+                        UnknownRewardAmountEvent {
+                            coin_address: coin_address,
+                            lp_ids: unknown_ids,
+                        }
+                    );
+                }
+
+                // Give the user his rewards
+                (
+                    Some(
+                        campaign.rewards_vault.take_advanced(
+                            rewards_amount,
+                            WithdrawStrategy::Rounded(RoundingMode::ToZero)
+                        )
+                    ),
+                    Decimal::ZERO,
+                )
+            }
+        }
+
+        // After a RemoveLiquidity, SplitLiquidity or MergeLiquidity operation take note of the
+        // current amount of the LP tokens that still exist (partially removed, split and merged
+        // ones) and forget about the burned ones
+        // The rewards that could not be paid are assigned to the first LP token that still
+        // exists, so they are not lost when LP tokens are merged
+        fn update_liquidity_providers(
+            &mut self,
+            argument: &HookArgument,
+
+            // Rewards accrued by the LP tokens but not paid because the campaign is out of funds
+            unpaid_rewards: Decimal,
+        ) {
+            let lp_resource_manager = NonFungibleResourceManager::from(
+                argument.component.get_pool_info().lp_resource_address
+            );
+            let now = Clock::current_time_rounded_to_seconds().seconds_since_unix_epoch;
+            let campaign_id = match self.active_campaign.get(&argument.coin_address) {
+                None => 0,
+                Some(campaign_id) => *campaign_id,
+            };
+            let mut unpaid_rewards = unpaid_rewards;
+
+            for lp_id in argument.ids.iter() {
+                let non_fungible_id = NonFungibleLocalId::integer(*lp_id);
+
+                if lp_resource_manager.non_fungible_exists(&non_fungible_id) {
+                    self.liquidity_providers.insert(
+                        (argument.coin_address, *lp_id),
+                        LiquidityProvider {
+                            amount: lp_resource_manager.get_non_fungible_data::<LPData>(&non_fungible_id).deposited_coins,
+                            last_rewards_withdraw_time: now,
+                            unpaid_rewards: unpaid_rewards,
+                            unpaid_campaign_id: campaign_id,
+                        }
+                    );
+                    unpaid_rewards = Decimal::ZERO;
+                } else {
+                    self.liquidity_providers.remove(&(argument.coin_address, *lp_id));
+                }
+            }
+        }
     }
 
     impl HookInterfaceTrait for LpRewardsHook {
@@ -436,6 +598,11 @@ mod lp_rewards_hook {
             Vec<HookArgument>, // Always empty
         ) {
 
+            // Fungible LP units have no ids, there's nothing to keep track of
+            if argument.ids.len() == 0 {
+                return (hook_badge_bucket, None, vec![], vec![]);
+            }
+
             match argument.operation {
 
                 // In case of a AddLiquidity operation, just take note of the amount and mint
@@ -446,88 +613,35 @@ mod lp_rewards_hook {
                         LiquidityProvider {
                             amount: argument.amount.unwrap(),
                             last_rewards_withdraw_time: Clock::current_time_rounded_to_seconds().seconds_since_unix_epoch,
+                            unpaid_rewards: Decimal::ZERO,
+                            unpaid_campaign_id: 0,
                         }
                     );
 
                     (hook_badge_bucket, None, vec![], vec![])
                 },
 
-                // In case of a RemoveLiquidity we have to give the rewards
-                HookableOperation::RemoveLiquidity => {
-                    
-                    // Is there any active campaign for this coin?
-                    // If not just quit
-                    let campaign_id = self.active_campaign.get(&argument.coin_address);
-                    if campaign_id.is_none() {
-                        return (hook_badge_bucket, None, vec![], vec![]);
-                    }
-                    let mut campaign = self.liquidity_campaigns.get_mut(&campaign_id.unwrap()).unwrap();
-
-                    let mut rewards_amount = Decimal::ZERO;
-                    let mut unknown_ids: Vec<u64> = vec![];
-                    let now = Clock::current_time_rounded_to_seconds().seconds_since_unix_epoch;
-                    let reward_per_coin_per_second = campaign.daily_reward_per_coin / SECONDS_PER_DAY;
-
-                    // It is possible to remove multiple LP tokens in a single operation, let's loop
-                    // amoung them
-                    for lp_id in argument.ids.iter() {
-
-                        // Search information about che LP toke
-                        let lp = self.liquidity_providers.get(
-                            &(argument.coin_address, *lp_id)
-                        );
-
-                        match lp {
-
-                            // If there are no informations about it there's nothing we can do (it
-                            // has already been burned). Just keep track of the issue
-                            None => unknown_ids.push(*lp_id),
-
-                            // If found, add the rewards for this LP token to the total rewards
-                            Some(lp) => {
-                                rewards_amount += lp.amount *
-                                reward_per_coin_per_second *
-                                (now - lp.last_rewards_withdraw_time);
-                            },
-                        }
-                    }
+                // In case of a RemoveLiquidity, SplitLiquidity or MergeLiquidity we have to give
+                // the rewards accrued so far and take note of the new amounts of the LP tokens
+                // that still exist
+                HookableOperation::RemoveLiquidity |
+                HookableOperation::SplitLiquidity |
+                HookableOperation::MergeLiquidity => {
+
+                    // The second id of a SplitLiquidity is the new LP token, it has no rewards yet
+                    let rewarded_ids = match argument.operation {
+                        HookableOperation::SplitLiquidity => vec![argument.ids[0]],
+                        _ => argument.ids.clone(),
+                    };
+
+                    let (rewards_bucket, unpaid_rewards) = self.withdraw_rewards(
+                        argument.coin_address,
+                        rewarded_ids,
+                    );
 
-                    // If there are not enough rewards for this user, don't panic, just emit an
-                    // event so people know
-                    if campaign.rewards_vault.amount() < rewards_amount {
-                        Runtime::emit_event(
-                            OutOfFundsEvent {
-                                coin_address: argument.coin_address,
-                                lp_ids: argument.ids,
-                            }
-                        );
+                    self.update_liquidity_providers(&argument, unpaid_rewards);
 
-                        return (hook_badge_bucket, None, vec![], vec![]);
-                    } else {
-
-                        // Emit the event about the non found LPs
-                        if unknown_ids.len() > 0 {
-                            Runtime::emit_event(
-                                UnknownRewardAmountEvent {
-                                    coin_address: argument.coin_address,
-                                    lp_ids: unknown_ids,
-                                }
-                            );
-                        }
-
-                        // Give the user his rewards
-                        return (
-                            hook_badge_bucket,
-                            Some(
-                                campaign.rewards_vault.take_advanced(
-                                    rewards_amount,
-                                    WithdrawStrategy::Rounded(RoundingMode::ToZero)
-                                )
-                            ),
-                            vec![],
-                            vec![]
-                        );
-                    }
+                    (hook_badge_bucket, rewards_bucket, vec![], vec![])
                 },
                 _ => (hook_badge_bucket, None, vec![], vec![]),
             }
@@ -537,4 +651,3 @@ mod lp_rewards_hook {
         fn get_hook_info(&self) -> (HookExecutionRound, bool) {(2, false)}
     }
 }
-
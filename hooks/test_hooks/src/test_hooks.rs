@@ -95,17 +95,25 @@ mod test_hook0 {
             // Take one base coin from the vault and use the badge provided by RadixPump to call
             // the buy method of the pool
             if self.base_coin_vault.amount() >= Decimal::ONE {
-                let (coin_bucket, new_hook_argument, event) = hook_badge_bucket.as_ref().unwrap().authorize_with_amount(
+                let (coin_bucket, new_hook_argument, event, graduation) = hook_badge_bucket.as_ref().unwrap().authorize_with_amount(
                     1,
-                    || argument.component.buy(self.base_coin_vault.take(Decimal::ONE))
+                    || argument.component.buy(self.base_coin_vault.take(Decimal::ONE), Decimal::ZERO, None, None)
                 );
+                let mut events = vec![event];
+                let mut new_hook_arguments = vec![new_hook_argument];
+
+                // The buy may have made a bonding curve launched coin graduate
+                if let Some((graduation_hook_argument, graduation_event)) = graduation {
+                    events.push(graduation_event);
+                    new_hook_arguments.push(graduation_hook_argument);
+                }
 
                 (
                     hook_badge_bucket, // The hook_badge_bucket must always be returned!
                     Some(coin_bucket.into()), // Give the bought coins to the user
-                    vec![event], // Report the pool BuyEvent back to RadixPump
-                    vec![new_hook_argument], // Report the new HookArgument the Pool prepared back
-                                             // to RadixPump so that it can trigger more hooks
+                    events, // Report the pool BuyEvent back to RadixPump
+                    new_hook_arguments, // Report the new HookArgument the Pool prepared back to
+                                        // RadixPump so that it can trigger more hooks
                 )
             } else {
 
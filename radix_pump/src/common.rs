@@ -10,6 +10,7 @@ pub enum PoolMode {
     Normal,            // Normal operation
     Liquidation,       // Liquidation mode
     Uninitialised,     // Pool created for a pre existing coin without adding liquidity
+    Refunding,         // FairLaunch terminated below the soft cap, buyers can get their base coins back
 }
 
 // Info about the state of a pool
@@ -91,6 +92,135 @@ pub struct PoolInfo {
     // This is the same for all of the pools
     // The non fungible data of this token is the struct CreatorData
     pub creator_badge_resource_address: Option<ResourceAddress>,
+
+    // Unlock schedule of the creator allocation (FairLaunch and RandomLaunch only)
+    pub vesting_schedule: Option<VestingSchedule>,
+
+    // Part of the creator allocation that can be unlocked now (FairLaunch and RandomLaunch only)
+    pub claimable_amount: Option<Decimal>,
+
+    // Resource address of the vesting NFTs created by splitting creator allocations
+    pub vesting_nft_resource_address: Option<ResourceAddress>,
+
+    // Flash loan fee (in base coins) paid to the pool (Pool get_pool_info) or paid to the pool and
+    // the component owner (RadixPump get_pool_info) when borrowing base coins
+    pub total_base_coin_flash_loan_fee: Decimal,
+
+    // Flash loan fee percentage (on the value of the borrowed coins at the pool price) paid to the
+    // pool (Pool get_pool_info) or paid to the pool and the component owner (RadixPump
+    // get_pool_info); it adds to the fixed fees
+    pub total_flash_loan_fee_percentage: Decimal,
+
+    // Maximum percentage of the coins (or base coins) in the pool that can be borrowed with a
+    // flash loan
+    pub max_flash_loan_percentage: Decimal,
+
+    // Percentage of the base coins bought or sold paid to the coin creator
+    pub creator_fee_percentage: Decimal,
+
+    // Opening window of a quick launched coin and the time it ends (QuickLaunch only)
+    // The current extra buy fee is included in total_buy_fee_percentage
+    pub anti_sniping_window: Option<AntiSnipingWindow>,
+    pub anti_sniping_end_time: Option<i64>,
+
+    // Whether buy, sell, flash loans, add_liquidity, buy_ticket and commit_bid are paused
+    // When calling the RadixPump get_pool_info method, this is true also if the whole component
+    // is paused
+    pub paused: bool,
+
+    // If the pool issues fungible LP units instead of LP NFTs, their resource address
+    // Each unit is worth 1/total_lp of the liquidity in the pool; LP NFTs minted before the
+    // switch can still be used to remove liquidity
+    pub fungible_lp_resource_address: Option<ResourceAddress>,
+
+    // Total liquidity in the pool, the lp_share of LP NFTs and fungible LP units are fractions of
+    // it
+    pub total_lp: Decimal,
+
+    // The part of total_lp added by users (the rest is not owned by anyone)
+    pub total_users_lp: Decimal,
+
+    // lp_share locked in the RadixPump liquidity locker and its percentage of total_users_lp
+    // When calling the Pool get_pool_info method, these are always zero
+    pub locked_lp_share: Decimal,
+    pub locked_lp_percentage: Decimal,
+}
+
+// Expected result of a buy or sell operation, returned by the quote_buy and quote_sell methods of
+// the Pool component
+#[derive(Debug, ScryptoSbor, Clone)]
+pub struct PoolQuote {
+    // Base coins (buy) or coins (sell) sent to the pool
+    pub amount_in: Decimal,
+
+    // Coins (buy) or base coins (sell) received from the pool
+    pub amount_out: Decimal,
+
+    // Base coins paid to the pool as fee
+    pub pool_fee: Decimal,
+
+    // Price of the operation, this will be the pool last_price after it
+    pub price: Decimal,
+
+    // The price based on the vault amount ratio after the operation
+    pub new_pool_price: Decimal,
+
+    // Difference between the price after and before the operation as a percentage of the price
+    // before the operation
+    pub price_impact_percentage: Decimal,
+
+    // Mode of the pool at the time of the quote, the creator fee is only paid when selling in
+    // Normal mode
+    pub mode: PoolMode,
+}
+
+// Expected result of a swap, returned by the quote_buy, quote_sell and quote_swap methods of the
+// RadixPump component
+#[derive(Debug, ScryptoSbor, Clone)]
+pub struct SwapQuote {
+    // Coins the user will receive, net of all fees
+    pub amount_out: Decimal,
+
+    // Base coins paid as fee to the integrator or the component owner
+    pub platform_fee: Decimal,
+
+    // Base coins paid as fee to the creators of coin1 and coin2
+    pub creator_fee: Decimal,
+
+    // Sell operation in the pool of coin1 (None if coin1 is the base coin)
+    pub sell_quote: Option<PoolQuote>,
+
+    // Buy operation in the pool of coin2 (None if coin2 is the base coin)
+    pub buy_quote: Option<PoolQuote>,
+}
+
+// Status of a liquidity position, returned by the get_lp_position method of the Pool component
+// Values and fees are expressed in base coins at the current price
+#[derive(Debug, ScryptoSbor, Clone)]
+pub struct LPPosition {
+    // Numeric id of the LP NFT
+    pub id: u64,
+
+    // Share of the pool liquidity owned by the LP NFT
+    pub lp_share: Decimal,
+
+    // Base coins and coins that remove_liquidity would return now
+    pub redeemable_base_coins: Decimal,
+    pub redeemable_coins: Decimal,
+
+    // Value of the redeemable base coins and coins
+    pub position_value: Decimal,
+
+    // Value of the deposited base coins and coins if they had been held instead of added to the
+    // pool
+    pub hold_value: Decimal,
+
+    // Pool fees earned by the position since the liquidity was added
+    pub fees_accrued: Decimal,
+
+    // Loss of the position against holding, fees excluded (hold_value - position_value +
+    // fees_accrued); a negative value is a gain
+    pub impermanent_loss: Decimal,
 }
 
 // Non fungible data for the creator badges
@@ -101,6 +231,7 @@ pub struct CreatorData {
     pub coin_symbol: String,
     pub creation_date: Instant,
     pub lp_token_address: ResourceAddress,
+    #[mutable]
     pub key_image_url: Url,
     #[mutable]
     pub pool_mode: PoolMode,
@@ -151,6 +282,71 @@ pub enum HookableOperation {
     AddLiquidity,           // add_liquidity method
     RemoveLiquidity,        // remove_liquidity method
     Timer,                  // used by the Timer component
+    BondingCurveLaunch,     // new_bonding_curve_launch method
+    BondingCurveGraduation, // buy method reaching the graduation target
+    DutchAuction,           // launch method (dutch auction)
+    TerminateDutchAuction,  // terminate_launch method (dutch auction)
+    CommitBid,              // commit_bid method
+    RedeemBid,              // redeem_bid method
+    Refund,                 // refund method
+    ReturnBaseCoinFlashLoan, // return_base_coin_flash_loan method
+    SplitLiquidity,         // split_lp method
+    MergeLiquidity,         // merge_lp method
+}
+
+// Which side of a pool has been borrowed in a flash loan
+#[derive(Debug, ScryptoSbor, PartialEq, Clone, Copy)]
+pub enum FlashLoanSide {
+    Coin,
+    BaseCoin,
+}
+
+// Shape of the price curve of a bonding curve launched coin, where supply is the number of coins
+// sold along the curve
+#[derive(Debug, ScryptoSbor, PartialEq, Clone, Copy)]
+pub enum BondingCurveType {
+    Linear,         // price = start_price + slope * supply
+    Exponential,    // price = start_price * e^(slope * supply)
+}
+
+// When reached, a bonding curve launched coin graduates into a constant product pool
+#[derive(Debug, ScryptoSbor, PartialEq, Clone, Copy)]
+pub enum GraduationTarget {
+    Raise(Decimal),     // base coins raised along the curve
+    MarketCap(Decimal), // curve price * supply
+}
+
+// How the creator allocation of a fair or random launched coin unlocks after the cliff
+#[derive(Debug, ScryptoSbor, PartialEq, Clone, Copy)]
+pub enum VestingType {
+    Linear,     // continuously until unlocking_time
+    Steps(u32), // in this number of equal tranches evenly spaced until unlocking_time
+}
+
+// Unlock schedule of the creator allocation of a fair or random launched coin
+// The vesting period goes from the end of the launch to unlocking_time; nothing but the TGE share
+// can be unlocked before the end of the cliff, then the amount vested so far becomes available
+#[derive(Debug, ScryptoSbor, PartialEq, Clone, Copy)]
+pub struct VestingSchedule {
+    pub cliff_duration: i64,       // seconds after the end of the launch
+    pub vesting_type: VestingType,
+    pub tge_percentage: Decimal,   // share of the allocation unlocked at the end of the launch
+}
+
+// Opening window of a quick launched coin, it starts when the coin is launched
+// During the window buys are capped and pay the pool an extra fee that decreases linearly to zero
+#[derive(Debug, ScryptoSbor, PartialEq, Clone, Copy)]
+pub struct AntiSnipingWindow {
+    pub duration: i64,                            // seconds
+    pub max_buy_per_transaction: Option<Decimal>, // base coins, fees included
+    pub extra_buy_fee_percentage: Decimal,        // extra buy fee at the start of the window
+}
+
+// Who is allowed to buy during the launch phase of a gated fair or random launch
+#[derive(Debug, ScryptoSbor, PartialEq, Clone)]
+pub enum LaunchAccessRule {
+    Resource(ResourceAddress),              // a proof of this resource is required
+    NonFungibles(Vec<NonFungibleGlobalId>), // a proof of one of these non fungibles is required
 }
 
 // Event created by a pool launch method
@@ -159,8 +355,11 @@ pub struct FairLaunchStartEvent {
     pub resource_address: ResourceAddress,
     pub price: Decimal,
     pub creator_locked_percentage: Decimal,
+    pub soft_cap: Option<Decimal>,
+    pub hard_cap: Option<Decimal>,
     pub end_launch_time: i64,
     pub unlocking_time: i64,
+    pub vesting_schedule: VestingSchedule,
     pub buy_pool_fee_percentage: Decimal,
     pub sell_pool_fee_percentage: Decimal,
     pub flash_loan_pool_fee: Decimal,
@@ -176,6 +375,22 @@ pub struct FairLaunchEndEvent {
     pub coins_in_pool: Decimal,
 }
 
+// Event created by a pool terminate_launch method when a fair launch didn't reach its soft cap
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Copy)]
+pub struct SoftCapNotReachedEvent {
+    pub resource_address: ResourceAddress,
+    pub raised_base_coins: Decimal,
+    pub soft_cap: Decimal,
+}
+
+// Event created by a pool refund method
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Copy)]
+pub struct RefundEvent {
+    pub resource_address: ResourceAddress,
+    pub coin_amount: Decimal,
+    pub base_coin_amount: Decimal,
+}
+
 // Event created by a pool new_quick_launch method
 #[derive(ScryptoSbor, ScryptoEvent, Clone, Copy)]
 pub struct QuickLaunchEvent {
@@ -186,6 +401,7 @@ pub struct QuickLaunchEvent {
     pub buy_pool_fee_percentage: Decimal,
     pub sell_pool_fee_percentage: Decimal,
     pub flash_loan_pool_fee: Decimal,
+    pub anti_sniping_window: Option<AntiSnipingWindow>,
 }
 
 // Event created by a pool launch method
@@ -197,6 +413,7 @@ pub struct RandomLaunchStartEvent {
     pub coins_per_winning_ticket: Decimal,
     pub end_launch_time: i64,
     pub unlocking_time: i64,
+    pub vesting_schedule: VestingSchedule,
     pub buy_pool_fee_percentage: Decimal,
     pub sell_pool_fee_percentage: Decimal,
     pub flash_loan_pool_fee: Decimal,
@@ -212,6 +429,29 @@ pub struct RandomLaunchEndEvent {
     pub coins_in_pool: Decimal,
 }
 
+// Event created by a pool new_bonding_curve_launch function
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Copy)]
+pub struct BondingCurveLaunchEvent {
+    pub resource_address: ResourceAddress,
+    pub curve: BondingCurveType,
+    pub start_price: Decimal,
+    pub slope: Decimal,
+    pub graduation_target: GraduationTarget,
+    pub buy_pool_fee_percentage: Decimal,
+    pub sell_pool_fee_percentage: Decimal,
+    pub flash_loan_pool_fee: Decimal,
+}
+
+// Event created when a buy makes a bonding curve launched coin graduate
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Copy)]
+pub struct BondingCurveGraduationEvent {
+    pub resource_address: ResourceAddress,
+    pub price: Decimal,
+    pub raised_base_coins: Decimal,
+    pub supply: Decimal,
+    pub coins_in_pool: Decimal,
+}
+
 // Event created by a pool buy method
 #[derive(ScryptoSbor, ScryptoEvent, Clone, Copy)]
 pub struct BuyEvent {
@@ -249,11 +489,49 @@ pub struct LiquidationEvent {
 #[derive(ScryptoSbor, ScryptoEvent, Clone, Copy)]
 pub struct FlashLoanEvent {
     pub resource_address: ResourceAddress,
+    pub side: FlashLoanSide,
     pub amount: Decimal,
     pub fee_paid_to_the_pool: Decimal,
     pub integrator_id: u64,
 }
 
+// Event created by a pool launch method
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Copy)]
+pub struct DutchAuctionStartEvent {
+    pub resource_address: ResourceAddress,
+    pub start_price: Decimal,
+    pub floor_price: Decimal,
+    pub decay_period: i64,
+    pub supply_for_sale: Decimal,
+    pub end_launch_time: i64,
+    pub buy_pool_fee_percentage: Decimal,
+    pub sell_pool_fee_percentage: Decimal,
+    pub flash_loan_pool_fee: Decimal,
+}
+
+// Event created by a pool terminate_launch method
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Copy)]
+pub struct DutchAuctionEndEvent {
+    pub resource_address: ResourceAddress,
+    pub clearing_price: Decimal,
+    pub sold_coins: Decimal,
+    pub creator_proceeds: Decimal,
+    pub refunds: Decimal,
+    pub coins_in_pool: Decimal,
+}
+
+// Event created by a pool commit_bid method
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Copy)]
+pub struct CommitBidEvent {
+    pub resource_address: ResourceAddress,
+    pub price: Decimal,
+    pub coin_amount: Decimal,
+    pub bid_resource_address: ResourceAddress,
+    pub bid_id: u64,
+    pub committed_coins: Decimal,
+    pub fee_paid_to_the_pool: Decimal,
+}
+
 // Event created by a pool buy_ticket method
 #[derive(ScryptoSbor, ScryptoEvent, Clone, Copy)]
 pub struct BuyTicketEvent {
@@ -272,6 +550,10 @@ pub struct FeeUpdateEvent {
     pub buy_pool_fee_percentage: Decimal,
     pub sell_pool_fee_percentage: Decimal,
     pub flash_loan_pool_fee: Decimal,
+    pub base_coin_flash_loan_pool_fee: Decimal,
+    pub flash_loan_pool_fee_percentage: Decimal,
+    pub max_flash_loan_percentage: Decimal,
+    pub creator_fee_percentage: Decimal,
 }
 
 // Event created by a pool burn method
@@ -286,8 +568,9 @@ pub struct BurnEvent {
 pub struct AddLiquidityEvent {
     pub resource_address: ResourceAddress,
     pub amount: Decimal,
-    pub lp_id: u64,
+    pub lp_id: Option<u64>, // None if fungible LP units have been minted
     pub coins_in_pool: Decimal,
+    pub lp_share: Decimal,
 }
 
 // Event created by a pool remove_liquidity method
@@ -296,6 +579,7 @@ pub struct RemoveLiquidityEvent {
     pub resource_address: ResourceAddress,
     pub amount: Decimal,
     pub coins_in_pool: Decimal,
+    pub lp_share: Decimal,
 }
 
 // A wrapper for any event a pool can generate
@@ -315,6 +599,13 @@ pub enum AnyPoolEvent {
     BurnEvent(BurnEvent),
     AddLiquidityEvent(AddLiquidityEvent),
     RemoveLiquidityEvent(RemoveLiquidityEvent),
+    BondingCurveLaunchEvent(BondingCurveLaunchEvent),
+    BondingCurveGraduationEvent(BondingCurveGraduationEvent),
+    DutchAuctionStartEvent(DutchAuctionStartEvent),
+    DutchAuctionEndEvent(DutchAuctionEndEvent),
+    CommitBidEvent(CommitBidEvent),
+    SoftCapNotReachedEvent(SoftCapNotReachedEvent),
+    RefundEvent(RefundEvent),
 }
 
 // Non fungible data for the ticket NFT used in RandomLaunch
@@ -324,14 +615,31 @@ pub struct TicketData {
     pub buy_date: Instant,
 }
 
+// Non fungible data for the bid NFT used in DutchAuction
+#[derive(Debug, ScryptoSbor, NonFungibleData)]
+pub struct AuctionBidData {
+    pub coin_resource_address: ResourceAddress,
+    pub bid_date: Instant,
+    pub price: Decimal,
+    pub coin_amount: Decimal,
+}
+
 // Non fungible data for the liquidity tokens of a pool
 #[derive(Debug, ScryptoSbor, NonFungibleData)]
 pub struct LPData {
+    #[mutable]
     pub deposited_coins: Decimal,
+    #[mutable]
     pub deposited_base_coins: Decimal,
+    #[mutable]
     pub lp_share: Decimal,
     pub date: Instant,
     pub coin_resource_address: ResourceAddress,
+
+    // Value of the pool fee_growth_per_lp when the liquidity was added (weighted average of the
+    // merged LP NFTs after a merge)
+    #[mutable]
+    pub fee_growth_checkpoint: Decimal,
 }
 
 // This is a brief description of an opertation done on a pool, this argument is passed to a hook
@@ -350,9 +658,19 @@ pub struct HookArgument {
     // if RedeemWinningTicket it is the number of winning tickets reedemed (integer)
     // if AddLiquidity it is the amount of coins added to the pool
     // if RemoveLiquidity it is the amount of coins withdrawn from the pool
+    // if SplitLiquidity it is the percentage of the liquidity moved to the new liquidity token
+    // if MergeLiquidity it is None
     // if FairLaunch or RandomLaunch it is None
     // if TerminateFairLaunch or QuickLaunch or TerminateRandomLaunch it is the total supply of the coin
+    // if Refund it is the amount of coins burned
+    // if BondingCurveLaunch it is None
+    // if BondingCurveGraduation it is the total supply of the coin
+    // if DutchAuction it is None
+    // if TerminateDutchAuction it is the total supply of the coin
+    // if CommitBid it is the amount of coins requested by the bid
+    // if RedeemBid it is the amount of coins received by redeeming the bids
     // if ReturnFlashLoan it is the amount of coins returned
+    // if ReturnBaseCoinFlashLoan it is the amount of base coins returned (fees excluded)
     pub amount: Option<Decimal>,
 
     pub mode: PoolMode,
@@ -362,8 +680,14 @@ pub struct HookArgument {
     // if BuyTicket it is the list of ids of the bought tickets
     // if RedeemLosingTicket it is the list of ids of the redeemed losing tickets
     // if RedeemWinningTicket it is the list of ids of the redeemed winning tickets
+    // if CommitBid it is the id of the minted bid
+    // if RedeemBid it is the list of ids of the redeemed bids
     // if AddLiquidity it is the id of the minted liquidity token
-    // if RemoveLiquidity it is the list of the ids of the burned liquidity tokens
+    // if RemoveLiquidity it is the list of the ids of the burned (or partially redeemed) liquidity
+    // tokens
+    // if SplitLiquidity it is the id of the split liquidity token followed by the id of the new one
+    // if MergeLiquidity it is the id of the resulting liquidity token followed by the ids of the
+    // burned ones
     // if Timer it is the id of the TimerBadge
     // in any other case it is just an empty array
     pub ids: Vec<u64>,
@@ -432,27 +756,76 @@ define_interface! {
 define_interface! {
     RadixPumpPool impl [ScryptoStub, Trait, ScryptoTestStub] {
 
-// THE FOLLOWING METHOD REQUIRES NO AUTHENTICATION, IT CAN BE CALLED BY ANYONE
+// THE FOLLOWING METHODS REQUIRE NO AUTHENTICATION, THEY CAN BE CALLED BY ANYONE
 
         // Return detailed information about the pool status
         fn get_pool_info(&self) -> PoolInfo;
 
+        // Return the time weighted average price of the coin over the last window_seconds seconds
+        fn get_twap(
+            &self,
+            window_seconds: i64,
+        ) -> Decimal;
+
+        // Return the status of the liquidity positions represented by the given LP NFTs
+        fn get_lp_position(
+            &self,
+            ids: Vec<u64>,
+        ) -> Vec<LPPosition>;
+
+        // Return the expected result of a buy operation without executing it
+        fn quote_buy(
+            &self,
+            base_coin_amount: Decimal,
+        ) -> PoolQuote;
+
+        // Return the expected result of a sell operation without executing it
+        fn quote_sell(
+            &self,
+            coin_amount: Decimal,
+        ) -> PoolQuote;
+
+        // Return the amount of base coins needed to buy exactly coin_amount_out coins
+        fn get_buy_amount_in(
+            &self,
+            coin_amount_out: Decimal,
+        ) -> Decimal;
+
+        // Return the amount of coins to sell to receive exactly base_coin_amount_out base coins
+        fn get_sell_amount_in(
+            &self,
+            base_coin_amount_out: Decimal,
+        ) -> Decimal;
+
 // THE FOLLOWING METHODS CAN ONLY BE CALLED BY ROUND 0 AND 1 HOOKS AND BY THE RadixPump COMPONENT
 
         // Call this method to buy coins with base coins
+        // The operation fails if less than min_amount_out coins are bought or if it is executed
+        // after the deadline
+        // During the launch phase of a gated launch a proof of the allowlisted badge is required
+        // If the buy makes a bonding curve launched coin reach its graduation target, the pool
+        // graduates and the BondingCurveGraduation HookArgument and event are returned too
         fn buy(
             &mut self,
             base_coin_bucket: FungibleBucket,
+            min_amount_out: Decimal,
+            deadline: Option<Instant>,
+            buyer_proof: Option<Proof>,
         ) -> (
             FungibleBucket,
             HookArgument,
             AnyPoolEvent,
+            Option<(HookArgument, AnyPoolEvent)>,
         );
 
         // Call this method to sell coins for base coins
+        // The operation fails if less than min_amount_out base coins are received or if it is
+        // executed after the deadline
         fn sell(
             &mut self,
             coin_bucket: FungibleBucket,
+            min_amount_out: Decimal,
+            deadline: Option<Instant>,
         ) -> (
             FungibleBucket,
             HookArgument,
@@ -460,10 +833,12 @@ define_interface! {
         );
 
         // Call this method to buy tickets for a reandom launched coin launch phase
+        // If the launch is gated a proof of the allowlisted badge is required
         fn buy_ticket(
             &mut self,
             amount: u32,
             base_coin_bucket: FungibleBucket,
+            buyer_proof: Option<Proof>,
         ) -> (
             Bucket,
             NonFungibleBucket,
@@ -483,6 +858,39 @@ define_interface! {
             Option<HookArgument>,
         );
 
+        // Call this method to commit base coins to a dutch auction at the current auction price
+        fn commit_bid(
+            &mut self,
+            base_coin_bucket: FungibleBucket,
+        ) -> (
+            Bucket,
+            NonFungibleBucket,
+            HookArgument,
+            AnyPoolEvent,
+        );
+
+        // Users who took part in a dutch auction can call this method to get their coins and the
+        // refund of the difference between their bid price and the clearing price
+        fn redeem_bid(
+            &mut self,
+            bid_bucket: Bucket,
+        ) -> (
+            FungibleBucket, // base coin bucket
+            FungibleBucket, // coin bucket
+            HookArgument,
+        );
+
+        // Buyers of a fair launch that didn't reach its soft cap can call this method to burn their
+        // coins and get their base coins back (fees excluded)
+        fn refund(
+            &mut self,
+            coin_bucket: FungibleBucket,
+        ) -> (
+            FungibleBucket, // base coin bucket
+            HookArgument,
+            AnyPoolEvent,
+        );
+
         // Users can add liquidity to the pool by calling this method.
         // Both base coins and coins must be provided
         fn add_liquidity(
@@ -490,7 +898,7 @@ define_interface! {
             base_coin_bucket: Bucket,
             coin_bucket: Bucket,
         ) -> (  
-            NonFungibleBucket,
+            Bucket,
             Option<Bucket>,
             HookArgument, 
             AnyPoolEvent,
@@ -508,6 +916,40 @@ define_interface! {
             AnyPoolEvent,
         );
 
+        // Users can invoke this method to get back a percentage of the liquidity represented by
+        // one or more LP NFTs
+        fn remove_liquidity_partial(
+            &mut self,
+            lp_bucket: Bucket,
+            percentage: Decimal,
+        ) -> (
+            Bucket,
+            FungibleBucket,
+            Option<FungibleBucket>,
+            HookArgument,
+            AnyPoolEvent,
+        );
+
+        // Split an LP NFT in two
+        fn split_lp(
+            &mut self,
+            lp_bucket: Bucket,
+            percentage: Decimal,
+        ) -> (
+            Bucket,
+            Bucket,
+            HookArgument,
+        );
+
+        // Merge multiple LP NFTs into one
+        fn merge_lp(
+            &mut self,
+            lp_bucket: Bucket,
+        ) -> (
+            Bucket,
+            HookArgument,
+        );
+
 // THE FOLLOWING METHODS CAN ONLY BE CALLED BY THE RadixPump COMPONENT
 
         // The creator of a fair or random launched coin can use this method to start the launch
@@ -524,7 +966,8 @@ define_interface! {
 
         // The creator of a fair or random launched coin can use this method to terminate the launch
         // phase            
-        // In case of a fair launch the mode goes from Launching to Normal in just one step
+        // In case of a fair launch the mode goes from Launching to Normal in just one step (or to
+        // Refunding if the soft cap has not been reached)
         // In case of a random launch there are 4 possibilities:
         // - Launching -> Normal (if sold tickets <= winning tickets, everybody won)
         // - Launching -> TerminatingLaunch (request random data to the RandomComponent)
@@ -539,12 +982,31 @@ define_interface! {
 
         // The creator of a fair or random launched coin can use this method to get (part of) his
         // allocation
-        // The creator allocation is time locked and has an unlock schedule linear with time
+        // The creator allocation is time locked and follows the vesting schedule chosen at creation
         fn unlock(
             &mut self,
             amount: Option<Decimal>,
         ) -> FungibleBucket;
 
+        // The creator of a fair or random launched coin can use this method to move part of his
+        // still locked allocation to a vesting NFT
+        fn split_vesting(
+            &mut self,
+            amount: Decimal,
+        ) -> (
+            Decimal,
+            Decimal,
+        );
+
+        // The holder of a vesting NFT can use this method (through RadixPump) to get (part of) the
+        // coins of his position
+        fn unlock_vesting(
+            &mut self,
+            allocation: Decimal,
+            unlocked_amount: Decimal,
+            amount: Option<Decimal>,
+        ) -> FungibleBucket;
+
         // Call this method to put the pool in Liquidation mode (authentication is in RadixPump
         // component)
         // The goal of the liquidation mode is to get all of the coins into the pool and all of the
@@ -555,16 +1017,41 @@ define_interface! {
         );
 
         // Get a flash loan. It is RadixPump responsibility to ensure the loan will be returned
+        // The value of the loan in base coins at the current pool price is returned too
         fn get_flash_loan(
             &mut self,
             amount: Decimal,
-        ) -> FungibleBucket;
+        ) -> (
+            FungibleBucket,
+            Decimal,
+        );
 
-        // Return a previoulsy received flash loan
+        // Return a previoulsy received flash loan, the pool takes its fee from base_coin_bucket and
+        // returns the remaining base coins unless keep_all is true
         fn return_flash_loan(
             &mut self,
             base_coin_bucket: Bucket,
             coin_bucket: Bucket,
+            loan_value: Decimal,
+            keep_all: bool,
+        ) -> (
+            Option<Bucket>,
+            HookArgument,
+            AnyPoolEvent,
+        );
+
+        // Get a flash loan of base coins. It is RadixPump responsibility to ensure the loan will be
+        // returned
+        fn get_base_coin_flash_loan(
+            &mut self,
+            amount: Decimal,
+        ) -> FungibleBucket;
+
+        // Return a previoulsy received base coin flash loan, the bucket contains the borrowed base
+        // coins plus the fee paid to the pool
+        fn return_base_coin_flash_loan(
+            &mut self,
+            base_coin_bucket: Bucket,
         ) -> (
             HookArgument,
             AnyPoolEvent,
@@ -576,6 +1063,10 @@ define_interface! {
             buy_pool_fee_percentage: Decimal,
             sell_pool_fee_percentage: Decimal,
             flash_loan_pool_fee: Decimal,
+            base_coin_flash_loan_pool_fee: Decimal,
+            flash_loan_pool_fee_percentage: Decimal,
+            max_flash_loan_percentage: Decimal,
+            creator_fee_percentage: Decimal,
         ) -> AnyPoolEvent;
 
         // The creator of a quick launched coin can use this method to burn excess coins in the
@@ -584,5 +1075,26 @@ define_interface! {
             &mut self,
             amount: Decimal,
         ) -> AnyPoolEvent;
+
+        // Pause or unpause the pool. Authentication is managed by RadixPump
+        fn set_paused(
+            &mut self,
+            paused: bool,
+        );
+
+        // Pause or unpause the pool because the whole component has been paused or unpaused.
+        // Authentication is managed by RadixPump
+        fn set_globally_paused(
+            &mut self,
+            globally_paused: bool,
+        );
+
+        // Switch the pool to fungible LP units. Authentication is managed by RadixPump
+        fn enable_fungible_lp(
+            &mut self,
+            coin_creator_badge_rule: CompositeRequirement,
+            owner_badge_address: ResourceAddress,
+            dapp_definition: ComponentAddress,
+        ) -> ResourceAddress;
     }
 }
@@ -229,6 +229,16 @@ pub fn string_to_operation(operation: &String) -> HookableOperation {
         "RedeemLosingTicket" => HookableOperation::RedeemLosingTicket,
         "AddLiquidity" => HookableOperation::AddLiquidity,
         "RemoveLiquidity" => HookableOperation::RemoveLiquidity,
+        "BondingCurveLaunch" => HookableOperation::BondingCurveLaunch,
+        "BondingCurveGraduation" => HookableOperation::BondingCurveGraduation,
+        "DutchAuction" => HookableOperation::DutchAuction,
+        "TerminateDutchAuction" => HookableOperation::TerminateDutchAuction,
+        "CommitBid" => HookableOperation::CommitBid,
+        "RedeemBid" => HookableOperation::RedeemBid,
+        "Refund" => HookableOperation::Refund,
+        "ReturnBaseCoinFlashLoan" => HookableOperation::ReturnBaseCoinFlashLoan,
+        "SplitLiquidity" => HookableOperation::SplitLiquidity,
+        "MergeLiquidity" => HookableOperation::MergeLiquidity,
         _ => Runtime::panic("Operation not found".to_string()),
     }
 }
@@ -60,6 +60,19 @@ impl LoanSafeVault {
         )
     }
 
+    // Take coins from the Vault with the specified withdraw strategy
+    pub fn take_advanced(&mut self, amount: Decimal, withdraw_strategy: WithdrawStrategy) -> FungibleBucket {
+        self.vault.take_advanced(
+            amount,
+            withdraw_strategy,
+        )
+    }
+
+    // Get the amount of the ongoing loan (zero if there's no ongoing loan)
+    pub fn ongoing_loan(&self) -> Decimal {
+        self.ongoing_loan
+    }
+
     // Take coins from the Vault and take note of the taken amount
     pub fn get_loan(&mut self, amount: Decimal) -> FungibleBucket {
 
@@ -21,11 +21,20 @@ use scrypto_interface::*;
      Winning tickets will receive a share of coins while losers get a refund.
      The coin creator receives the equivalent of a winning ticket but his allocation is time locked.
      The creator also receives the launch sale proceeds (fees excluded).
+   - BondingCurve: during the launch phase coins are minted and sold along a price curve (linear or exponential).
+     Users can also sell them back along the same curve.
+     When a target raise or market cap is reached the pool graduates: the raised base coins become the liquidity of a
+     constant product pool.
+   - DutchAuction: during an initial launch phase the price decreases from a start price to a floor price and users
+     commit base coins at the current price to request a share of a fixed supply.
+     At the end of the launch phase everybody pays the same clearing price and overpayments are refunded.
+     The creator receives the launch sale proceeds (fees excluded).
    It is also possible to create a pool for an already existing coin, this way there will be no launch phase.
 
    Depending on the launch type and his history a Pool can be in a number of different modes of operation.
    - Quick launched coins start directly in the Normal mode
-   - Fair and Random launched coins start in the WaitingForLaunch mode
+   - Fair, Random and DutchAuction launched coins start in the WaitingForLaunch mode
+   - BondingCurve launched coins start directly in the Launching mode
    - Pools for externally created coins start in the Uninitialised mode
    These are all of the possible modes:
    - WaitingForLaunch: FairLaunch or RandomLaunch not started yet
@@ -47,6 +56,16 @@ struct QuickLaunchDetails {
     // The number of ignored coins can decrease over time as users buy the coin or the creator
     // burns them.
     ignored_coins: Decimal,
+
+    // Opening window set by the creator (None = no anti-sniping protection)
+    anti_sniping_window: Option<AntiSnipingWindow>,
+
+    // When the opening window ends
+    anti_sniping_end_time: i64,
+
+    // Hash of the last transaction that bought during the opening window and the base coins it
+    // spent; it is used to enforce the per transaction cap
+    anti_sniping_last_transaction: Option<(Hash, Decimal)>,
 }
 
 // Additional state for FairLaunched pools
@@ -62,12 +81,29 @@ struct FairLaunchDetails {
     // allocation can only be expressed as a percentage
     creator_locked_percentage: Decimal,
 
+    // Minimum amount of base coins (fees included) to raise during the launch phase; if it is not
+    // reached, buyers can get a refund and the creator allocation is never minted
+    soft_cap: Option<Decimal>,
+
+    // Maximum amount of base coins (fees included) that can be raised during the launch phase
+    hard_cap: Option<Decimal>,
+
+    // Who is allowed to buy during the launch phase (None = everybody)
+    access_rule: Option<LaunchAccessRule>,
+
+    // Maximum amount of base coins (fees included) a single holder can spend during the launch
+    // phase
+    max_buy_per_holder: Option<Decimal>,
+
     // Vault containing the creator allocation (time locked)
     locked_vault: FungibleVault,
 
     // When the creator allocation will be fully unlocked
     unlocking_time: i64,
 
+    // How the creator allocation unlocks until unlocking_time
+    vesting_schedule: VestingSchedule,
+
     // Creator allocation
     // The supply of the coin is unknown until the end of the launch phase so the creator
     // allocation is unknown at start
@@ -76,6 +112,13 @@ struct FairLaunchDetails {
     // Part of the creator allocation that has been already withdrawn
     unlocked_amount: Decimal,
 
+    // Part of the creator allocation moved to vesting NFTs by the creator
+    split_allocation: Decimal,
+
+    // Part of split_allocation that has been already withdrawn by the vesting NFT holders
+    // (unlocked_amount includes it too)
+    split_unlocked_amount: Decimal,
+
     // Resource manager to mint the coins when they are bought during the launch phase
     resource_manager: FungibleResourceManager,
 }
@@ -97,6 +140,9 @@ struct RandomLaunchDetails {
     // When the creator allocation will be fully unlocked
     unlocking_time: i64,
 
+    // How the creator allocation unlocks until unlocking_time
+    vesting_schedule: VestingSchedule,
+
     // Price of a ticket for taking part in the launch phase extraction (includes fee)
     ticket_price: Decimal,
 
@@ -106,6 +152,12 @@ struct RandomLaunchDetails {
     // How many coins a winning ticket will receive
     coins_per_winning_ticket: Decimal,
 
+    // Who is allowed to buy tickets during the launch phase (None = everybody)
+    access_rule: Option<LaunchAccessRule>,
+
+    // Maximum amount of base coins (fees included) a single holder can spend in tickets
+    max_buy_per_holder: Option<Decimal>,
+
     // How many tickets have been sold
     sold_tickets: u32,
 
@@ -118,6 +170,13 @@ struct RandomLaunchDetails {
     // Part of the creator allocation that has been already withdrawn
     unlocked_amount: Decimal,
 
+    // Part of the creator allocation moved to vesting NFTs by the creator
+    split_allocation: Decimal,
+
+    // Part of split_allocation that has been already withdrawn by the vesting NFT holders
+    // (unlocked_amount includes it too)
+    split_unlocked_amount: Decimal,
+
     // Whether to extract winners or losers
     // If the number of winners is less than half of the sold tickets it's cheaper to extract
     // winners, if it's bigger it's cheaper to extract losers
@@ -145,6 +204,63 @@ struct RandomLaunchDetails {
     buy_fee_during_launch: Decimal,
 }
 
+// Additional state for BondingCurve launched pools
+#[derive(Debug, ScryptoSbor, PartialEq)]
+struct BondingCurveLaunchDetails {
+
+    // Shape and parameters of the price curve
+    curve: BondingCurveType,
+    start_price: Decimal,
+    slope: Decimal,
+
+    // When this target is reached the pool graduates to the Normal mode
+    graduation_target: GraduationTarget,
+
+    // Number of coins sold along the curve and not sold back; this is the position on the curve
+    supply_on_curve: Decimal,
+
+    // Resource manager to mint the coins when they are bought during the launch phase
+    resource_manager: FungibleResourceManager,
+}
+
+// Additional state for DutchAuction launched pools
+#[derive(ScryptoSbor, PartialEq)]
+struct DutchAuctionLaunchDetails {
+
+    // During the launch phase the auction price decreases linearly from start_price to floor_price
+    // in decay_period seconds, then it stays at floor_price
+    start_price: Decimal,
+    floor_price: Decimal,
+    decay_period: i64,
+
+    // Maximum number of coins sold during the auction
+    supply_for_sale: Decimal,
+
+    // Launch phase timings
+    start_launch_time: i64,
+    end_launch_time: i64,
+
+    // Coins requested by all of the bids and base coins paid for them at the bid price (fees
+    // excluded)
+    committed_coins: Decimal,
+    committed_base_coins: Decimal,
+
+    // The single price paid by all of the bidders; it is set when the whole supply for sale has
+    // been requested or at the end of the launch phase
+    clearing_price: Decimal,
+
+    // Coins sold during the auction and overpayments, waiting for the bidders to redeem their bids
+    sold_coins_vault: FungibleVault,
+    refunds_vault: FungibleVault,
+
+    // Resource manager to mint the coins at the end of the launch phase
+    resource_manager: FungibleResourceManager,
+
+    // Resource manager to mint bids and numeric id of the last minted one
+    bid_resource_manager: NonFungibleResourceManager,
+    last_bid_id: u64,
+}
+
 // This enum can contain different structs (QuickLaunchDetails, FairLaunchDetails or RandomLaunchDetails) to
 // store information that are needed only for a specfic launch type.
 #[derive(ScryptoSbor, PartialEq)]
@@ -153,6 +269,19 @@ enum LaunchType {
     Fair(FairLaunchDetails),
     Random(RandomLaunchDetails),
     AlreadyExistingCoin,
+    BondingCurve(BondingCurveLaunchDetails),
+    DutchAuction(DutchAuctionLaunchDetails),
+}
+
+// A snapshot of the price accumulator, used to compute the time weighted average price
+#[derive(Debug, ScryptoSbor, Clone, Copy)]
+struct PriceObservation {
+
+    // When the snapshot was taken (seconds since Unix epoch)
+    timestamp: i64,
+
+    // Sum of price * seconds since the pool creation
+    price_cumulative: Decimal,
 }
 
 // Limits on the usage of the external random data source
@@ -162,17 +291,52 @@ static MAX_CALLS_TO_RANDOM: u32 = 10;
 // How many coins to unignore for each bought coin (quick launch only)
 static UNIGNORE_FACTOR: Decimal = dec!["0.3"];
 
+// How many price observations are kept to compute the time weighted average price
+static MAX_PRICE_OBSERVATIONS: u64 = 200;
+
+// Natural logarithm of 2 and number of terms of the series used in bonding curve computations
+static LN_2: PreciseDecimal = pdec!("0.693147180559945309417232121458176568");
+static SERIES_TERMS: u32 = 40;
+
 // Some common error message
 static MODE_NOT_ALLOWED: &str = "Not allowed in this mode";
 static TYPE_NOT_ALLOWED: &str = "Not allowed for this launch type";
 static SHOULD_NOT_HAPPEN: &str = "Should not happen";
 
+// Make sure a buy, sell or swap operation is not executed after the deadline specified by the user
+pub fn check_deadline(deadline: Option<Instant>) {
+    match deadline {
+        None => {},
+        Some(deadline) => assert!(
+            !Clock::current_time_is_strictly_after(deadline, TimePrecision::Second),
+            "Deadline expired",
+        ),
+    }
+}
+
+// Make sure a buy, sell or swap operation returns at least the minimum amount specified by the user
+pub fn check_min_amount_out(
+    amount_out: Decimal,
+    min_amount_out: Decimal,
+) {
+    assert!(
+        amount_out >= min_amount_out,
+        "Received amount {} is lower than the minimum accepted {}",
+        amount_out,
+        min_amount_out,
+    );
+}
+
 #[blueprint_with_traits]
 #[types(
     u64,
     bool,
     LPData,
     TicketData,
+    PriceObservation,
+    AuctionBidData,
+    NonFungibleGlobalId,
+    Decimal,
 )]
 mod pool {
 
@@ -204,22 +368,41 @@ mod pool {
         },
         methods {
             get_pool_info => PUBLIC;
+            quote_buy => PUBLIC;
+            quote_sell => PUBLIC;
+            get_buy_amount_in => PUBLIC;
+            get_sell_amount_in => PUBLIC;
+            get_twap => PUBLIC;
+            get_lp_position => PUBLIC;
 
             buy => restrict_to: [proxy, hook];
             sell => restrict_to: [proxy, hook];
             buy_ticket => restrict_to: [proxy, hook];
             redeem_ticket => restrict_to: [proxy, hook];
+            commit_bid => restrict_to: [proxy, hook];
+            redeem_bid => restrict_to: [proxy, hook];
+            refund => restrict_to: [proxy, hook];
             add_liquidity => restrict_to: [proxy, hook];
             remove_liquidity => restrict_to: [proxy, hook];
+            remove_liquidity_partial => restrict_to: [proxy, hook];
+            split_lp => restrict_to: [proxy, hook];
+            merge_lp => restrict_to: [proxy, hook];
 
             launch => restrict_to: [proxy];
             terminate_launch => restrict_to: [proxy];
             unlock => restrict_to: [proxy];
+            split_vesting => restrict_to: [proxy];
+            unlock_vesting => restrict_to: [proxy];
             set_liquidation_mode => restrict_to: [proxy];
             get_flash_loan => restrict_to: [proxy];
             return_flash_loan => restrict_to: [proxy];
+            get_base_coin_flash_loan => restrict_to: [proxy];
+            return_base_coin_flash_loan => restrict_to: [proxy];
             update_pool_fees => restrict_to: [proxy];
             burn => restrict_to: [proxy];
+            set_paused => restrict_to: [proxy];
+            set_globally_paused => restrict_to: [proxy];
+            enable_fungible_lp => restrict_to: [proxy];
 
             random_callback => PUBLIC;
             random_on_error => PUBLIC;
@@ -227,8 +410,8 @@ mod pool {
     }
 
     struct Pool {
-        // Vaults for keeping base coins and coins
-        base_coin_vault: FungibleVault,
+        // Vaults for keeping base coins and coins, both of them can be flash borrowed
+        base_coin_vault: LoanSafeVault,
         coin_vault: LoanSafeVault,
 
         // Current pool mode
@@ -241,6 +424,19 @@ mod pool {
         buy_pool_fee_percentage: Decimal,
         sell_pool_fee_percentage: Decimal,
         flash_loan_pool_fee: Decimal,
+        base_coin_flash_loan_pool_fee: Decimal,
+
+        // Flash loan fee percentage on the value of the borrowed coins (or base coins), it adds to
+        // the fixed fees
+        flash_loan_pool_fee_percentage: Decimal,
+
+        // Maximum percentage of the coins (or base coins) in the pool that can be borrowed with a
+        // flash loan
+        max_flash_loan_percentage: Decimal,
+
+        // Percentage of the base coins bought or sold paid to the coin creator; RadixPump collects
+        // it, the pool only keeps track of it
+        creator_fee_percentage: Decimal,
 
         // Launch type with variants
         launch: LaunchType,
@@ -266,30 +462,51 @@ mod pool {
         // This variable is only used in liquidation mode to keep track of the coins belonging to the
         // liquidity providers
         base_coins_to_lp_providers: Decimal,
+
+        // Sum of price * seconds since the pool creation, updated by buy, sell, add_liquidity and
+        // remove_liquidity before changing the pool status
+        price_cumulative: Decimal,
+
+        // When price_cumulative was last updated
+        last_observation_time: i64,
+
+        // Ring buffer of the last MAX_PRICE_OBSERVATIONS snapshots of price_cumulative
+        price_observations: KeyValueStore<u64, PriceObservation>,
+
+        // Total number of snapshots taken so far
+        price_observations_count: u64,
+
+        // Base coins spent by each allowlisted holder during the launch phase of a gated launch
+        // This is only needed by FairLaunch and RandomLaunch but, as for extracted_tickets, it
+        // can't be put in the launch details
+        launch_purchases: KeyValueStore<NonFungibleGlobalId, Decimal>,
+
+        // A paused pool doesn't allow buy, sell, flash loans, add_liquidity, buy_ticket and
+        // commit_bid; unlike the Liquidation mode this is reversible
+        paused: bool,
+
+        // Set by RadixPump when the whole component is paused, it has the same effect as paused
+        // but it's kept separate so unpausing the component doesn't unpause this pool
+        globally_paused: bool,
+
+        // If set, add_liquidity mints fungible LP units (one unit for each unit of lp_share)
+        // instead of LP NFTs
+        fungible_lp_resource_manager: Option<FungibleResourceManager>,
+
+        // Cumulative base coin fees paid to the pool per unit of total_lp, updated by buy, sell
+        // and flash loans in Normal mode
+        fee_growth_per_lp: Decimal,
     }
 
     impl RadixPumpPoolInterfaceTrait for Pool {
 
-// THE FOLLOWING METHOD REQUIRES NO AUTHENTICATION, IT CAN BE CALLED BY ANYONE
+// THE FOLLOWING METHODS REQUIRE NO AUTHENTICATION, THEY CAN BE CALLED BY ANYONE
 
         // Return detailed information about the pool status
         fn get_pool_info(&self) -> PoolInfo {
 
             let coin_amount = self.coins_in_pool();
-            let price = match self.mode {
-                PoolMode::Normal => {
-                    if coin_amount > Decimal::ZERO {
-                        (PreciseDecimal::from(self.base_coin_vault.amount()) /
-                            PreciseDecimal::from(coin_amount))
-                            .checked_truncate(RoundingMode::ToZero)
-                            .unwrap()
-                    } else {
-                        Decimal::ZERO
-                    }
-                },
-                PoolMode::Uninitialised => Decimal::ZERO,
-                _ => self.last_price,
-            };
+            let price = self.current_price();
 
             // Not launched pools have zero LP
             let coin_lp_ratio: Decimal;
@@ -306,7 +523,7 @@ mod pool {
                 last_price: self.last_price,
                 price: price,
                 circulating_supply: self.circulating_supply(),
-                total_buy_fee_percentage: self.buy_pool_fee_percentage,
+                total_buy_fee_percentage: self.buy_pool_fee_percentage + self.anti_sniping_fee_percentage(),
                 total_sell_fee_percentage: self.sell_pool_fee_percentage,
                 total_flash_loan_fee: self.flash_loan_pool_fee,
                 pool_mode: self.mode,
@@ -315,6 +532,7 @@ mod pool {
                 end_launch_time: match &self.launch {
                     LaunchType::Fair(fair_launch) => Some(fair_launch.end_launch_time),
                     LaunchType::Random(random_launch) => Some(random_launch.end_launch_time),
+                    LaunchType::DutchAuction(dutch_auction) => Some(dutch_auction.end_launch_time),
                     _ => None,
                 },
                 unlocking_time: match &self.launch {
@@ -349,9 +567,410 @@ mod pool {
                 flash_loan_nft_resource_address: None,
                 hooks_badge_resource_address: None,
                 creator_badge_resource_address: None,
+
+                vesting_schedule: match &self.launch {
+                    LaunchType::Fair(fair_launch) => Some(fair_launch.vesting_schedule),
+                    LaunchType::Random(random_launch) => Some(random_launch.vesting_schedule),
+                    _ => None,
+                },
+
+                // The creator allocation (vesting NFTs excluded) can only be unlocked in Normal mode
+                claimable_amount: match &self.launch {
+                    LaunchType::Fair(_) | LaunchType::Random(_) if self.mode != PoolMode::Normal => Some(Decimal::ZERO),
+                    LaunchType::Fair(fair_launch) => Some(
+                        min(
+                            Pool::vested_amount(
+                                &fair_launch.vesting_schedule,
+                                fair_launch.initial_locked_amount - fair_launch.split_allocation,
+                                fair_launch.end_launch_time,
+                                fair_launch.unlocking_time,
+                            ) - (fair_launch.unlocked_amount - fair_launch.split_unlocked_amount),
+                            fair_launch.locked_vault.amount(),
+                        )
+                    ),
+                    LaunchType::Random(random_launch) => Some(
+                        min(
+                            Pool::vested_amount(
+                                &random_launch.vesting_schedule,
+                                random_launch.coins_per_winning_ticket - random_launch.split_allocation,
+                                random_launch.end_launch_time,
+                                random_launch.unlocking_time,
+                            ) - (random_launch.unlocked_amount - random_launch.split_unlocked_amount),
+                            random_launch.locked_vault.amount(),
+                        )
+                    ),
+                    _ => None,
+                },
+
+                // This information will be added by the proxy
+                vesting_nft_resource_address: None,
+
+                total_base_coin_flash_loan_fee: self.base_coin_flash_loan_pool_fee,
+
+                total_flash_loan_fee_percentage: self.flash_loan_pool_fee_percentage,
+                max_flash_loan_percentage: self.max_flash_loan_percentage,
+                creator_fee_percentage: self.creator_fee_percentage,
+
+                anti_sniping_window: match &self.launch {
+                    LaunchType::Quick(quick_launch) => quick_launch.anti_sniping_window,
+                    _ => None,
+                },
+                anti_sniping_end_time: match &self.launch {
+                    LaunchType::Quick(quick_launch) if quick_launch.anti_sniping_window.is_some() =>
+                        Some(quick_launch.anti_sniping_end_time),
+                    _ => None,
+                },
+
+                paused: self.paused || self.globally_paused,
+                fungible_lp_resource_address: self.fungible_lp_resource_manager
+                    .as_ref()
+                    .map(|fungible_lp_resource_manager| fungible_lp_resource_manager.address()),
+
+                total_lp: self.total_lp,
+                total_users_lp: self.total_users_lp,
+
+                // This information will be added by the proxy
+                locked_lp_share: Decimal::ZERO,
+                locked_lp_percentage: Decimal::ZERO,
             }
         }
 
+        // Return the time weighted average price of the coin over the last window_seconds
+        // seconds; unlike last_price and price, it can't be moved by operations happening in the
+        // same transaction (i.e. using a flash loan)
+        fn get_twap(
+            &self,
+            window_seconds: i64,
+        ) -> Decimal {
+            assert!(
+                window_seconds > 0,
+                "Window must be positive",
+            );
+
+            let now = Clock::current_time_rounded_to_seconds().seconds_since_unix_epoch;
+            let target_time = now - window_seconds;
+
+            // The price has not changed since the last update of the accumulator
+            let current_price = self.current_price();
+            let price_cumulative_now = self.price_cumulative + current_price * (now - self.last_observation_time);
+
+            let mut newer = PriceObservation {
+                timestamp: self.last_observation_time,
+                price_cumulative: self.price_cumulative,
+            };
+
+            // Find the value of the accumulator at target_time
+            let price_cumulative_then = match newer.timestamp <= target_time {
+                true => newer.price_cumulative + current_price * (target_time - newer.timestamp),
+
+                // Go back through the observations until the one preceding target_time is found,
+                // then interpolate between it and the following one
+                false => {
+                    let mut index = self.price_observations_count;
+                    loop {
+                        assert!(
+                            index > 0 && self.price_observations_count - index < MAX_PRICE_OBSERVATIONS,
+                            "Not enough price history for this window",
+                        );
+                        index -= 1;
+
+                        let older = *self.price_observations.get(&(index % MAX_PRICE_OBSERVATIONS)).unwrap();
+                        if older.timestamp <= target_time {
+                            break older.price_cumulative +
+                                (newer.price_cumulative - older.price_cumulative) *
+                                (target_time - older.timestamp) /
+                                (newer.timestamp - older.timestamp);
+                        }
+
+                        newer = older;
+                    }
+                },
+            };
+
+            (price_cumulative_now - price_cumulative_then) / window_seconds
+        }
+
+        // Return the status of the liquidity positions represented by the given LP NFTs: what
+        // they can redeem, the pool fees they earned and the impermanent loss against holding
+        fn get_lp_position(
+            &self,
+
+            // Numeric ids of the LP NFTs
+            ids: Vec<u64>,
+        ) -> Vec<LPPosition> {
+            let price = match self.mode {
+                PoolMode::Normal => self.current_price(),
+                PoolMode::Liquidation => self.last_price,
+                _ => Runtime::panic(MODE_NOT_ALLOWED.to_string()),
+            };
+
+            ids.iter().map(|id| {
+                let lp_data = self.lp_resource_manager.get_non_fungible_data::<LPData>(
+                    &NonFungibleLocalId::integer(*id)
+                );
+
+                // Same computation as withdraw_lp_share
+                let (redeemable_base_coins, redeemable_coins) = match self.mode {
+                    PoolMode::Normal => {
+                        let user_share = PreciseDecimal::from(lp_data.lp_share) / PreciseDecimal::from(self.total_lp);
+
+                        (
+                            (user_share * self.base_coin_vault.amount()).checked_truncate(RoundingMode::ToZero).unwrap(),
+                            (user_share * self.coin_vault.amount()).checked_truncate(RoundingMode::ToZero).unwrap(),
+                        )
+                    },
+                    _ => (
+                        self.base_coins_to_lp_providers * (lp_data.lp_share / self.total_users_lp),
+                        Decimal::ZERO,
+                    ),
+                };
+
+                let position_value = redeemable_base_coins + redeemable_coins * price;
+                let hold_value = lp_data.deposited_base_coins + lp_data.deposited_coins * price;
+                let fees_accrued = (self.fee_growth_per_lp - lp_data.fee_growth_checkpoint) * lp_data.lp_share;
+
+                LPPosition {
+                    id: *id,
+                    lp_share: lp_data.lp_share,
+                    redeemable_base_coins: redeemable_base_coins,
+                    redeemable_coins: redeemable_coins,
+                    position_value: position_value,
+                    hold_value: hold_value,
+                    fees_accrued: fees_accrued,
+                    impermanent_loss: hold_value - position_value + fees_accrued,
+                }
+            })
+            .collect()
+        }
+
+        // Return the expected result of a buy operation without executing it
+        fn quote_buy(
+            &self,
+            base_coin_amount: Decimal,
+        ) -> PoolQuote {
+            assert!(
+                base_coin_amount > Decimal::ZERO,
+                "Amount in must be positive",
+            );
+
+            let (coin_amount, fee, unignored_coins) = self.compute_buy(base_coin_amount);
+
+            let (price, new_pool_price) = match self.mode {
+                PoolMode::Normal => (
+                    base_coin_amount / coin_amount,
+                    Pool::spot_price(
+                        self.base_coin_vault.amount() + base_coin_amount,
+                        self.coins_in_pool() - coin_amount + unignored_coins,
+                    ),
+                ),
+
+                _ => match self.launch {
+
+                    // During the launch phase of a bonding curve launched coin the price moves
+                    // along the curve
+                    LaunchType::BondingCurve(ref bonding_curve) => (
+                        base_coin_amount / coin_amount,
+                        Pool::curve_price(
+                            bonding_curve,
+                            PreciseDecimal::from(bonding_curve.supply_on_curve + coin_amount),
+                        )
+                        .checked_truncate(RoundingMode::ToZero)
+                        .unwrap(),
+                    ),
+
+                    // During the launch phase of a fair launched coin the price is constant
+                    _ => (self.last_price, self.last_price),
+                },
+            };
+
+            self.new_quote(base_coin_amount, coin_amount, fee, price, new_pool_price)
+        }
+
+        // Return the expected result of a sell operation without executing it
+        fn quote_sell(
+            &self,
+            coin_amount: Decimal,
+        ) -> PoolQuote {
+            assert!(
+                coin_amount > Decimal::ZERO,
+                "Amount in must be positive",
+            );
+
+            let (base_coin_amount, fee) = self.compute_sell(coin_amount);
+
+            let (price, new_pool_price) = match self.mode {
+                PoolMode::Normal => (
+                    base_coin_amount / coin_amount,
+                    Pool::spot_price(
+                        self.base_coin_vault.amount() - base_coin_amount,
+                        self.coins_in_pool() + coin_amount,
+                    ),
+                ),
+
+                // During the launch phase of a bonding curve launched coin the price moves along
+                // the curve
+                PoolMode::Launching => match self.launch {
+                    LaunchType::BondingCurve(ref bonding_curve) => (
+                        base_coin_amount / coin_amount,
+                        Pool::curve_price(
+                            bonding_curve,
+                            PreciseDecimal::from(bonding_curve.supply_on_curve - coin_amount),
+                        )
+                        .checked_truncate(RoundingMode::ToZero)
+                        .unwrap(),
+                    ),
+                    _ => Runtime::panic(SHOULD_NOT_HAPPEN.to_string()),
+                },
+
+                // In Liquidation mode the price is constant
+                _ => (self.last_price, self.last_price),
+            };
+
+            self.new_quote(coin_amount, base_coin_amount, fee, price, new_pool_price)
+        }
+
+        // Return the amount of base coins needed to buy exactly coin_amount_out coins, pool fee
+        // included
+        fn get_buy_amount_in(
+            &self,
+            coin_amount_out: Decimal,
+        ) -> Decimal {
+            assert!(
+                coin_amount_out > Decimal::ZERO,
+                "Amount out must be positive",
+            );
+
+            let extra_fee_factor = pdec!(1) - PreciseDecimal::from(self.anti_sniping_fee_percentage()) / pdec!(100);
+            let fee_factor = extra_fee_factor - PreciseDecimal::from(self.buy_pool_fee_percentage) / pdec!(100);
+            let coin_amount_out = PreciseDecimal::from(coin_amount_out);
+
+            let base_coin_amount_in = match self.mode {
+                PoolMode::Normal => {
+                    let base_coins_in_pool = PreciseDecimal::from(self.base_coin_vault.amount());
+
+                    match self.launch {
+                        LaunchType::Quick(ref quick_launch) if quick_launch.ignored_coins > Decimal::ZERO => {
+
+                            // Inverse of the formula used by buy when there are ignored coins:
+                            // out = Cn * x / (B + (2 - UNIGNORE_FACTOR) * x)
+                            let coins_not_ignored = PreciseDecimal::from(self.coins_in_pool());
+                            let denominator = coins_not_ignored - (pdec![2] - UNIGNORE_FACTOR) * coin_amount_out;
+                            assert!(
+                                denominator > PreciseDecimal::ZERO,
+                                "Not enough coins in the pool",
+                            );
+
+                            // The anti-sniping extra fee is taken from the base coins before
+                            // applying the formula
+                            coin_amount_out * base_coins_in_pool / denominator / extra_fee_factor
+                        },
+
+                        // Inverse of the constant product formula, the pool fee is taken from
+                        // the base coins before applying it
+                        _ => {
+                            let coins_in_pool = PreciseDecimal::from(self.coin_vault.amount());
+                            assert!(
+                                coin_amount_out < coins_in_pool,
+                                "Not enough coins in the pool",
+                            );
+
+                            coin_amount_out * base_coins_in_pool / ((coins_in_pool - coin_amount_out) * fee_factor)
+                        },
+                    }
+                },
+
+                PoolMode::Launching => match self.launch {
+
+                    // During the launch phase of a fair launched coin the price is constant
+                    LaunchType::Fair(_) => coin_amount_out * PreciseDecimal::from(self.last_price) / fee_factor,
+
+                    // Base coins needed to move along the curve, the pool fee is taken from the
+                    // base coins before applying it
+                    LaunchType::BondingCurve(ref bonding_curve) => {
+                        let supply = PreciseDecimal::from(bonding_curve.supply_on_curve);
+
+                        (Pool::curve_reserve(bonding_curve, supply + coin_amount_out) -
+                            Pool::curve_reserve(bonding_curve, supply)) / fee_factor
+                    },
+
+                    _ => Runtime::panic(MODE_NOT_ALLOWED.to_string()),
+                },
+
+                _ => Runtime::panic(MODE_NOT_ALLOWED.to_string()),
+            };
+
+            // Round up so that buying with this amount gives at least coin_amount_out coins
+            base_coin_amount_in
+                .checked_truncate(RoundingMode::ToPositiveInfinity)
+                .unwrap()
+        }
+
+        // Return the amount of coins to sell to receive exactly base_coin_amount_out base coins,
+        // pool fee included
+        fn get_sell_amount_in(
+            &self,
+            base_coin_amount_out: Decimal,
+        ) -> Decimal {
+            assert!(
+                base_coin_amount_out > Decimal::ZERO,
+                "Amount out must be positive",
+            );
+
+            let base_coin_amount_out = PreciseDecimal::from(base_coin_amount_out);
+
+            let coin_amount_in = match self.mode {
+                PoolMode::Normal => {
+
+                    // Inverse of the constant product formula, the pool fee is taken from the
+                    // bought base coins
+                    let bought_base_coins = base_coin_amount_out /
+                        (pdec!(1) - PreciseDecimal::from(self.sell_pool_fee_percentage) / pdec!(100));
+                    let base_coins_in_pool = PreciseDecimal::from(self.base_coin_vault.amount());
+                    assert!(
+                        bought_base_coins < base_coins_in_pool,
+                        "Not enough base coins in the pool",
+                    );
+
+                    bought_base_coins * PreciseDecimal::from(self.coins_in_pool()) / (base_coins_in_pool - bought_base_coins)
+                },
+
+                // In Liquidation the price is constant and no fees are paid to the pool
+                PoolMode::Liquidation => {
+                    assert!(
+                        base_coin_amount_out <= PreciseDecimal::from(self.base_coin_vault.amount()),
+                        "Not enough base coins in the pool",
+                    );
+
+                    base_coin_amount_out / PreciseDecimal::from(self.last_price)
+                },
+
+                // Coins to sell back along the curve, the pool fee is taken from the bought base
+                // coins
+                PoolMode::Launching => match self.launch {
+                    LaunchType::BondingCurve(ref bonding_curve) => {
+                        let bought_base_coins = base_coin_amount_out /
+                            (pdec!(1) - PreciseDecimal::from(self.sell_pool_fee_percentage) / pdec!(100));
+                        let supply = PreciseDecimal::from(bonding_curve.supply_on_curve);
+                        let reserve = Pool::curve_reserve(bonding_curve, supply);
+                        assert!(
+                            bought_base_coins <= reserve,
+                            "Not enough base coins in the pool",
+                        );
+
+                        supply - Pool::curve_supply(bonding_curve, reserve - bought_base_coins)
+                    },
+                    _ => Runtime::panic(MODE_NOT_ALLOWED.to_string()),
+                },
+
+                _ => Runtime::panic(MODE_NOT_ALLOWED.to_string()),
+            };
+
+            // Round up so that selling this amount gives at least base_coin_amount_out base coins
+            coin_amount_in
+                .checked_truncate(RoundingMode::ToPositiveInfinity)
+                .unwrap()
+        }
+
 // THE FOLLOWING METHODS CAN ONLY BE CALLED BY ROUND 0 AND 1 HOOKS AND BY THE RadixPump COMPONENT
 
         // Call this method to buy coins with base coins
@@ -360,56 +979,48 @@ mod pool {
 
             // Base coins
             base_coin_bucket: FungibleBucket,
+
+            // The operation fails if the bought coins are less than this
+            min_amount_out: Decimal,
+
+            // The operation fails if executed after this time (None = no deadline)
+            deadline: Option<Instant>,
+
+            // Proof of the allowlisted badge, only needed during the launch phase of a gated launch
+            buyer_proof: Option<Proof>,
         ) -> (
             FungibleBucket, // Coins
             HookArgument, // Short description of the operation happened, to be used by hooks
             AnyPoolEvent, // BuyEvent
+            Option<(HookArgument, AnyPoolEvent)>, // BondingCurveGraduation HookArgument and event
+                                                  // if this buy made the pool graduate
         ) {
-            // Compute the fees owed to the pool
-            let fee = base_coin_bucket.amount() * self.buy_pool_fee_percentage / dec!(100);
+            self.check_not_paused();
+            check_deadline(deadline);
+            self.update_price_cumulative();
 
-            match self.mode {
-                PoolMode::Normal => {
+            // Compute the bought coins and the fees owed to the pool
+            let (coin_amount_bought, fee, unignored_coins) = self.compute_buy(base_coin_bucket.amount());
+            check_min_amount_out(coin_amount_bought, min_amount_out);
 
-                    let coin_amount_bought = match self.launch {
-                        LaunchType::Quick(ref mut quick_launch) => {
-
-                            // In case of a quick launch we have to use a different formula if
-                            // there are ignored coins
-                            if quick_launch.ignored_coins > Decimal::ZERO {
-
-                                // This formula was derived by imposing the price on the bought
-                                // coins equal to the new base coin / coin ratio in the pool after
-                                // a number of ignored coins is unignored.
-                                // Because of the fees the new price will be a little smaller than
-                                // the bought price.
-                                let coin_amount_bought = (PreciseDecimal::from(self.coin_vault.amount() - quick_launch.ignored_coins) * PreciseDecimal::from(base_coin_bucket.amount()) /
-                                    (PreciseDecimal::from(self.base_coin_vault.amount()) + (pdec![2] - UNIGNORE_FACTOR) * PreciseDecimal::from(base_coin_bucket.amount())))
-                                    .checked_truncate(RoundingMode::ToZero)
-                                    .unwrap();
-
-                                // Unignore coins
-                                let unignored_coins = min(
-                                    UNIGNORE_FACTOR * coin_amount_bought,
-                                    quick_launch.ignored_coins
-                                );
-                                quick_launch.ignored_coins -= unignored_coins;
+            // Verify that the buyer is allowed to take part in a gated launch
+            self.check_buyer(buyer_proof, base_coin_bucket.amount());
 
-                                coin_amount_bought
-                            } else {
+            // Enforce the per transaction cap during the opening window of a quick launched coin
+            self.check_anti_sniping_cap(base_coin_bucket.amount());
 
-                                // Classic constant product formula
-                                self.constant_product_buy(PreciseDecimal::from(base_coin_bucket.amount() - fee))
-                            }
-                        },
+            let (coin_bucket, hook_argument, event) = match self.mode {
+                PoolMode::Normal => {
 
-                        // Classic constant product formula
-                        _ => self.constant_product_buy(PreciseDecimal::from(base_coin_bucket.amount() - fee)),
-                    };
+                    // In case of a quick launch some of the ignored coins may have been unignored
+                    if let LaunchType::Quick(ref mut quick_launch) = self.launch {
+                        quick_launch.ignored_coins -= unignored_coins;
+                    }
 
                     self.last_price = base_coin_bucket.amount() / coin_amount_bought;
 
                     self.base_coin_vault.put(base_coin_bucket);
+                    self.accrue_fee(fee);
 
                     (
                         self.coin_vault.take(coin_amount_bought),
@@ -495,56 +1106,107 @@ mod pool {
                         )
                     },
 
-                    // To take part in a random launch you have to buy a ticket, not the coin
-                    // itself
-                    LaunchType::Random(_) => Runtime::panic("Use buy_ticket instead".to_string()),
+                    LaunchType::BondingCurve(ref mut bonding_curve) => {
+                        bonding_curve.supply_on_curve += coin_amount_bought;
 
-                    // Only RandomLaunch and FairLaunch have a Launching phase
-                    _ => Runtime::panic(SHOULD_NOT_HAPPEN.to_string()),
-                },
+                        // Coins sold back along the curve are in the pool vault, use them before
+                        // minting new ones
+                        let coins_from_vault = min(coin_amount_bought, self.coin_vault.amount());
+                        let mut coin_bucket = self.coin_vault.take(coins_from_vault);
+                        if coin_amount_bought > coins_from_vault {
+                            coin_bucket.put(
+                                bonding_curve.resource_manager.mint(coin_amount_bought - coins_from_vault)
+                            );
+                        }
 
-                // Not allowed in WaitingForLaunch, TerminatingLaunch, Liquidation and Uninitialised
-                // modes
-                _ => Runtime::panic(MODE_NOT_ALLOWED.to_string()),
-            }
-        }
+                        self.last_price = base_coin_bucket.amount() / coin_amount_bought;
 
-        // Call this method to sell coins for base coins
-        fn sell(
-            &mut self,
+                        // All of the base coins stay in the pool, they will be its liquidity after
+                        // graduation
+                        self.base_coin_vault.put(base_coin_bucket);
+
+                        (
+                            coin_bucket,
+
+                            // Create the HookArgument that RadixPump will use to call hooks
+                            HookArgument {
+                                component: Runtime::global_address().into(),
+                                coin_address: self.coin_vault.resource_address(),
+                                operation: HookableOperation::Buy,
+                                amount: Some(coin_amount_bought),
+                                mode: PoolMode::Launching,
+                                price: self.last_price,
+                                ids: vec![],
+                            },
+
+                            // Create the event but let RadixPump emit it
+                            AnyPoolEvent::BuyEvent(
+                                BuyEvent {
+                                    resource_address: self.coin_vault.resource_address(),
+                                    mode: PoolMode::Launching,
+                                    amount: coin_amount_bought,
+                                    price: self.last_price,
+                                    coins_in_pool: self.coin_vault.amount(),
+                                    fee_paid_to_the_pool: fee,
+                                    integrator_id: 0, // This will be set by RadixPump
+                                    circulating_supply: self.circulating_supply(),
+                                }
+                            )
+                        )
+                    },
+
+                    // Other launch types have already been excluded by compute_buy
+                    _ => Runtime::panic(SHOULD_NOT_HAPPEN.to_string()),
+                },
+
+                // Other modes have already been excluded by compute_buy
+                _ => Runtime::panic(SHOULD_NOT_HAPPEN.to_string()),
+            };
+
+            // A buy during the launch phase of a bonding curve launched coin may reach the
+            // graduation target; check it here so that buys made by hooks graduate the pool too
+            let (graduation_hook_argument, graduation_event) = self.graduate();
+
+            (coin_bucket, hook_argument, event, graduation_hook_argument.zip(graduation_event))
+        }
+
+        // Call this method to sell coins for base coins
+        fn sell(
+            &mut self,
 
             // Coins to sell
             coin_bucket: FungibleBucket,
+
+            // The operation fails if the received base coins are less than this
+            min_amount_out: Decimal,
+
+            // The operation fails if executed after this time (None = no deadline)
+            deadline: Option<Instant>,
         ) -> (
             FungibleBucket, // Base coins
             HookArgument, // Short description of the operation happened, to be used by hooks
             AnyPoolEvent, // SellEvent
         ) {
-            match self.mode {
-                PoolMode::Normal => {
+            self.check_not_paused();
+            check_deadline(deadline);
+            self.update_price_cumulative();
 
-                    // In Normal mode use the constant product formula to get the amount of base
-                    // coins bought
-                    let constant_product = PreciseDecimal::from(self.base_coin_vault.amount()) * PreciseDecimal::from(self.coins_in_pool());
-                    let coin_bucket_amount = coin_bucket.amount();
-                    let base_coins_in_vault_new = (
-                        constant_product / 
-                        PreciseDecimal::from(coin_bucket_amount + self.coins_in_pool())
-                    )
-                    .checked_truncate(RoundingMode::ToZero)
-                    .unwrap();
-                    let bought_base_coins = self.base_coin_vault.amount() - base_coins_in_vault_new;
+            // Compute the bought base coins and the fees owed to the pool
+            let coin_bucket_amount = coin_bucket.amount();
+            let (base_coin_amount, fee_amount) = self.compute_sell(coin_bucket_amount);
 
-                    // Take the fee from the base coin bucket
-                    let fee_amount = bought_base_coins * self.sell_pool_fee_percentage / dec!(100);
+            match self.mode {
+                PoolMode::Normal => {
                     let base_coin_bucket = self.base_coin_vault.take_advanced(
-                        bought_base_coins - fee_amount,
+                        base_coin_amount,
                         WithdrawStrategy::Rounded(RoundingMode::ToZero),
                     );
+                    check_min_amount_out(base_coin_bucket.amount(), min_amount_out);
 
                     self.last_price = base_coin_bucket.amount() / coin_bucket_amount;
 
                     self.coin_vault.put(coin_bucket);
+                    self.accrue_fee(fee_amount);
 
                     (
                         base_coin_bucket,
@@ -576,16 +1238,16 @@ mod pool {
                     )
                 },
                 PoolMode::Liquidation => {
-                    let coin_bucket_amount = coin_bucket.amount();
-
                     self.coin_vault.put(coin_bucket);
 
+                    let base_coin_bucket = self.base_coin_vault.take_advanced(
+                        base_coin_amount,
+                        WithdrawStrategy::Rounded(RoundingMode::ToZero),
+                    );
+                    check_min_amount_out(base_coin_bucket.amount(), min_amount_out);
+
                     (
-                        // In Liquidation the price is constant and no fees are paid to the pool
-                        self.base_coin_vault.take_advanced(
-                            coin_bucket_amount * self.last_price,
-                            WithdrawStrategy::Rounded(RoundingMode::ToZero),
-                        ),
+                        base_coin_bucket,
 
                         // Create the HookArgument that RadixPump will use to call hooks
                         HookArgument {
@@ -613,7 +1275,57 @@ mod pool {
                         )
                     )
                 },
-                _ => Runtime::panic(MODE_NOT_ALLOWED.to_string()),
+
+                PoolMode::Launching => {
+
+                    // Coins sold back along the curve are kept in the pool vault
+                    match self.launch {
+                        LaunchType::BondingCurve(ref mut bonding_curve) =>
+                            bonding_curve.supply_on_curve -= coin_bucket_amount,
+                        _ => Runtime::panic(SHOULD_NOT_HAPPEN.to_string()),
+                    }
+                    self.coin_vault.put(coin_bucket);
+
+                    let base_coin_bucket = self.base_coin_vault.take_advanced(
+                        base_coin_amount,
+                        WithdrawStrategy::Rounded(RoundingMode::ToZero),
+                    );
+                    check_min_amount_out(base_coin_bucket.amount(), min_amount_out);
+
+                    self.last_price = base_coin_bucket.amount() / coin_bucket_amount;
+
+                    (
+                        base_coin_bucket,
+
+                        // Create the HookArgument that RadixPump will use to call hooks
+                        HookArgument {
+                            component: Runtime::global_address().into(),
+                            coin_address: self.coin_vault.resource_address(),
+                            operation: HookableOperation::Sell,
+                            amount: Some(coin_bucket_amount),
+                            mode: PoolMode::Launching,
+                            price: self.last_price,
+                            ids: vec![],
+                        },
+
+                        // Create the event but let RadixPump emit it
+                        AnyPoolEvent::SellEvent(
+                            SellEvent {
+                                resource_address: self.coin_vault.resource_address(),
+                                mode: PoolMode::Launching,
+                                amount: coin_bucket_amount,
+                                price: self.last_price,
+                                coins_in_pool: self.coin_vault.amount(),
+                                fee_paid_to_the_pool: fee_amount,
+                                integrator_id: 0, // This will be set by RadixPump
+                                circulating_supply: self.circulating_supply(),
+                            }
+                        )
+                    )
+                },
+
+                // Other modes have already been excluded by compute_sell
+                _ => Runtime::panic(SHOULD_NOT_HAPPEN.to_string()),
             }
         }
 
@@ -626,12 +1338,16 @@ mod pool {
 
             // Base coins to buy the tickets
             mut base_coin_bucket: FungibleBucket,
+
+            // Proof of the allowlisted badge, only needed if the launch is gated
+            buyer_proof: Option<Proof>,
         ) -> (
             Bucket, // Eventual excess base coins
             NonFungibleBucket, // Tickets
             HookArgument, // Short description of the operation happened, to be used by hooks
             AnyPoolEvent, // BuyTicketEvent
         ) {
+            self.check_not_paused();
             assert!(
                 self.mode == PoolMode::Launching,
                 "Not allowed in this mode",
@@ -641,6 +1357,14 @@ mod pool {
                 "It is not permitted to buy more than {} tickets in a single operation",
                 MAX_TICKETS_PER_OPERATION,
             );
+
+            // Verify that the buyer is allowed to take part in a gated launch
+            if let LaunchType::Random(ref random_launch) = self.launch {
+                self.check_buyer(
+                    buyer_proof,
+                    Decimal::try_from(amount).unwrap() * random_launch.ticket_price,
+                );
+            }
        
             let excess_base_coin_bucket: Bucket;
 
@@ -875,6 +1599,255 @@ mod pool {
             }
         }
 
+        // Call this method to commit base coins to a dutch auction at the current auction price
+        // The bidder receives a bid NFT; after the end of the auction it can be redeemed for the
+        // requested coins and the refund of the difference between the bid price and the clearing
+        // price
+        fn commit_bid(
+            &mut self,
+
+            // Base coins to commit (fee included)
+            mut base_coin_bucket: FungibleBucket,
+        ) -> (
+            Bucket, // Eventual excess base coins
+            NonFungibleBucket, // Bid
+            HookArgument, // Short description of the operation happened, to be used by hooks
+            AnyPoolEvent, // CommitBidEvent
+        ) {
+            self.check_not_paused();
+            assert!(
+                self.mode == PoolMode::Launching,
+                "Not allowed in this mode",
+            );
+            self.update_price_cumulative();
+
+            match self.launch {
+                LaunchType::DutchAuction(ref mut dutch_auction) => {
+                    let now = Clock::current_time_rounded_to_seconds();
+                    assert!(
+                        now.seconds_since_unix_epoch < dutch_auction.end_launch_time,
+                        "Auction ended",
+                    );
+                    let remaining_coins = dutch_auction.supply_for_sale - dutch_auction.committed_coins;
+                    assert!(
+                        remaining_coins > Decimal::ZERO,
+                        "Sold out",
+                    );
+
+                    let price = Pool::auction_price(dutch_auction, now.seconds_since_unix_epoch);
+
+                    // If the bid exceeds the remaining supply put the excess base coins in a
+                    // bucket to return to the user
+                    let base_coin_needed_amount = remaining_coins * price * 100 / (100 - self.buy_pool_fee_percentage);
+                    let excess_base_coin_bucket: Bucket = base_coin_bucket.take_advanced(
+                        max(base_coin_bucket.amount() - base_coin_needed_amount, Decimal::ZERO),
+                        WithdrawStrategy::Rounded(RoundingMode::ToZero),
+                    )
+                    .into();
+
+                    let fee = base_coin_bucket.amount() * self.buy_pool_fee_percentage / 100;
+                    let coin_amount = min((base_coin_bucket.amount() - fee) / price, remaining_coins);
+                    assert!(
+                        coin_amount > Decimal::ZERO,
+                        "Zero amount not allowed",
+                    );
+
+                    dutch_auction.committed_coins += coin_amount;
+                    dutch_auction.committed_base_coins += coin_amount * price;
+
+                    // Once the whole supply has been requested the price stops decreasing
+                    if dutch_auction.committed_coins == dutch_auction.supply_for_sale {
+                        dutch_auction.clearing_price = price;
+                    }
+
+                    // Mint the bid
+                    dutch_auction.last_bid_id += 1;
+                    let bid_bucket = dutch_auction.bid_resource_manager.mint_non_fungible(
+                        &NonFungibleLocalId::integer(dutch_auction.last_bid_id.into()),
+                        AuctionBidData {
+                            coin_resource_address: self.coin_vault.resource_address(),
+                            bid_date: now,
+                            price: price,
+                            coin_amount: coin_amount,
+                        },
+                    );
+
+                    self.last_price = price;
+
+                    // All of the base coins stay in the pool vault until the end of the auction
+                    self.base_coin_vault.put(base_coin_bucket);
+
+                    (
+                        excess_base_coin_bucket,
+                        bid_bucket,
+
+                        // Create the HookArgument that RadixPump will use to call hooks
+                        HookArgument { 
+                            component: Runtime::global_address().into(),
+                            coin_address: self.coin_vault.resource_address(),
+                            operation: HookableOperation::CommitBid,
+                            amount: Some(coin_amount),
+                            mode: PoolMode::Launching,
+                            price: price,
+                            ids: vec![dutch_auction.last_bid_id],
+                        },
+
+                        // Create the event but let RadixPump emit it
+                        AnyPoolEvent::CommitBidEvent(
+                            CommitBidEvent {
+                                resource_address: self.coin_vault.resource_address(),
+                                price: price,
+                                coin_amount: coin_amount,
+                                bid_resource_address: dutch_auction.bid_resource_manager.address(),
+                                bid_id: dutch_auction.last_bid_id,
+                                committed_coins: dutch_auction.committed_coins,
+                                fee_paid_to_the_pool: fee,
+                            }
+                        )
+                    )
+                },
+
+                // Only dutch auctions accept bids
+                _ => Runtime::panic(TYPE_NOT_ALLOWED.to_string()),
+            }
+        }
+
+        // Users who took part in a dutch auction can call this method to get their coins and the
+        // refund of the difference between their bid price and the clearing price
+        fn redeem_bid(
+            &mut self,
+
+            // Bids to redeem. It is possible to redeem any number of bids
+            bid_bucket: Bucket,
+        ) -> (
+            FungibleBucket, // Refund (base coins)
+            FungibleBucket, // Coins
+            HookArgument, // Short description of the operation happened, to be used by hooks
+        ) {
+            assert!(
+                self.mode == PoolMode::Normal || self.mode == PoolMode::Liquidation,
+                "Not allowed in this mode"
+            );
+
+            match self.launch {
+                LaunchType::DutchAuction(ref mut dutch_auction) => {
+                    assert!(
+                        bid_bucket.resource_address() == dutch_auction.bid_resource_manager.address(),
+                        "Unknown bid",
+                    );
+
+                    let mut coin_amount = Decimal::ZERO;
+                    let mut refund_amount = Decimal::ZERO;
+                    let mut ids: Vec<u64> = vec![];
+
+                    // For each bid in the bucket add up the requested coins and the difference
+                    // between the bid price and the clearing price
+                    for bid_id in bid_bucket.as_non_fungible().non_fungible_local_ids().iter() {
+                        let bid_data = dutch_auction.bid_resource_manager.get_non_fungible_data::<AuctionBidData>(&bid_id);
+                        coin_amount += bid_data.coin_amount;
+                        refund_amount += bid_data.coin_amount * (bid_data.price - dutch_auction.clearing_price);
+
+                        match &bid_id {
+                            NonFungibleLocalId::Integer(bid_id) => ids.push(bid_id.value()),
+                            _ => Runtime::panic(SHOULD_NOT_HAPPEN.to_string()),
+                        }
+                    }
+
+                    bid_bucket.burn();
+
+                    // Rounding may leave a tiny difference between the sum of the refunds and the
+                    // refunds vault
+                    let refund_bucket = dutch_auction.refunds_vault.take_advanced(
+                        min(refund_amount, dutch_auction.refunds_vault.amount()),
+                        WithdrawStrategy::Rounded(RoundingMode::ToZero),
+                    );
+
+                    (
+                        refund_bucket,
+                        dutch_auction.sold_coins_vault.take(coin_amount),
+
+                        // Create the HookArgument that RadixPump will use to call hooks
+                        HookArgument { 
+                            component: Runtime::global_address().into(),
+                            coin_address: self.coin_vault.resource_address(),
+                            operation: HookableOperation::RedeemBid,
+                            amount: Some(coin_amount),
+                            mode: self.mode,
+                            price: self.last_price,
+                            ids: ids,
+                        },
+                    )
+                },
+
+                // Only dutch auctions have bids
+                _ => Runtime::panic(TYPE_NOT_ALLOWED.to_string()),
+            }
+        }
+
+        // Buyers of a fair launch that didn't reach its soft cap can call this method to burn their
+        // coins and get their base coins back at the launch price (fees excluded)
+        fn refund(
+            &mut self,
+
+            // Coins to return
+            coin_bucket: FungibleBucket,
+        ) -> (
+            FungibleBucket, // Refund (base coins)
+            HookArgument, // Short description of the operation happened, to be used by hooks
+            AnyPoolEvent, // RefundEvent
+        ) {
+            assert!(
+                self.mode == PoolMode::Refunding,
+                "Not allowed in this mode"
+            );
+            assert!(
+                coin_bucket.resource_address() == self.coin_vault.resource_address(),
+                "Wrong coin",
+            );
+
+            let coin_amount = coin_bucket.amount();
+            assert!(
+                coin_amount > Decimal::ZERO,
+                "Zero amount not allowed",
+            );
+
+            coin_bucket.burn();
+
+            // During the launch phase the coins were sold at a constant price and the fees were
+            // kept by the pool, so refunding at the same price gives back the base coins paid
+            // minus fees
+            // Rounding may leave a tiny difference between the refund and the base coin vault
+            let base_coin_bucket = self.base_coin_vault.take_advanced(
+                min(coin_amount * self.last_price, self.base_coin_vault.amount()),
+                WithdrawStrategy::Rounded(RoundingMode::ToZero),
+            );
+            let base_coin_bucket_amount = base_coin_bucket.amount();
+
+            (
+                base_coin_bucket,
+
+                // Create the HookArgument that RadixPump will use to call hooks
+                HookArgument {
+                    component: Runtime::global_address().into(),
+                    coin_address: self.coin_vault.resource_address(),
+                    operation: HookableOperation::Refund,
+                    amount: Some(coin_amount),
+                    mode: PoolMode::Refunding,
+                    price: self.last_price,
+                    ids: vec![],
+                },
+
+                // Create the event but let RadixPump emit it
+                AnyPoolEvent::RefundEvent(
+                    RefundEvent {
+                        resource_address: self.coin_vault.resource_address(),
+                        coin_amount: coin_amount,
+                        base_coin_amount: base_coin_bucket_amount,
+                    }
+                )
+            )
+        }
+
         // Users can add liquidity to the pool by calling this method.
         // Both base coins and coins must be provided
         fn add_liquidity(
@@ -886,7 +1859,8 @@ mod pool {
             // Coins
             mut coin_bucket: Bucket,
         ) -> (
-            NonFungibleBucket, // Liquidity token representing the deposited coins
+            Bucket, // Liquidity token representing the deposited coins (an LP NFT or fungible LP
+                    // units)
             Option<Bucket>, // Eventual excess coins are returned (eventual excess base coins are
                             // not returned!)
             HookArgument, // Short description of the operation happened, to be used by hooks
@@ -894,10 +1868,12 @@ mod pool {
             Option<PoolMode>, // If the mode of the pool has changed, tell it to the RadixPump
                               // component
         ) {
+            self.check_not_paused();
             assert!(
                 coin_bucket.amount() > Decimal::ZERO && base_coin_bucket.amount() > Decimal::ZERO,
                 "Zero amount not allowed",
             );
+            self.update_price_cumulative();
 
             let coins_in_vault = PreciseDecimal::from(self.coins_in_pool());
             let base_coin_amount = PreciseDecimal::from(base_coin_bucket.amount());
@@ -958,18 +1934,34 @@ mod pool {
             self.total_lp += lp;
             self.total_users_lp += lp;
 
-            // Mint the LP token
-            self.last_lp_id += 1;
-            let lp_bucket = self.lp_resource_manager.mint_non_fungible(
-                &NonFungibleLocalId::integer(self.last_lp_id),
-                LPData {
-                    deposited_coins: coin_bucket.amount(),
-                    deposited_base_coins: base_coin_bucket.amount(),
-                    lp_share: lp,
-                    date: Clock::current_time_rounded_to_seconds(),
-                    coin_resource_address: coin_bucket.resource_address(),
-                }
-            );
+            // Mint the LP token: fungible LP units are minted one per unit of lp_share, otherwise
+            // an LP NFT containing the lp_share is minted
+            let (lp_bucket, lp_id) = match &self.fungible_lp_resource_manager {
+                Some(fungible_lp_resource_manager) => (
+                    Bucket::from(fungible_lp_resource_manager.mint(lp)),
+                    None,
+                ),
+                None => {
+                    self.last_lp_id += 1;
+
+                    (
+                        Bucket::from(
+                            self.lp_resource_manager.mint_non_fungible(
+                                &NonFungibleLocalId::integer(self.last_lp_id),
+                                LPData {
+                                    deposited_coins: coin_bucket.amount(),
+                                    deposited_base_coins: base_coin_bucket.amount(),
+                                    lp_share: lp,
+                                    date: Clock::current_time_rounded_to_seconds(),
+                                    coin_resource_address: coin_bucket.resource_address(),
+                                    fee_growth_checkpoint: self.fee_growth_per_lp,
+                                }
+                            )
+                        ),
+                        Some(self.last_lp_id),
+                    )
+                },
+            };
 
             self.base_coin_vault.put(FungibleBucket(base_coin_bucket));
             self.coin_vault.put(FungibleBucket(coin_bucket));
@@ -987,7 +1979,7 @@ mod pool {
                     amount: Some(coin_amount.checked_truncate(RoundingMode::ToZero).unwrap()),
                     mode: PoolMode::Normal,
                     price: self.last_price,
-                    ids: vec![self.last_lp_id],
+                    ids: lp_id.map_or(vec![], |lp_id| vec![lp_id]), // Empty for fungible LP units
                 },
 
                 // Create the event but let RadixPump emit it
@@ -995,8 +1987,9 @@ mod pool {
                     AddLiquidityEvent {
                         resource_address: self.coin_vault.resource_address(),
                         amount: coin_amount.checked_truncate(RoundingMode::ToZero).unwrap(),
-                        lp_id: self.last_lp_id,
+                        lp_id: lp_id,
                         coins_in_pool: self.coins_in_pool(),
+                        lp_share: lp,
                     }
                 ),
                 mode, // Tell RadixPump about the new mode (if changed)
@@ -1007,7 +2000,8 @@ mod pool {
         fn remove_liquidity(
             &mut self,
 
-            // LP tokens. It is possible to provide multiple LP tokens in a single operation
+            // LP tokens. It is possible to provide multiple LP NFTs in a single operation or any
+            // amount of fungible LP units
             lp_bucket: Bucket,
         ) -> (
             FungibleBucket, // Base coins
@@ -1015,65 +2009,121 @@ mod pool {
             HookArgument, // Short description of the operation happened, to be used by hooks
             AnyPoolEvent, // RemoveLiquidityEvent
         ) {
+            let fungible_lp = match &self.fungible_lp_resource_manager {
+                Some(fungible_lp_resource_manager) =>
+                    lp_bucket.resource_address() == fungible_lp_resource_manager.address(),
+                None => false,
+            };
             assert!(
-                lp_bucket.resource_address() == self.lp_resource_manager.address(),
+                fungible_lp || lp_bucket.resource_address() == self.lp_resource_manager.address(),
                 "Unknown LP token",
             );
+            assert!(
+                lp_bucket.amount() > Decimal::ZERO,
+                "Zero amount not allowed",
+            );
+            self.update_price_cumulative();
 
             let mut lp_share = Decimal::ZERO;
             let mut ids: Vec<u64> = vec![];
 
-            // Compute the total lp_share of the given LP tokens and build the ids list
-            for lp_id in lp_bucket.as_non_fungible().non_fungible_local_ids().iter() {
-                match &lp_id {
-                    NonFungibleLocalId::Integer(lp_id) => {
-                        ids.push(lp_id.value());
-                    },
-                    _ => Runtime::panic(SHOULD_NOT_HAPPEN.to_string()),
-                }
+            if fungible_lp {
+
+                // Each fungible LP unit is worth one unit of lp_share, there are no ids to report
+                lp_share = lp_bucket.amount();
+            } else {
+
+                // Compute the total lp_share of the given LP tokens and build the ids list
+                for lp_id in lp_bucket.as_non_fungible().non_fungible_local_ids().iter() {
+                    match &lp_id {
+                        NonFungibleLocalId::Integer(lp_id) => {
+                            ids.push(lp_id.value());
+                        },
+                        _ => Runtime::panic(SHOULD_NOT_HAPPEN.to_string()),
+                    }
 
-                lp_share += self.lp_resource_manager.get_non_fungible_data::<LPData>(&lp_id).lp_share;
+                    lp_share += self.lp_resource_manager.get_non_fungible_data::<LPData>(&lp_id).lp_share;
+                }
             }
-            let user_share = PreciseDecimal::from(lp_share) / PreciseDecimal::from(self.total_lp);
+            let (base_coin_bucket, coin_bucket, amount) = self.withdraw_lp_share(lp_share);
 
-            let (base_coin_bucket, coin_bucket, amount) = match &self.mode {
-                PoolMode::Normal => {
+            lp_bucket.burn();
 
-                    // In Normal mode take a user_share ratio of both the coins and the base coins
-                    // out of the vaults
-                    let amount = (user_share * self.coin_vault.amount())
-                    .checked_truncate(RoundingMode::ToZero).unwrap();
+            (
+                base_coin_bucket,
+                coin_bucket,
 
-                    (
-                        self.base_coin_vault.take_advanced(
-                            (user_share * self.base_coin_vault.amount()).checked_truncate(RoundingMode::ToZero).unwrap(),
-                            WithdrawStrategy::Rounded(RoundingMode::ToZero),
-                        ),
-                        Some(
-                            self.coin_vault.take(amount)
-                        ),
-                        amount,
-                    )
+                // Create the HookArgument that RadixPump will use to call hooks
+                HookArgument {
+                    component: Runtime::global_address().into(),
+                    coin_address: self.coin_vault.resource_address(),
+                    operation: HookableOperation::RemoveLiquidity,
+                    amount: Some(amount),
+                    mode: self.mode,
+                    price: self.last_price,
+                    ids: ids,
                 },
-                PoolMode::Liquidation => (
 
-                    // In Liquidation mode only take base coins out of the vault
-                    self.base_coin_vault.take_advanced(
-                        self.base_coins_to_lp_providers * (lp_share / self.total_users_lp),
-                        WithdrawStrategy::Rounded(RoundingMode::ToZero),
-                    ),
-                    None,
-                    Decimal::ZERO,
+                // Create the event but let RadixPump emit it
+                AnyPoolEvent::RemoveLiquidityEvent(
+                    RemoveLiquidityEvent {
+                        resource_address: self.coin_vault.resource_address(),
+                        amount: amount,
+                        coins_in_pool: self.coins_in_pool(),
+                        lp_share: lp_share,
+                    }
                 ),
-                _ => Runtime::panic(MODE_NOT_ALLOWED.to_string()),
-            };
+            )
+        }
 
-            lp_bucket.burn();
+        // Users can invoke this method to get back a percentage of the liquidity represented by
+        // one or more LP NFTs; the LP NFTs are updated and returned
+        fn remove_liquidity_partial(
+            &mut self,
 
-            self.total_lp -= lp_share;
-            self.total_users_lp -= lp_share;
+            // LP NFTs. It is possible to provide multiple LP NFTs in a single operation
+            lp_bucket: Bucket,
+
+            // Percentage (expressed as a number from 0 to 100) of the liquidity to remove from each
+            // LP NFT
+            percentage: Decimal,
+        ) -> (
+            Bucket, // The updated LP NFTs
+            FungibleBucket, // Base coins
+            Option<FungibleBucket>, // Coins
+            HookArgument, // Short description of the operation happened, to be used by hooks
+            AnyPoolEvent, // RemoveLiquidityEvent
+        ) {
+            assert!(
+                percentage > Decimal::ZERO && percentage < dec!(100),
+                "Percentage must be between 0 and 100, use remove_liquidity to remove the whole liquidity",
+            );
+            let lp_nfts = self.lp_nfts(&lp_bucket);
+            self.update_price_cumulative();
+
+            let mut lp_share = Decimal::ZERO;
+            let mut ids: Vec<u64> = vec![];
+
+            // Reduce the LP NFTs by the requested percentage and compute the total lp_share to
+            // withdraw
+            for (id, lp_data) in lp_nfts.iter() {
+                let removed_lp_share = lp_data.lp_share * percentage / dec!(100);
+                self.update_lp_data(
+                    *id,
+                    lp_data.deposited_coins - lp_data.deposited_coins * percentage / dec!(100),
+                    lp_data.deposited_base_coins - lp_data.deposited_base_coins * percentage / dec!(100),
+                    lp_data.lp_share - removed_lp_share,
+                    lp_data.fee_growth_checkpoint,
+                );
+
+                lp_share += removed_lp_share;
+                ids.push(*id);
+            }
+
+            let (base_coin_bucket, coin_bucket, amount) = self.withdraw_lp_share(lp_share);
 
             (
+                lp_bucket,
                 base_coin_bucket,
                 coin_bucket,
 
@@ -1094,11 +2144,149 @@ mod pool {
                         resource_address: self.coin_vault.resource_address(),
                         amount: amount,
                         coins_in_pool: self.coins_in_pool(),
+                        lp_share: lp_share,
                     }
                 ),
             )
         }
 
+        // Split an LP NFT in two: the new LP NFT gets the requested percentage of the liquidity
+        // The date of the original LP NFT is kept in both of them
+        fn split_lp(
+            &mut self,
+
+            // The LP NFT to split
+            lp_bucket: Bucket,
+
+            // Percentage (expressed as a number from 0 to 100) of the liquidity to move to the new
+            // LP NFT
+            percentage: Decimal,
+        ) -> (
+            Bucket, // The updated LP NFT
+            Bucket, // The new LP NFT
+            HookArgument, // Short description of the operation happened, to be used by hooks
+        ) {
+            assert!(
+                percentage > Decimal::ZERO && percentage < dec!(100),
+                "Percentage must be between 0 and 100",
+            );
+            let lp_nfts = self.lp_nfts(&lp_bucket);
+            assert!(
+                lp_nfts.len() == 1,
+                "Only one LP NFT can be split at a time",
+            );
+            let (id, lp_data) = &lp_nfts[0];
+
+            let new_deposited_coins = lp_data.deposited_coins * percentage / dec!(100);
+            let new_deposited_base_coins = lp_data.deposited_base_coins * percentage / dec!(100);
+            let new_lp_share = lp_data.lp_share * percentage / dec!(100);
+
+            // The sum of the lp_share of the two LP NFTs is the lp_share of the original one so
+            // total_users_lp doesn't change
+            self.update_lp_data(
+                *id,
+                lp_data.deposited_coins - new_deposited_coins,
+                lp_data.deposited_base_coins - new_deposited_base_coins,
+                lp_data.lp_share - new_lp_share,
+                lp_data.fee_growth_checkpoint,
+            );
+
+            self.last_lp_id += 1;
+            let new_lp_bucket = self.lp_resource_manager.mint_non_fungible(
+                &NonFungibleLocalId::integer(self.last_lp_id),
+                LPData {
+                    deposited_coins: new_deposited_coins,
+                    deposited_base_coins: new_deposited_base_coins,
+                    lp_share: new_lp_share,
+                    date: lp_data.date,
+                    coin_resource_address: lp_data.coin_resource_address,
+                    fee_growth_checkpoint: lp_data.fee_growth_checkpoint,
+                }
+            );
+
+            (
+                lp_bucket,
+                new_lp_bucket.into(),
+
+                // Create the HookArgument that RadixPump will use to call hooks
+                HookArgument {
+                    component: Runtime::global_address().into(),
+                    coin_address: self.coin_vault.resource_address(),
+                    operation: HookableOperation::SplitLiquidity,
+                    amount: Some(percentage),
+                    mode: self.mode,
+                    price: self.last_price,
+                    ids: vec![*id, self.last_lp_id],
+                },
+            )
+        }
+
+        // Merge multiple LP NFTs into the one with the lowest id (and its date), the others are
+        // burned
+        fn merge_lp(
+            &mut self,
+
+            // The LP NFTs to merge
+            lp_bucket: Bucket,
+        ) -> (
+            Bucket, // The merged LP NFT
+            HookArgument, // Short description of the operation happened, to be used by hooks
+        ) {
+            let mut lp_nfts = self.lp_nfts(&lp_bucket);
+            assert!(
+                lp_nfts.len() > 1,
+                "At least two LP NFTs are needed",
+            );
+            lp_nfts.sort_by_key(|(id, _)| *id);
+
+            // Sum the LP NFTs; total_users_lp doesn't change
+            let mut deposited_coins = Decimal::ZERO;
+            let mut deposited_base_coins = Decimal::ZERO;
+            let mut lp_share = Decimal::ZERO;
+            let mut weighted_fee_growth_checkpoint = Decimal::ZERO;
+            let mut ids: Vec<u64> = vec![];
+            for (id, lp_data) in lp_nfts.iter() {
+                deposited_coins += lp_data.deposited_coins;
+                deposited_base_coins += lp_data.deposited_base_coins;
+                lp_share += lp_data.lp_share;
+                weighted_fee_growth_checkpoint += lp_data.fee_growth_checkpoint * lp_data.lp_share;
+                ids.push(*id);
+            }
+
+            // Keep the LP NFT with the lowest id and burn the others
+            let merged_id = NonFungibleLocalId::integer(ids[0]);
+            let merged_lp_bucket = lp_bucket.as_non_fungible().take_non_fungible(&merged_id);
+            lp_bucket.burn();
+
+            self.update_lp_data(
+                ids[0],
+                deposited_coins,
+                deposited_base_coins,
+                lp_share,
+
+                // The merged LP NFT keeps the fees accrued by all of them
+                match lp_share > Decimal::ZERO {
+                    true => weighted_fee_growth_checkpoint / lp_share,
+                    false => self.fee_growth_per_lp,
+                },
+            );
+
+            (
+                merged_lp_bucket.into(),
+
+                // Create the HookArgument that RadixPump will use to call hooks
+                HookArgument {
+                    component: Runtime::global_address().into(),
+                    coin_address: self.coin_vault.resource_address(),
+                    operation: HookableOperation::MergeLiquidity,
+                    amount: None,
+                    mode: self.mode,
+                    price: self.last_price,
+                    ids: ids,
+                },
+            )
+        }
+
 // THE FOLLOWING METHODS CAN ONLY BE CALLED BY THE RadixPump COMPONENT
 
         // The creator of a fair or random launched coin can use this method to start the launch
@@ -1124,6 +2312,10 @@ mod pool {
 
             match self.launch {
                 LaunchType::Fair(ref mut fair_launch) => {
+                    assert!(
+                        unlocking_time - end_launch_time >= fair_launch.vesting_schedule.cliff_duration,
+                        "Cliff longer than the vesting period",
+                    );
 
                     // Set the timings
                     fair_launch.end_launch_time = end_launch_time;
@@ -1149,8 +2341,11 @@ mod pool {
                                 resource_address: fair_launch.resource_manager.address(),
                                 price: self.last_price,
                                 creator_locked_percentage: fair_launch.creator_locked_percentage,
+                                soft_cap: fair_launch.soft_cap,
+                                hard_cap: fair_launch.hard_cap,
                                 end_launch_time: end_launch_time,
                                 unlocking_time: unlocking_time,
+                                vesting_schedule: fair_launch.vesting_schedule,
                                 buy_pool_fee_percentage: self.buy_pool_fee_percentage,
                                 sell_pool_fee_percentage: self.sell_pool_fee_percentage,
                                 flash_loan_pool_fee: self.flash_loan_pool_fee,
@@ -1159,6 +2354,10 @@ mod pool {
                     )
                 },
                 LaunchType::Random(ref mut random_launch) => {
+                    assert!(
+                        unlocking_time - end_launch_time >= random_launch.vesting_schedule.cliff_duration,
+                        "Cliff longer than the vesting period",
+                    );
 
                     // Set the timings
                     random_launch.end_launch_time = end_launch_time;
@@ -1187,6 +2386,43 @@ mod pool {
                                 coins_per_winning_ticket: random_launch.coins_per_winning_ticket,
                                 end_launch_time: end_launch_time,
                                 unlocking_time: unlocking_time,
+                                vesting_schedule: random_launch.vesting_schedule,
+                                buy_pool_fee_percentage: self.buy_pool_fee_percentage,
+                                sell_pool_fee_percentage: self.sell_pool_fee_percentage,
+                                flash_loan_pool_fee: self.flash_loan_pool_fee,
+                            }
+                        )
+                    )
+                },
+                LaunchType::DutchAuction(ref mut dutch_auction) => {
+
+                    // Set the timings; there's no creator allocation so unlocking_time is ignored
+                    dutch_auction.start_launch_time = Clock::current_time_rounded_to_seconds().seconds_since_unix_epoch;
+                    dutch_auction.end_launch_time = end_launch_time;
+
+                    (
+                        PoolMode::Launching,
+
+                        // Create the HookArgument that RadixPump will use to call hooks
+                        HookArgument {
+                            component: Runtime::global_address().into(),
+                            coin_address: self.coin_vault.resource_address(),
+                            operation: HookableOperation::DutchAuction,
+                            amount: None,
+                            mode: self.mode,
+                            price: self.last_price,
+                            ids: vec![],
+                        },
+
+                        // Create the event but let RadixPump emit it
+                        AnyPoolEvent::DutchAuctionStartEvent(
+                            DutchAuctionStartEvent {
+                                resource_address: dutch_auction.resource_manager.address(),
+                                start_price: dutch_auction.start_price,
+                                floor_price: dutch_auction.floor_price,
+                                decay_period: dutch_auction.decay_period,
+                                supply_for_sale: dutch_auction.supply_for_sale,
+                                end_launch_time: end_launch_time,
                                 buy_pool_fee_percentage: self.buy_pool_fee_percentage,
                                 sell_pool_fee_percentage: self.sell_pool_fee_percentage,
                                 flash_loan_pool_fee: self.flash_loan_pool_fee,
@@ -1200,7 +2436,8 @@ mod pool {
 
         // The creator of a fair or random launched coin can use this method to terminate the launch
         // phase
-        // In case of a fair launch the mode goes from Launching to Normal in just one step
+        // In case of a fair launch the mode goes from Launching to Normal in just one step (or to
+        // Refunding if the soft cap has not been reached)
         // In case of a random launch there are 4 possibilities:
         // - Launching -> Normal (if sold tickets <= winning tickets, everybody won)
         // - Launching -> TerminatingLaunch (request random data to the RandomComponent)
@@ -1218,15 +2455,60 @@ mod pool {
                         self.mode == PoolMode::Launching,
                         "Not allowed in this mode",
                     );
-                    self.mode = PoolMode::Normal;
 
+                    // The launch can be terminated early if the hard cap has been reached
                     let now = Clock::current_time_rounded_to_seconds().seconds_since_unix_epoch;
                     assert!(
-                        now >= fair_launch.end_launch_time,
+                        now >= fair_launch.end_launch_time ||
+                        fair_launch.hard_cap.is_some_and(|hard_cap| self.base_coin_vault.amount() >= hard_cap),
                         "Too soon",
                     );
                     fair_launch.end_launch_time = now;
 
+                    // If the soft cap has not been reached the base coins stay in the pool so that
+                    // buyers can get a refund and the creator allocation is never minted
+                    let raised_base_coins = self.base_coin_vault.amount();
+                    if let Some(soft_cap) = fair_launch.soft_cap {
+                        if raised_base_coins < soft_cap {
+                            self.mode = PoolMode::Refunding;
+
+                            // Disable mint forever
+                            fair_launch.resource_manager.set_mintable(rule!(deny_all));
+                            fair_launch.resource_manager.lock_mintable();
+
+                            return (
+                                None,
+                                Some(PoolMode::Refunding),
+                                Some(
+
+                                    // Create the HookArgument that RadixPump will use to call hooks
+                                    HookArgument {
+                                        component: Runtime::global_address().into(),
+                                        coin_address: self.coin_vault.resource_address(),
+                                        operation: HookableOperation::TerminateFairLaunch,
+                                        amount: fair_launch.resource_manager.total_supply(),
+                                        mode: PoolMode::Refunding,
+                                        price: self.last_price,
+                                        ids: vec![],
+                                    }
+                                ),
+                                Some(
+
+                                    // Create the event but let RadixPump emit it
+                                    AnyPoolEvent::SoftCapNotReachedEvent(
+                                        SoftCapNotReachedEvent {
+                                            resource_address: fair_launch.resource_manager.address(),
+                                            raised_base_coins: raised_base_coins,
+                                            soft_cap: soft_cap,
+                                        }
+                                    )
+                                )
+                            );
+                        }
+                    }
+
+                    self.mode = PoolMode::Normal;
+
                     // Get the proceeds of the sale (fee excluded)
                     let base_coin_bucket = self.base_coin_vault.take_advanced(
                         self.base_coin_vault.amount() * (100 - self.buy_pool_fee_percentage) / 100,
@@ -1339,75 +2621,207 @@ mod pool {
                         _ => Runtime::panic(MODE_NOT_ALLOWED.to_string()),
                     }
                 },
-                _ => Runtime::panic(TYPE_NOT_ALLOWED.to_string()),
-            }
-        }
-
-        // The creator of a fair or random launched coin can use this method to get (part of) his
-        // allocation
-        // The creator allocation is time locked and has an unlock schedule linear with time
-        fn unlock(
-            &mut self,
+                LaunchType::DutchAuction(ref mut dutch_auction) => {
+                    assert!(
+                        self.mode == PoolMode::Launching,
+                        "Not allowed in this mode",
+                    );
 
-            // The maximum amount to withdraw (None = all available coins)
-            amount: Option<Decimal>,
-        ) -> 
-            FungibleBucket // Unlocked coins
-        {
-            assert!(
-                self.mode == PoolMode::Normal,
-                "Not allowed in this mode",
-            );
+                    // The auction can be terminated early if the whole supply has been requested
+                    let now = Clock::current_time_rounded_to_seconds().seconds_since_unix_epoch;
+                    assert!(
+                        now >= dutch_auction.end_launch_time || dutch_auction.clearing_price > Decimal::ZERO,
+                        "Too soon",
+                    );
 
-            match self.launch {
-                LaunchType::Fair(ref mut fair_launch) => {
+                    // If not all of the supply has been requested, the clearing price is the
+                    // auction price at the end of the launch phase
+                    if dutch_auction.clearing_price == Decimal::ZERO {
+                        dutch_auction.clearing_price = Pool::auction_price(
+                            dutch_auction,
+                            dutch_auction.end_launch_time,
+                        );
+                    }
+                    dutch_auction.end_launch_time = now;
+                    self.mode = PoolMode::Normal;
+                    self.last_price = dutch_auction.clearing_price;
 
-                    // How much is it possible to unlock now?
-                    let now = min(Clock::current_time_rounded_to_seconds().seconds_since_unix_epoch, fair_launch.unlocking_time);
-                    let unlockable_amount =
-                        fair_launch.initial_locked_amount *
-                        (now - fair_launch.end_launch_time) / (fair_launch.unlocking_time - fair_launch.end_launch_time) -
-                        fair_launch.unlocked_amount;
+                    // Mint the coins requested by the bidders; they will receive them when
+                    // redeeming their bids
+                    dutch_auction.sold_coins_vault.put(
+                        dutch_auction.resource_manager.mint(dutch_auction.committed_coins)
+                    );
 
-                    // Does the user want to unlock everything available or just a part of it?
-                    let amount_to_unlock = min(
-                        fair_launch.locked_vault.amount(),
-                        match amount {
-                            None => unlockable_amount,
-                            Some(amount) => min(unlockable_amount, amount),
-                        }
+                    // Get the proceeds of the sale at clearing price (fee excluded)
+                    let base_coin_bucket = self.base_coin_vault.take_advanced(
+                        dutch_auction.committed_coins * dutch_auction.clearing_price,
+                        WithdrawStrategy::Rounded(RoundingMode::ToZero),
                     );
+                    let base_coin_bucket_amount = base_coin_bucket.amount();
 
-                    fair_launch.unlocked_amount += amount_to_unlock;
+                    // Move the overpayments in the refunds vault
+                    dutch_auction.refunds_vault.put(
+                        self.base_coin_vault.take_advanced(
+                            dutch_auction.committed_base_coins - base_coin_bucket_amount,
+                            WithdrawStrategy::Rounded(RoundingMode::AwayFromZero),
+                        )
+                    );
 
-                    fair_launch.locked_vault.take(amount_to_unlock)
+                    // Only the fees are left in the pool; add a coin amount matching their value
+                    // at clearing price
+                    self.coin_vault.put(
+                        dutch_auction.resource_manager.mint(
+                            self.base_coin_vault.amount() / dutch_auction.clearing_price
+                        )
+                    );
+
+                    // Disable mint forever
+                    dutch_auction.resource_manager.set_mintable(rule!(deny_all));
+                    dutch_auction.resource_manager.lock_mintable();
+
+                    let supply = dutch_auction.resource_manager.total_supply().unwrap();
+
+                    // This initial liquidity is not owned by anyone, it belongs to the pool itself
+                    self.total_lp = self.coin_vault.amount();
+
+                    (
+                        Some(base_coin_bucket),
+                        Some(PoolMode::Normal),
+                        Some(
+
+                            // Create the HookArgument that RadixPump will use to call hooks
+                            HookArgument {
+                                component: Runtime::global_address().into(),
+                                coin_address: self.coin_vault.resource_address(),
+                                operation: HookableOperation::TerminateDutchAuction,
+                                amount: Some(supply),
+                                mode: PoolMode::Normal,
+                                price: self.last_price,
+                                ids: vec![],
+                            }
+                        ),
+                        Some(
+
+                            // Create the event but let RadixPump emit it
+                            AnyPoolEvent::DutchAuctionEndEvent(
+                                DutchAuctionEndEvent {
+                                    resource_address: dutch_auction.resource_manager.address(),
+                                    clearing_price: dutch_auction.clearing_price,
+                                    sold_coins: dutch_auction.committed_coins,
+                                    creator_proceeds: base_coin_bucket_amount,
+                                    refunds: dutch_auction.refunds_vault.amount(),
+                                    coins_in_pool: self.coin_vault.amount(),
+                                }
+                            )
+                        )
+                    )
                 },
-                LaunchType::Random(ref mut random_launch) => {
+                _ => Runtime::panic(TYPE_NOT_ALLOWED.to_string()),
+            }
+        }
 
-                    // How much is it possible to unlock now?
-                    let now = min(Clock::current_time_rounded_to_seconds().seconds_since_unix_epoch, random_launch.unlocking_time);
-                    let unlockable_amount =
-                        random_launch.coins_per_winning_ticket *
-                        (now - random_launch.end_launch_time) / (random_launch.unlocking_time - random_launch.end_launch_time) -
-                        random_launch.unlocked_amount;
+        // The creator of a fair or random launched coin can use this method to get (part of) his
+        // allocation
+        // The creator allocation is time locked and follows the vesting schedule chosen at creation
+        fn unlock(
+            &mut self,
 
-                    // Does the user want to unlock everything available or just a part of it?
-                    let amount_to_unlock = min(
-                        random_launch.locked_vault.amount(),
-                        match amount {
-                            None => unlockable_amount,
-                            Some(amount) => min(unlockable_amount, amount),
-                        }
+            // The maximum amount to withdraw (None = all available coins)
+            amount: Option<Decimal>,
+        ) -> 
+            FungibleBucket // Unlocked coins
+        {
+            self.unlock_position(None, amount)
+        }
+
+        // The creator of a fair or random launched coin can use this method to move part of his
+        // still locked allocation to a vesting NFT (minted by RadixPump)
+        // The vesting NFT position follows the same vesting schedule as the creator allocation and
+        // has the same unlocked percentage as the creator allocation at split time
+        fn split_vesting(
+            &mut self,
+
+            // The amount of still locked coins to move to the new position
+            amount: Decimal,
+        ) -> (
+            Decimal, // Allocation of the new position
+            Decimal, // Part of the allocation of the new position considered already unlocked
+        ) {
+            assert!(
+                self.mode == PoolMode::Normal,
+                "Not allowed in this mode",
+            );
+            assert!(
+                amount > Decimal::ZERO,
+                "Amount must be bigger than zero",
+            );
+
+            match self.launch {
+                LaunchType::Fair(ref mut fair_launch) => {
+
+                    // What's left to the creator
+                    let creator_allocation = fair_launch.initial_locked_amount - fair_launch.split_allocation;
+                    let creator_locked_amount =
+                        creator_allocation - (fair_launch.unlocked_amount - fair_launch.split_unlocked_amount);
+                    assert!(
+                        amount <= creator_locked_amount,
+                        "Not enough locked coins",
                     );
 
-                    random_launch.unlocked_amount += amount_to_unlock;
+                    // The new position takes a proportional share of both the allocation and the
+                    // unlocked amount so that its locked part is exactly amount
+                    let allocation = creator_allocation * amount / creator_locked_amount;
+                    let unlocked_amount = allocation - amount;
 
-                    random_launch.locked_vault.take(amount_to_unlock)
+                    fair_launch.split_allocation += allocation;
+                    fair_launch.split_unlocked_amount += unlocked_amount;
+
+                    (allocation, unlocked_amount)
+                },
+                LaunchType::Random(ref mut random_launch) => {
+
+                    // What's left to the creator
+                    let creator_allocation = random_launch.coins_per_winning_ticket - random_launch.split_allocation;
+                    let creator_locked_amount =
+                        creator_allocation - (random_launch.unlocked_amount - random_launch.split_unlocked_amount);
+                    assert!(
+                        amount <= creator_locked_amount,
+                        "Not enough locked coins",
+                    );
+
+                    // The new position takes a proportional share of both the allocation and the
+                    // unlocked amount so that its locked part is exactly amount
+                    let allocation = creator_allocation * amount / creator_locked_amount;
+                    let unlocked_amount = allocation - amount;
+
+                    random_launch.split_allocation += allocation;
+                    random_launch.split_unlocked_amount += unlocked_amount;
+
+                    (allocation, unlocked_amount)
                 },
                 _ => Runtime::panic(TYPE_NOT_ALLOWED.to_string()),
             }
         }
 
+        // The holder of a vesting NFT can use this method (through RadixPump) to get (part of) the
+        // coins of his position
+        fn unlock_vesting(
+            &mut self,
+
+            // Allocation of the vesting NFT position
+            allocation: Decimal,
+
+            // Part of the allocation already withdrawn
+            unlocked_amount: Decimal,
+
+            // The maximum amount to withdraw (None = all available coins)
+            amount: Option<Decimal>,
+        ) ->
+            FungibleBucket // Unlocked coins
+        {
+            self.unlock_position(Some((allocation, unlocked_amount)), amount)
+        }
+
         // Call this method to put the pool in Liquidation mode (authentication is in RadixPump
         // component)
         // The goal of the liquidation mode is to get all of the coins into the pool and all of the
@@ -1491,22 +2905,40 @@ mod pool {
 
             // The requested amount of coins
             amount: Decimal,
-        ) -> FungibleBucket {
+        ) -> (
+            FungibleBucket, // Borrowed coins
+            Decimal, // Value of the borrowed coins in base coins at the current price
+        ) {
+            self.check_not_paused();
+            assert!(
+                amount <= self.coins_in_pool() * self.max_flash_loan_percentage / dec!(100),
+                "Loan exceeds the maximum borrowable share of the pool",
+            );
+
+            // Evaluate the loan before taking the coins
+            let loan_value = amount * self.current_price();
 
             // Use the get_loan method instead of take, so the output of amount() doesn't change
-            self.coin_vault.get_loan(amount)
+            (self.coin_vault.get_loan(amount), loan_value)
         }
 
         // Return a previoulsy received flash loan
         fn return_flash_loan(
             &mut self,
 
-            // Fee paid to the pool (base coins)
-            base_coin_bucket: Bucket,
+            // Base coins to pay the fee to the pool
+            mut base_coin_bucket: Bucket,
 
             // Coins to return
             coin_bucket: Bucket,
+
+            // Value of the borrowed coins when the loan was received
+            loan_value: Decimal,
+
+            // If true, the pool keeps all of the base coins instead of just its fee
+            keep_all: bool,
         ) -> (
+            Option<Bucket>, // Base coins exceeding the pool fee (None if keep_all is true)
             HookArgument, // Short description of the operation happened, to be used by hooks
             AnyPoolEvent, // FlashLoanEvent
         ) {
@@ -1514,20 +2946,32 @@ mod pool {
                 self.mode == PoolMode::Normal,
                 "Not allowed in this mode",
             );
+            let fee = self.flash_loan_fee(self.flash_loan_pool_fee, loan_value);
             assert!(
-                base_coin_bucket.amount() >= self.flash_loan_pool_fee,
+                base_coin_bucket.amount() >= fee,
                 "Insufficient fee paid to the pool",
             );
 
             // Both RadixPump and LoanSafeVault verify that the returned amount is correct, no need to 
             // do it again here
             let coin_bucket_amount = coin_bucket.amount();
-            let base_coin_bucket_amount = base_coin_bucket.amount();
 
+            // Unless keep_all is true, only take the pool fee: the remaining base coins are needed to
+            // pay other loans in the same bundle
+            let (fee_paid, remaining_base_coin_bucket) = match keep_all {
+                true => (base_coin_bucket.amount(), None),
+                false => {
+                    let remaining_base_coin_bucket = base_coin_bucket.take(base_coin_bucket.amount() - fee);
+                    (fee, Some(remaining_base_coin_bucket))
+                },
+            };
             self.base_coin_vault.put(FungibleBucket(base_coin_bucket));
             self.coin_vault.return_loan(FungibleBucket(coin_bucket));
+            self.accrue_fee(fee_paid);
 
             (
+                remaining_base_coin_bucket,
+
                 // Create the HookArgument that RadixPump will use to call hooks
                 HookArgument { 
                     component: Runtime::global_address().into(),
@@ -1543,14 +2987,155 @@ mod pool {
                 AnyPoolEvent::FlashLoanEvent(
                     FlashLoanEvent {
                         resource_address: self.coin_vault.resource_address(),
+                        side: FlashLoanSide::Coin,
                         amount: coin_bucket_amount,
-                        fee_paid_to_the_pool: base_coin_bucket_amount,
+                        fee_paid_to_the_pool: fee_paid,
+                        integrator_id: 0, // RadixPump will set this
+                    }
+                )
+            )
+        }
+
+        // Get a flash loan of base coins. It is RadixPump responsibility to ensure the loan will be
+        // returned
+        fn get_base_coin_flash_loan(
+            &mut self,
+
+            // The requested amount of base coins
+            amount: Decimal,
+        ) -> FungibleBucket {
+            self.check_not_paused();
+            assert!(
+                self.mode == PoolMode::Normal,
+                "Not allowed in this mode",
+            );
+            assert!(
+                amount <= self.base_coin_vault.amount() * self.max_flash_loan_percentage / dec!(100),
+                "Loan exceeds the maximum borrowable share of the pool",
+            );
+
+            // Use the get_loan method instead of take, so the output of amount() doesn't change
+            // and the price is not affected
+            self.base_coin_vault.get_loan(amount)
+        }
+
+        // Return a previoulsy received base coin flash loan
+        fn return_base_coin_flash_loan(
+            &mut self,
+
+            // Base coins to return plus the fee paid to the pool
+            base_coin_bucket: Bucket,
+        ) -> (
+            HookArgument, // Short description of the operation happened, to be used by hooks
+            AnyPoolEvent, // FlashLoanEvent
+        ) {
+            assert!(
+                self.mode == PoolMode::Normal,
+                "Not allowed in this mode",
+            );
+
+            // LoanSafeVault verifies that the borrowed amount is returned, here check the fee
+            let loan_amount = self.base_coin_vault.ongoing_loan();
+            let fee_paid_to_the_pool = base_coin_bucket.amount() - loan_amount;
+            assert!(
+                fee_paid_to_the_pool >= self.flash_loan_fee(self.base_coin_flash_loan_pool_fee, loan_amount),
+                "Insufficient fee paid to the pool",
+            );
+
+            self.base_coin_vault.return_loan(FungibleBucket(base_coin_bucket));
+            self.accrue_fee(fee_paid_to_the_pool);
+
+            (
+                // Create the HookArgument that RadixPump will use to call hooks
+                HookArgument { 
+                    component: Runtime::global_address().into(),
+                    coin_address: self.coin_vault.resource_address(),
+                    operation: HookableOperation::ReturnBaseCoinFlashLoan,
+                    amount: Some(loan_amount),
+                    mode: PoolMode::Normal,
+                    price: self.last_price,
+                    ids: vec![],
+                },
+
+                // Create the event but let RadixPump emit it
+                AnyPoolEvent::FlashLoanEvent(
+                    FlashLoanEvent {
+                        resource_address: self.coin_vault.resource_address(),
+                        side: FlashLoanSide::BaseCoin,
+                        amount: loan_amount,
+                        fee_paid_to_the_pool: fee_paid_to_the_pool,
                         integrator_id: 0, // RadixPump will set this
                     }
                 )
             )
         }
 
+        // Pause or unpause the pool, authentication is in the RadixPump component
+        fn set_paused(
+            &mut self,
+            paused: bool,
+        ) {
+            assert!(
+                self.paused != paused,
+                "The pool is already in this state",
+            );
+
+            self.paused = paused;
+        }
+
+        // RadixPump calls this method on every pool when the whole component is paused or
+        // unpaused, so that hooks can't trade on the pools while the component is paused
+        fn set_globally_paused(
+            &mut self,
+            globally_paused: bool,
+        ) {
+            self.globally_paused = globally_paused;
+        }
+
+        // Switch the pool from LP NFTs to fungible LP units, user authentication is managed by
+        // RadixPump
+        // LP NFTs already minted keep working
+        fn enable_fungible_lp(
+            &mut self,
+
+            // CompositeRequirement identifying the coin creator badge, it will be the owner of the LP
+            // units
+            coin_creator_badge_rule: CompositeRequirement,
+
+            // Component owner badge
+            owner_badge_address: ResourceAddress,
+
+            // dApp definition account address to set int he metadata
+            dapp_definition: ComponentAddress,
+
+        ) -> ResourceAddress // Resource address of the fungible LP units
+        {
+            assert!(
+                self.fungible_lp_resource_manager.is_none(),
+                "Fungible LP units already enabled",
+            );
+
+            // Use the same name and icon of the LP NFTs
+            let lp_name: String = self.lp_resource_manager.get_metadata("name")
+                .expect(SHOULD_NOT_HAPPEN)
+                .expect(SHOULD_NOT_HAPPEN);
+            let lp_icon_url: UncheckedUrl = self.lp_resource_manager.get_metadata("icon_url")
+                .expect(SHOULD_NOT_HAPPEN)
+                .expect(SHOULD_NOT_HAPPEN);
+
+            let fungible_lp_resource_manager = Pool::fungible_lp_resource_manager(
+                lp_name,
+                lp_icon_url,
+                coin_creator_badge_rule,
+                owner_badge_address,
+                Runtime::global_address().into(),
+                dapp_definition,
+            );
+            self.fungible_lp_resource_manager = Some(fungible_lp_resource_manager);
+
+            fungible_lp_resource_manager.address()
+        }
+
         // The coin creator can use this method to update poll fees, user authentication is managed by RadixPump
         fn update_pool_fees(
             &mut self,
@@ -1559,6 +3144,10 @@ mod pool {
             buy_pool_fee_percentage: Decimal,
             sell_pool_fee_percentage: Decimal,
             flash_loan_pool_fee: Decimal,
+            base_coin_flash_loan_pool_fee: Decimal,
+            flash_loan_pool_fee_percentage: Decimal,
+            max_flash_loan_percentage: Decimal,
+            creator_fee_percentage: Decimal,
 
         ) -> AnyPoolEvent // FeeUpdateEvent
         {
@@ -1578,16 +3167,30 @@ mod pool {
                 "You can't increase pool percentage fees",
             );
 
+            // The creator fee is a share of the trading volume after the launch
+            assert!(
+                creator_fee_percentage == Decimal::ZERO || self.mode == PoolMode::Normal,
+                "Creator fee can only be set after launch",
+            );
+
             assert!(
                 buy_pool_fee_percentage < self.buy_pool_fee_percentage ||
                 sell_pool_fee_percentage < self.sell_pool_fee_percentage ||
-                flash_loan_pool_fee != self.flash_loan_pool_fee,
+                flash_loan_pool_fee != self.flash_loan_pool_fee ||
+                base_coin_flash_loan_pool_fee != self.base_coin_flash_loan_pool_fee ||
+                flash_loan_pool_fee_percentage != self.flash_loan_pool_fee_percentage ||
+                max_flash_loan_percentage != self.max_flash_loan_percentage ||
+                creator_fee_percentage != self.creator_fee_percentage,
                 "No changes made",
             );
 
             self.buy_pool_fee_percentage = buy_pool_fee_percentage;
             self.sell_pool_fee_percentage = sell_pool_fee_percentage;
             self.flash_loan_pool_fee = flash_loan_pool_fee;
+            self.base_coin_flash_loan_pool_fee = base_coin_flash_loan_pool_fee;
+            self.flash_loan_pool_fee_percentage = flash_loan_pool_fee_percentage;
+            self.max_flash_loan_percentage = max_flash_loan_percentage;
+            self.creator_fee_percentage = creator_fee_percentage;
 
             // Just create the event, let RadixPump emit it
             AnyPoolEvent::FeeUpdateEvent(
@@ -1596,6 +3199,10 @@ mod pool {
                     buy_pool_fee_percentage: buy_pool_fee_percentage,
                     sell_pool_fee_percentage: sell_pool_fee_percentage,
                     flash_loan_pool_fee: flash_loan_pool_fee,
+                    base_coin_flash_loan_pool_fee: base_coin_flash_loan_pool_fee,
+                    flash_loan_pool_fee_percentage: flash_loan_pool_fee_percentage,
+                    max_flash_loan_percentage: max_flash_loan_percentage,
+                    creator_fee_percentage: creator_fee_percentage,
                 }
             )
         }
@@ -1686,6 +3293,18 @@ mod pool {
             // unknown until the end of the launch phase
             creator_locked_percentage: Decimal,
 
+            // Optional minimum and maximum amount of base coins (fees included) to raise during
+            // the launch phase
+            soft_cap: Option<Decimal>,
+            hard_cap: Option<Decimal>,
+
+            // Optional restriction on who can buy during the launch phase and per holder cap
+            access_rule: Option<LaunchAccessRule>,
+            max_buy_per_holder: Option<Decimal>,
+
+            // Unlock schedule of the creator allocation
+            vesting_schedule: VestingSchedule,
+
             // Fees for the pool
             buy_pool_fee_percentage: Decimal,
             sell_pool_fee_percentage: Decimal,
@@ -1718,9 +3337,21 @@ mod pool {
                 coin_creator_badge_rule.clone(),
             )
 
-            // Only the creator can burn coins but he is also allowed to change this setting
+            // Both the creator and this component can burn coins (the component needs it to burn
+            // refunded coins if the soft cap is not reached), the creator can change this behaviour
             .burn_roles(burn_roles!(
-                burner => AccessRule::Protected(coin_creator_badge_rule.clone());
+                burner => AccessRule::Protected(
+                    CompositeRequirement::AnyOf(
+                        vec![
+                            coin_creator_badge_rule.clone(),
+                            CompositeRequirement::BasicRequirement(
+                                BasicRequirement::Require(
+                                    global_caller(component_address)
+                                )
+                            )
+                        ]
+                    )
+                );
                 burner_updater => AccessRule::Protected(coin_creator_badge_rule.clone());
             ))
 
@@ -1744,21 +3375,32 @@ mod pool {
 
             // Instantiate the Pool component
             Self {
-                base_coin_vault: FungibleVault::new(base_coin_address),
+                base_coin_vault: LoanSafeVault::new(base_coin_address),
                 coin_vault: LoanSafeVault::new(resource_manager.address()),
                 mode: PoolMode::WaitingForLaunch,
                 last_price: launch_price,
                 buy_pool_fee_percentage: buy_pool_fee_percentage,
                 sell_pool_fee_percentage: sell_pool_fee_percentage,
                 flash_loan_pool_fee: flash_loan_pool_fee,
+                base_coin_flash_loan_pool_fee: flash_loan_pool_fee, // The creator can change it later
+                flash_loan_pool_fee_percentage: Decimal::ZERO,
+                max_flash_loan_percentage: dec!(100),
+                creator_fee_percentage: Decimal::ZERO,
                 launch: LaunchType::Fair(
                     FairLaunchDetails {
                         end_launch_time: 0,
                         creator_locked_percentage: creator_locked_percentage,
+                        soft_cap: soft_cap,
+                        hard_cap: hard_cap,
+                        access_rule: access_rule,
+                        max_buy_per_holder: max_buy_per_holder,
                         locked_vault: FungibleVault::new(resource_manager.address()),
                         unlocking_time: 0,
+                        vesting_schedule: vesting_schedule,
                         initial_locked_amount: Decimal::ZERO,
                         unlocked_amount: Decimal::ZERO,
+                        split_allocation: Decimal::ZERO,
+                        split_unlocked_amount: Decimal::ZERO,
                         resource_manager: resource_manager,
                     }
                 ),
@@ -1768,6 +3410,15 @@ mod pool {
                 total_users_lp: Decimal::ZERO,
                 last_lp_id: 0,
                 base_coins_to_lp_providers: Decimal::ZERO,
+                price_cumulative: Decimal::ZERO,
+                last_observation_time: Clock::current_time_rounded_to_seconds().seconds_since_unix_epoch,
+                price_observations: KeyValueStore::new_with_registered_type(),
+                price_observations_count: 0,
+                launch_purchases: KeyValueStore::new_with_registered_type(),
+                paused: false,
+                globally_paused: false,
+                fungible_lp_resource_manager: None,
+                fee_growth_per_lp: Decimal::ZERO,
             }
             .instantiate()
             .prepare_to_globalize(OwnerRole::Updatable(rule!(require(owner_badge_address))))
@@ -1789,10 +3440,13 @@ mod pool {
             (component_address, resource_manager.address(), lp_resource_manager.address())
         }
 
-        // This constructor creates an empty pool component that will manage an already existing
-        // coin.
-        // The user will have to add liquidity to make the pool usable.
-        pub fn new(
+        // Instantiate a pool component to manage a bonding curve launch
+        // During the launch phase coins are minted and sold along a price curve; users can also sell
+        // them back along the same curve.
+        // When the graduation target is reached the pool moves to the Normal mode using the raised
+        // base coins as liquidity.
+        // After creation the pool is in Launching mode: no further actions are required
+        pub fn new_bonding_curve_launch(
 
             // Component owner badge address
             owner_badge_address: ResourceAddress,
@@ -1803,50 +3457,242 @@ mod pool {
             // The badge round 0 and 1 hooks will use for authentication
             hook_badge_address: ResourceAddress,
 
-            // The base coin used to buy and sell the coin and to pay fees
-            base_coin_address: ResourceAddress,
-
-            // Information about the existing coin
-            coin_address: ResourceAddress,
+            // Metadata for the coin to create
+            coin_symbol: String,
             coin_name: String,
-            coin_icon_url: UncheckedUrl,
+            coin_icon_url: String,
+            coin_description: String,
+            coin_info_url: String,
+            coin_social_url: Vec<String>,
+
+            // Shape and parameters of the price curve
+            curve: BondingCurveType,
+            start_price: Decimal,
+            slope: Decimal,
+
+            // When this target is reached the pool graduates to the Normal mode
+            graduation_target: GraduationTarget,
 
             // Fees for the pool
             buy_pool_fee_percentage: Decimal,
             sell_pool_fee_percentage: Decimal,
             flash_loan_pool_fee: Decimal,
 
-            // CompositeRequirement identifying the coin creator badge, it will be the owner of the LP
-            // tokens
+            // CompositeRequirement identifying the coin creator badge, it will be the owner of the coin
+            // and the LP tokens
             coin_creator_badge_rule: CompositeRequirement,
 
+            // The base coin used to buy and sell the coin and to pay fees
+            base_coin_address: ResourceAddress,
+
             // dApp definition account address to use in components and resources
             dapp_definition: ComponentAddress,
         ) -> (
-            ComponentAddress, // The address of the created Pool component
-            ResourceAddress, // The address of the LP tokens
+            ComponentAddress, // The new Pool component address
+            ResourceAddress, // The coin resource address
+            ResourceAddress, // The LP token resource address
+            HookArgument, // Short description of the operation happened, to be used by hooks
+            AnyPoolEvent, // BondingCurveLaunchEvent
         ) {
             let (address_reservation, component_address) = Runtime::allocate_component_address(Pool::blueprint_id());
 
-            // Create a resource manager to mint the LP tokens
-            let lp_resource_manager = Pool::lp_resource_manager(
+            // Create a resource manager to mint the coin during launch phase
+            let resource_manager = Pool::start_resource_manager_creation(
+                coin_symbol,
                 coin_name.clone(),
-                coin_icon_url,
-                coin_creator_badge_rule,
-                owner_badge_address,
-                component_address,
-                dapp_definition,
-            );
-
-            // Instantiate the pool component
-            Self {
-                base_coin_vault: FungibleVault::new(base_coin_address),
+                coin_icon_url.clone(),
+                coin_description,
+                coin_info_url,
+                coin_social_url.clone(),
+                coin_creator_badge_rule.clone(),
+            )
+
+            // Both the creator and this component can burn coins (the component needs it to burn
+            // the excess coins in the pool at graduation), the creator can change this behaviour
+            .burn_roles(burn_roles!(
+                burner => AccessRule::Protected(
+                    CompositeRequirement::AnyOf(
+                        vec![
+                            coin_creator_badge_rule.clone(),
+                            CompositeRequirement::BasicRequirement(
+                                BasicRequirement::Require(
+                                    global_caller(component_address)
+                                )
+                            )
+                        ]
+                    )
+                );
+                burner_updater => AccessRule::Protected(coin_creator_badge_rule.clone());
+            ))
+
+            // During the launch phase coins will be minted as users buy them
+            // At graduation the mint will be locked
+            .mint_roles(mint_roles!(
+                minter => rule!(require(global_caller(component_address)));
+                minter_updater => rule!(require(global_caller(component_address)));
+            ))
+            .create_with_no_initial_supply();
+
+            // Create a resource manager to mint LP tokens
+            let lp_resource_manager = Pool::lp_resource_manager(
+                coin_name.clone(),
+                UncheckedUrl::of(coin_icon_url),
+                coin_creator_badge_rule,
+                owner_badge_address,
+                component_address,
+                dapp_definition,
+            );
+
+            // Instantiate the Pool component
+            Self {
+                base_coin_vault: LoanSafeVault::new(base_coin_address),
+                coin_vault: LoanSafeVault::new(resource_manager.address()),
+                mode: PoolMode::Launching,
+                last_price: start_price,
+                buy_pool_fee_percentage: buy_pool_fee_percentage,
+                sell_pool_fee_percentage: sell_pool_fee_percentage,
+                flash_loan_pool_fee: flash_loan_pool_fee,
+                base_coin_flash_loan_pool_fee: flash_loan_pool_fee, // The creator can change it later
+                flash_loan_pool_fee_percentage: Decimal::ZERO,
+                max_flash_loan_percentage: dec!(100),
+                creator_fee_percentage: Decimal::ZERO,
+                launch: LaunchType::BondingCurve(
+                    BondingCurveLaunchDetails {
+                        curve: curve,
+                        start_price: start_price,
+                        slope: slope,
+                        graduation_target: graduation_target,
+                        supply_on_curve: Decimal::ZERO,
+                        resource_manager: resource_manager,
+                    }
+                ),
+                extracted_tickets: KeyValueStore::new_with_registered_type(),
+                lp_resource_manager : lp_resource_manager,
+                total_lp: Decimal::ZERO,
+                total_users_lp: Decimal::ZERO,
+                last_lp_id: 0,
+                base_coins_to_lp_providers: Decimal::ZERO,
+                price_cumulative: Decimal::ZERO,
+                last_observation_time: Clock::current_time_rounded_to_seconds().seconds_since_unix_epoch,
+                price_observations: KeyValueStore::new_with_registered_type(),
+                price_observations_count: 0,
+                launch_purchases: KeyValueStore::new_with_registered_type(),
+                paused: false,
+                globally_paused: false,
+                fungible_lp_resource_manager: None,
+                fee_growth_per_lp: Decimal::ZERO,
+            }
+            .instantiate()
+            .prepare_to_globalize(OwnerRole::Updatable(rule!(require(owner_badge_address))))
+
+            // Restrict Pool access to the RadixPump component and the hooks
+            .roles(roles!(
+                proxy => rule!(require(proxy_badge_address));
+                hook => rule!(require(hook_badge_address));
+            ))
+            .with_address(address_reservation)
+            .metadata(metadata! {
+                init {
+                    "name" => format!("{} pool", coin_name), updatable;
+                    "dapp_definition" => dapp_definition, updatable;
+                }
+            })
+            .globalize();
+
+            (
+                component_address,
+                resource_manager.address(),
+                lp_resource_manager.address(),
+
+                // Prepare the argument to call the hooks
+                HookArgument {
+                    component: component_address.into(),
+                    coin_address: resource_manager.address(),
+                    operation: HookableOperation::BondingCurveLaunch,
+                    amount: None,
+                    mode: PoolMode::Launching,
+                    price: start_price,
+                    ids: vec![],
+                },
+
+                // Prepare the event the RadixPump component will emit
+                AnyPoolEvent::BondingCurveLaunchEvent(
+                    BondingCurveLaunchEvent {
+                        resource_address: resource_manager.address(),
+                        curve: curve,
+                        start_price: start_price,
+                        slope: slope,
+                        graduation_target: graduation_target,
+                        buy_pool_fee_percentage: buy_pool_fee_percentage,
+                        sell_pool_fee_percentage: sell_pool_fee_percentage,
+                        flash_loan_pool_fee: flash_loan_pool_fee,
+                    }
+                ),
+            )
+        }
+
+        // This constructor creates an empty pool component that will manage an already existing
+        // coin.
+        // The user will have to add liquidity to make the pool usable.
+        pub fn new(
+
+            // Component owner badge address
+            owner_badge_address: ResourceAddress,
+
+            // The badge RadixPump will use for authentication
+            proxy_badge_address: ResourceAddress,
+
+            // The badge round 0 and 1 hooks will use for authentication
+            hook_badge_address: ResourceAddress,
+
+            // The base coin used to buy and sell the coin and to pay fees
+            base_coin_address: ResourceAddress,
+
+            // Information about the existing coin
+            coin_address: ResourceAddress,
+            coin_name: String,
+            coin_icon_url: UncheckedUrl,
+
+            // Fees for the pool
+            buy_pool_fee_percentage: Decimal,
+            sell_pool_fee_percentage: Decimal,
+            flash_loan_pool_fee: Decimal,
+
+            // CompositeRequirement identifying the coin creator badge, it will be the owner of the LP
+            // tokens
+            coin_creator_badge_rule: CompositeRequirement,
+
+            // dApp definition account address to use in components and resources
+            dapp_definition: ComponentAddress,
+        ) -> (
+            ComponentAddress, // The address of the created Pool component
+            ResourceAddress, // The address of the LP tokens
+        ) {
+            let (address_reservation, component_address) = Runtime::allocate_component_address(Pool::blueprint_id());
+
+            // Create a resource manager to mint the LP tokens
+            let lp_resource_manager = Pool::lp_resource_manager(
+                coin_name.clone(),
+                coin_icon_url,
+                coin_creator_badge_rule,
+                owner_badge_address,
+                component_address,
+                dapp_definition,
+            );
+
+            // Instantiate the pool component
+            Self {
+                base_coin_vault: LoanSafeVault::new(base_coin_address),
                 coin_vault: LoanSafeVault::new(coin_address),
                 mode: PoolMode::Uninitialised,
                 last_price: Decimal::ONE,
                 buy_pool_fee_percentage: buy_pool_fee_percentage,
                 sell_pool_fee_percentage: sell_pool_fee_percentage,
                 flash_loan_pool_fee: flash_loan_pool_fee,
+                base_coin_flash_loan_pool_fee: flash_loan_pool_fee, // The creator can change it later
+                flash_loan_pool_fee_percentage: Decimal::ZERO,
+                max_flash_loan_percentage: dec!(100),
+                creator_fee_percentage: Decimal::ZERO,
                 launch: LaunchType::AlreadyExistingCoin,
                 extracted_tickets: KeyValueStore::new_with_registered_type(),
                 total_lp: Decimal::ZERO,
@@ -1854,6 +3700,15 @@ mod pool {
                 lp_resource_manager: lp_resource_manager,
                 last_lp_id: 0,
                 base_coins_to_lp_providers: Decimal::ZERO,
+                price_cumulative: Decimal::ZERO,
+                last_observation_time: Clock::current_time_rounded_to_seconds().seconds_since_unix_epoch,
+                price_observations: KeyValueStore::new_with_registered_type(),
+                price_observations_count: 0,
+                launch_purchases: KeyValueStore::new_with_registered_type(),
+                paused: false,
+                globally_paused: false,
+                fungible_lp_resource_manager: None,
+                fee_growth_per_lp: Decimal::ZERO,
             }
             .instantiate()
             .prepare_to_globalize(OwnerRole::Updatable(rule!(require(owner_badge_address))))
@@ -1911,6 +3766,9 @@ mod pool {
             sell_pool_fee_percentage: Decimal,
             flash_loan_pool_fee: Decimal,
 
+            // Optional opening window to protect the launch from snipers
+            anti_sniping_window: Option<AntiSnipingWindow>,
+
             // CompositeRequirement identifying the coin creator badge, it will be the owner of the coin
             // and the LP tokens
             coin_creator_badge_rule: CompositeRequirement,
@@ -1972,6 +3830,24 @@ mod pool {
             );
             let creator_coin_bucket = coin_bucket.take(creator_amount);
 
+            // Make sure the opening window makes sense
+            if let Some(ref window) = anti_sniping_window {
+                assert!(
+                    window.duration > 0,
+                    "The opening window duration must be positive",
+                );
+                assert!(
+                    window.extra_buy_fee_percentage >= Decimal::ZERO && window.extra_buy_fee_percentage < dec!(100),
+                    "Extra buy fee percentage can go from 0 (included) to 100 (excluded)",
+                );
+                if let Some(max_buy_per_transaction) = window.max_buy_per_transaction {
+                    assert!(
+                        max_buy_per_transaction > Decimal::ZERO,
+                        "The maximum buy per transaction must be positive",
+                    );
+                }
+            }
+
             // Ignore some coins so that the base coin / coin ratio in the pool maches the price
             let ignored_coins = coin_bucket.amount() - base_coin_bucket.amount() / coin_price;
 
@@ -1990,16 +3866,24 @@ mod pool {
 
             // Instantiate the component
             Self {
-                base_coin_vault: FungibleVault::with_bucket(base_coin_bucket),
+                base_coin_vault: LoanSafeVault::with_bucket(base_coin_bucket),
                 coin_vault: LoanSafeVault::with_bucket(coin_bucket.into()),
                 mode: PoolMode::Normal,
                 last_price: coin_price,
                 buy_pool_fee_percentage: buy_pool_fee_percentage,
                 sell_pool_fee_percentage: sell_pool_fee_percentage,
                 flash_loan_pool_fee: flash_loan_pool_fee,
+                base_coin_flash_loan_pool_fee: flash_loan_pool_fee, // The creator can change it later
+                flash_loan_pool_fee_percentage: Decimal::ZERO,
+                max_flash_loan_percentage: dec!(100),
+                creator_fee_percentage: Decimal::ZERO,
                 launch: LaunchType::Quick(
                     QuickLaunchDetails {
                         ignored_coins: ignored_coins,
+                        anti_sniping_window: anti_sniping_window,
+                        anti_sniping_end_time: Clock::current_time_rounded_to_seconds().seconds_since_unix_epoch +
+                            anti_sniping_window.map_or(0, |window| window.duration),
+                        anti_sniping_last_transaction: None,
                     }
                 ),
                 extracted_tickets: KeyValueStore::new_with_registered_type(),
@@ -2008,6 +3892,15 @@ mod pool {
                 lp_resource_manager: lp_resource_manager,
                 last_lp_id: 0,
                 base_coins_to_lp_providers: Decimal::ZERO,
+                price_cumulative: Decimal::ZERO,
+                last_observation_time: Clock::current_time_rounded_to_seconds().seconds_since_unix_epoch,
+                price_observations: KeyValueStore::new_with_registered_type(),
+                price_observations_count: 0,
+                launch_purchases: KeyValueStore::new_with_registered_type(),
+                paused: false,
+                globally_paused: false,
+                fungible_lp_resource_manager: None,
+                fee_growth_per_lp: Decimal::ZERO,
             }
             .instantiate()
             .prepare_to_globalize(OwnerRole::Updatable(rule!(require(owner_badge_address))))
@@ -2052,6 +3945,7 @@ mod pool {
                         buy_pool_fee_percentage: buy_pool_fee_percentage,
                         sell_pool_fee_percentage: sell_pool_fee_percentage,
                         flash_loan_pool_fee: flash_loan_pool_fee,
+                        anti_sniping_window: anti_sniping_window,
                     }
                 ),
                 lp_resource_manager.address(),
@@ -2093,6 +3987,13 @@ mod pool {
             // How many coins will a winning ticket receive
             coins_per_winning_ticket: Decimal,
 
+            // Optional restriction on who can buy tickets during the launch phase and per holder cap
+            access_rule: Option<LaunchAccessRule>,
+            max_buy_per_holder: Option<Decimal>,
+
+            // Unlock schedule of the creator allocation
+            vesting_schedule: VestingSchedule,
+
             // Pool fees
             buy_pool_fee_percentage: Decimal,
             sell_pool_fee_percentage: Decimal,
@@ -2224,26 +4125,35 @@ mod pool {
 
             // Instantiate the pool component
             Self {
-                base_coin_vault: FungibleVault::new(base_coin_address),
+                base_coin_vault: LoanSafeVault::new(base_coin_address),
                 coin_vault: LoanSafeVault::new(resource_manager.address()),
                 mode: PoolMode::WaitingForLaunch,
                 last_price: ticket_price / coins_per_winning_ticket,
                 buy_pool_fee_percentage: buy_pool_fee_percentage,
                 sell_pool_fee_percentage: sell_pool_fee_percentage,
                 flash_loan_pool_fee: flash_loan_pool_fee,
+                base_coin_flash_loan_pool_fee: flash_loan_pool_fee, // The creator can change it later
+                flash_loan_pool_fee_percentage: Decimal::ZERO,
+                max_flash_loan_percentage: dec!(100),
+                creator_fee_percentage: Decimal::ZERO,
                 launch: LaunchType::Random(
                     RandomLaunchDetails {
                         end_launch_time: 0,
                         winners_vault: FungibleVault::new(resource_manager.address()),
                         locked_vault: FungibleVault::new(resource_manager.address()),
                         unlocking_time: 0,
+                        vesting_schedule: vesting_schedule,
                         ticket_price: ticket_price,
                         winning_tickets: winning_tickets,
                         coins_per_winning_ticket: coins_per_winning_ticket,
+                        access_rule: access_rule,
+                        max_buy_per_holder: max_buy_per_holder,
                         sold_tickets: 0,
                         resource_manager: resource_manager,
                         ticket_resource_manager: ticket_resource_manager,
                         unlocked_amount: Decimal::ZERO,
+                        split_allocation: Decimal::ZERO,
+                        split_unlocked_amount: Decimal::ZERO,
                         extract_winners: true,
                         number_of_extracted_tickets: 0,
                         refunds_vault: FungibleVault::new(base_coin_address),
@@ -2258,6 +4168,15 @@ mod pool {
                 lp_resource_manager: lp_resource_manager,
                 last_lp_id: 1,
                 base_coins_to_lp_providers: Decimal::ZERO,
+                price_cumulative: Decimal::ZERO,
+                last_observation_time: Clock::current_time_rounded_to_seconds().seconds_since_unix_epoch,
+                price_observations: KeyValueStore::new_with_registered_type(),
+                price_observations_count: 0,
+                launch_purchases: KeyValueStore::new_with_registered_type(),
+                paused: false,
+                globally_paused: false,
+                fungible_lp_resource_manager: None,
+                fee_growth_per_lp: Decimal::ZERO,
             }
             .instantiate()
             .prepare_to_globalize(OwnerRole::Updatable(rule!(require(owner_badge_address))))
@@ -2275,114 +4194,1071 @@ mod pool {
             })
             .globalize();
 
-            (component_address, resource_manager.address(), lp_resource_manager.address())
+            (component_address, resource_manager.address(), lp_resource_manager.address())
+        }
+
+        // Instantiate a pool component to manage a dutch auction
+        // During an initial launch phase the price decreases from start_price to floor_price and
+        // users commit base coins at the current price to request a share of a fixed supply.
+        // At the end of the launch phase everybody pays the same clearing price and overpayments are
+        // refunded.
+        // The creator receives the launch sale proceeds (fees excluded).
+        // The creator will have to call the launch method to start the launch phase.
+        pub fn new_dutch_auction(
+
+            // Component owner badge address
+            owner_badge_address: ResourceAddress,
+
+            // The badge RadixPump will use for authentication
+            proxy_badge_address: ResourceAddress,
+
+            // The badge round 0 and 1 hooks will use for authentication
+            hook_badge_address: ResourceAddress,
+
+            // Metadata for the coin to create
+            coin_symbol: String,
+            coin_name: String,
+            coin_icon_url: String,
+            coin_description: String,
+            coin_info_url: String,
+            coin_social_url: Vec<String>,
+
+            // Price schedule of the auction
+            start_price: Decimal,
+            floor_price: Decimal,
+            decay_period: i64,
+
+            // Maximum number of coins to sell during the auction
+            supply_for_sale: Decimal,
+
+            // Pool fees
+            buy_pool_fee_percentage: Decimal,
+            sell_pool_fee_percentage: Decimal,
+            flash_loan_pool_fee: Decimal,
+
+            // CompositeRequirement identifying the coin creator badge, it will be the owner of the coin,
+            // the bids and the LP tokens
+            coin_creator_badge_rule: CompositeRequirement,
+
+            // The base coin used to buy and sell the coin and to pay fees
+            base_coin_address: ResourceAddress,
+
+            // dApp definition account address to use in components and resources
+            dapp_definition: ComponentAddress,
+        ) -> (
+            ComponentAddress, // The created component address
+            ResourceAddress, // The coin resource address
+            ResourceAddress, // The LP tokens resource address
+        ) {
+            let (address_reservation, component_address) = Runtime::allocate_component_address(Pool::blueprint_id());
+
+            // Create a resource manager to mint bids
+            let bid_resource_manager = ResourceBuilder::new_integer_non_fungible_with_registered_type::<AuctionBidData>(
+                OwnerRole::Updatable(AccessRule::Protected(coin_creator_badge_rule.clone()))
+            )
+            // Only this component can mint bids
+            .mint_roles(mint_roles!(
+                minter => rule!(require(global_caller(component_address)));
+                minter_updater => rule!(require(global_caller(component_address)));
+            ))
+            // Only this component can burn bids
+            .burn_roles(burn_roles!(
+                burner => rule!(require(global_caller(component_address)));
+                burner_updater => rule!(deny_all);
+            ))
+            // Everyone can deposit and withdraw bids and the creator can't change this
+            .deposit_roles(deposit_roles!(
+                depositor => rule!(allow_all);
+                depositor_updater => rule!(deny_all);
+            ))
+            .withdraw_roles(withdraw_roles!(
+                withdrawer => rule!(allow_all);
+                withdrawer_updater => rule!(deny_all);
+            ))
+            // No one can recall or freeze bids and the creator can't change this
+            .recall_roles(recall_roles!(
+                recaller => rule!(deny_all);
+                recaller_updater => rule!(deny_all);
+            ))
+            .freeze_roles(freeze_roles!(
+                freezer => rule!(deny_all);
+                freezer_updater => rule!(deny_all);
+            ))
+            // The creator can update bids metadata
+            .metadata(metadata!(
+                roles {
+                    metadata_setter => AccessRule::Protected(coin_creator_badge_rule.clone());
+                    metadata_setter_updater => AccessRule::Protected(coin_creator_badge_rule.clone());
+                    metadata_locker => AccessRule::Protected(coin_creator_badge_rule.clone());
+                    metadata_locker_updater => AccessRule::Protected(coin_creator_badge_rule.clone());
+                },
+                init {
+                    "name" => format!("Bid for the auction of {}", coin_name), updatable;
+                    "icon_url" => MetadataValue::Url(UncheckedUrl::of(coin_icon_url.clone())), updatable;
+                    "description" => coin_description.clone(), updatable;
+                    "dapp_definition" => dapp_definition, updatable;
+                }
+            ))
+            .create_with_no_initial_supply();
+
+            // Create the resource manager the mint the coins at the end of the launch phase
+            let resource_manager = Pool::start_resource_manager_creation(
+                coin_symbol,
+                coin_name.clone(),
+                coin_icon_url.clone(),
+                coin_description,
+                coin_info_url,
+                coin_social_url,
+                coin_creator_badge_rule.clone(),
+            )
+            // Only the creator can burn coins but he can also change this behaviour
+            .burn_roles(burn_roles!(
+                burner => AccessRule::Protected(coin_creator_badge_rule.clone());
+                burner_updater => AccessRule::Protected(coin_creator_badge_rule.clone());
+            ))
+            // Only this component can mint coins, the mint happens at the end of the launch phase,
+            // after that minting will be locked
+            .mint_roles(mint_roles!(
+                minter => rule!(require(global_caller(component_address)));
+                minter_updater => rule!(require(global_caller(component_address)));
+            ))
+            .create_with_no_initial_supply();
+
+            // Create a resource manager to mint LP tokens
+            let lp_resource_manager = Pool::lp_resource_manager(
+                coin_name.clone(),
+                UncheckedUrl::of(coin_icon_url),
+                coin_creator_badge_rule,
+                owner_badge_address,
+                component_address,
+                dapp_definition,
+            );
+
+            // Instantiate the pool component
+            Self {
+                base_coin_vault: LoanSafeVault::new(base_coin_address),
+                coin_vault: LoanSafeVault::new(resource_manager.address()),
+                mode: PoolMode::WaitingForLaunch,
+                last_price: start_price,
+                buy_pool_fee_percentage: buy_pool_fee_percentage,
+                sell_pool_fee_percentage: sell_pool_fee_percentage,
+                flash_loan_pool_fee: flash_loan_pool_fee,
+                base_coin_flash_loan_pool_fee: flash_loan_pool_fee, // The creator can change it later
+                flash_loan_pool_fee_percentage: Decimal::ZERO,
+                max_flash_loan_percentage: dec!(100),
+                creator_fee_percentage: Decimal::ZERO,
+                launch: LaunchType::DutchAuction(
+                    DutchAuctionLaunchDetails {
+                        start_price: start_price,
+                        floor_price: floor_price,
+                        decay_period: decay_period,
+                        supply_for_sale: supply_for_sale,
+                        start_launch_time: 0,
+                        end_launch_time: 0,
+                        committed_coins: Decimal::ZERO,
+                        committed_base_coins: Decimal::ZERO,
+                        clearing_price: Decimal::ZERO,
+                        sold_coins_vault: FungibleVault::new(resource_manager.address()),
+                        refunds_vault: FungibleVault::new(base_coin_address),
+                        resource_manager: resource_manager,
+                        bid_resource_manager: bid_resource_manager,
+                        last_bid_id: 0,
+                    }
+                ),
+                extracted_tickets: KeyValueStore::new_with_registered_type(),
+                lp_resource_manager : lp_resource_manager,
+                total_lp: Decimal::ZERO,
+                total_users_lp: Decimal::ZERO,
+                last_lp_id: 0,
+                base_coins_to_lp_providers: Decimal::ZERO,
+                price_cumulative: Decimal::ZERO,
+                last_observation_time: Clock::current_time_rounded_to_seconds().seconds_since_unix_epoch,
+                price_observations: KeyValueStore::new_with_registered_type(),
+                price_observations_count: 0,
+                launch_purchases: KeyValueStore::new_with_registered_type(),
+                paused: false,
+                globally_paused: false,
+                fungible_lp_resource_manager: None,
+                fee_growth_per_lp: Decimal::ZERO,
+            }
+            .instantiate()
+            .prepare_to_globalize(OwnerRole::Updatable(rule!(require(owner_badge_address))))
+
+            // Restrict Pool access to the RadixPump component and the hooks
+            .roles(roles!(
+                proxy => rule!(require(proxy_badge_address));
+                hook => rule!(require(hook_badge_address));
+            ))
+            .with_address(address_reservation)
+            .metadata(metadata! {
+                init {
+                    "name" => format!("{} pool", coin_name), updatable;
+                    "dapp_definition" => dapp_definition, updatable;
+                }
+            })
+            .globalize();
+
+            (component_address, resource_manager.address(), lp_resource_manager.address())
+        }
+
+// ONLY THE RandomComponent IS SUPPOSED TO CALL THE FOLLOWING METHODS AUTHENTICATION IS HANDLED BY A BADGE, MINTED BY
+// random_badge_resource_manager, THAT THIS COMPONENT PASSES TO THE RandomComponent, EXPECT TO RECEIVE BACK AND BURNS
+
+        // When random data are neeeded, at the end o the random launch phase, the RandomComponent
+        // is invoked passing it a badge.
+        // The RandomComponent, asynchronously, call this method to provide the random seed and
+        // returns the random badge
+        pub fn random_callback(
+            &mut self,
+
+            // The key specified in the RandomComponent invokation, we don't need that
+            _key: u32,
+
+            // The badge provided to the RandomComponent to authenticate this callback
+            badge: FungibleBucket,
+
+            // The random data
+            random_seed: Vec<u8>
+        ) {
+            match self.launch {
+                LaunchType::Random(ref mut random_launch) => {
+
+                    // Check the badge and burn it
+                    assert!(
+                        badge.resource_address() == random_launch.random_badge_resource_manager.address() &&
+                        badge.amount() == Decimal::ONE,
+                        "Wrong badge",
+                    );
+                    badge.burn();
+
+                    // How many tickets are we going to extract?
+                    // No more than MAX_TICKETS_PER_OPERATION to avoid hitting transaction cost
+                    // limits
+                    // We can either extract winners or losers, which number is smaller
+                    let tickets_to_extract = min(
+                        MAX_TICKETS_PER_OPERATION,
+                        match random_launch.extract_winners {
+                            true => random_launch.winning_tickets,
+                            false => random_launch.sold_tickets - random_launch.winning_tickets,
+                        } - random_launch.number_of_extracted_tickets,
+                    );
+
+                    // Fail quietly if there's nothing left to do
+                    if self.mode != PoolMode::TerminatingLaunch ||
+                        tickets_to_extract == 0 {
+                        return;
+                    }
+
+                    // Initialise the Random library
+                    let mut random: Random = Random::new(&random_seed);
+
+                    // For each ticket to extract
+                    for _i in 0..tickets_to_extract {
+
+                        // Generate a random number from zero to the number of sold tickets
+                        // excluded (ticket ids start from zero)
+                        let mut ticket_id = random.in_range::<u64>(0, random_launch.sold_tickets.into());
+
+                        // If the number has already been extracted, try again
+                        while self.extracted_tickets.get(&ticket_id).is_some() {
+                            ticket_id = random.in_range::<u64>(0, random_launch.sold_tickets.into());
+                        }
+
+                        // Add the extracted number to the KVS specifying if it's a winner or a
+                        // loser
+                        self.extracted_tickets.insert(ticket_id, random_launch.extract_winners);
+                    }
+
+                    // Update the count of extracted tokens
+                    random_launch.number_of_extracted_tickets += tickets_to_extract;
+
+                },
+                _ => Runtime::panic(TYPE_NOT_ALLOWED.to_string()),
+            }
+        }
+
+        // If a call to random_callback fails, RandomComponent calls this method to notify it.
+        // This method does nothing except burning the random badge: at the next terminate_launch
+        // invocation more call to the RandomComponent will be issued if we didn't extract enough
+        // tickets
+        pub fn random_on_error(
+            &self,
+
+            // The key specified in the RandomComponent invokation, we don't need that
+            _key: u32,
+
+            // The badge provided to the RandomComponent to authenticate calls to random_callback
+            // (and to this method too)
+            badge: FungibleBucket
+        ) {
+            match &self.launch {
+                LaunchType::Random(random_launch) => {
+                    assert!(
+                        badge.resource_address() == random_launch.random_badge_resource_manager.address() &&
+                        badge.amount() == Decimal::ONE,
+                        "Wrong badge",
+                    );
+
+                    badge.burn();
+                },
+                _ => Runtime::panic(TYPE_NOT_ALLOWED.to_string()),
+            }
+        }
+
+// PRIVATE METHODS AND FUNCTIONS
+
+        // Withdraw the vested coins of a position from the locked vault
+        // The position is either a vesting NFT (allocation and unlocked amount are specified) or
+        // what is left of the creator allocation (None)
+        fn unlock_position(
+            &mut self,
+
+            // Allocation and unlocked amount of a vesting NFT or None for the creator
+            position: Option<(Decimal, Decimal)>,
+
+            // The maximum amount to withdraw (None = all available coins)
+            amount: Option<Decimal>,
+        ) -> FungibleBucket {
+            assert!(
+                self.mode == PoolMode::Normal,
+                "Not allowed in this mode",
+            );
+
+            match self.launch {
+                LaunchType::Fair(ref mut fair_launch) => {
+                    let (allocation, unlocked_amount) = position.unwrap_or((
+                        fair_launch.initial_locked_amount - fair_launch.split_allocation,
+                        fair_launch.unlocked_amount - fair_launch.split_unlocked_amount,
+                    ));
+
+                    // How much is it possible to unlock now?
+                    let unlockable_amount = Pool::vested_amount(
+                        &fair_launch.vesting_schedule,
+                        allocation,
+                        fair_launch.end_launch_time,
+                        fair_launch.unlocking_time,
+                    ) - unlocked_amount;
+
+                    // Does the user want to unlock everything available or just a part of it?
+                    let amount_to_unlock = min(
+                        fair_launch.locked_vault.amount(),
+                        match amount {
+                            None => unlockable_amount,
+                            Some(amount) => min(unlockable_amount, amount),
+                        }
+                    );
+
+                    fair_launch.unlocked_amount += amount_to_unlock;
+                    if position.is_some() {
+                        fair_launch.split_unlocked_amount += amount_to_unlock;
+                    }
+
+                    fair_launch.locked_vault.take(amount_to_unlock)
+                },
+                LaunchType::Random(ref mut random_launch) => {
+                    let (allocation, unlocked_amount) = position.unwrap_or((
+                        random_launch.coins_per_winning_ticket - random_launch.split_allocation,
+                        random_launch.unlocked_amount - random_launch.split_unlocked_amount,
+                    ));
+
+                    // How much is it possible to unlock now?
+                    let unlockable_amount = Pool::vested_amount(
+                        &random_launch.vesting_schedule,
+                        allocation,
+                        random_launch.end_launch_time,
+                        random_launch.unlocking_time,
+                    ) - unlocked_amount;
+
+                    // Does the user want to unlock everything available or just a part of it?
+                    let amount_to_unlock = min(
+                        random_launch.locked_vault.amount(),
+                        match amount {
+                            None => unlockable_amount,
+                            Some(amount) => min(unlockable_amount, amount),
+                        }
+                    );
+
+                    random_launch.unlocked_amount += amount_to_unlock;
+                    if position.is_some() {
+                        random_launch.split_unlocked_amount += amount_to_unlock;
+                    }
+
+                    random_launch.locked_vault.take(amount_to_unlock)
+                },
+                _ => Runtime::panic(TYPE_NOT_ALLOWED.to_string()),
+            }
+        }
+
+        // Compute the part of a creator allocation vested so far according to its vesting schedule
+        fn vested_amount(
+            vesting_schedule: &VestingSchedule,
+
+            // The whole creator allocation
+            initial_locked_amount: Decimal,
+
+            // Start and end of the vesting period
+            end_launch_time: i64,
+            unlocking_time: i64,
+        ) -> Decimal {
+            let now = Clock::current_time_rounded_to_seconds().seconds_since_unix_epoch;
+
+            // Everything is vested at the end of the vesting period, whatever the schedule
+            if now >= unlocking_time {
+                return initial_locked_amount;
+            }
+
+            // The TGE share is available as soon as the launch ends
+            let tge_amount = initial_locked_amount * vesting_schedule.tge_percentage / dec!(100);
+            if now < end_launch_time + vesting_schedule.cliff_duration {
+                return tge_amount;
+            }
+
+            // After the cliff the rest of the allocation is vested as if there was no cliff
+            let vesting_amount = initial_locked_amount - tge_amount;
+            let elapsed_time = now - end_launch_time;
+            let vesting_period = unlocking_time - end_launch_time;
+            tge_amount + match vesting_schedule.vesting_type {
+                VestingType::Linear => vesting_amount * elapsed_time / vesting_period,
+                VestingType::Steps(steps) =>
+                    vesting_amount * (elapsed_time * i64::from(steps) / vesting_period) / steps,
+            }
+        }
+
+        // During the launch phase of a gated fair or random launch, verify that the buyer presented
+        // a proof satisfying the access rule and keep track of the base coins spent by each holder
+        // Outside of the launch phase everybody can buy and the proof is just dropped
+        fn check_buyer(
+            &self,
+
+            // Proof of the allowlisted badge
+            buyer_proof: Option<Proof>,
+
+            // Base coins spent in this operation (fees included)
+            base_coin_amount: Decimal,
+        ) {
+            let (access_rule, max_buy_per_holder) = match self.launch {
+                LaunchType::Fair(ref fair_launch) if self.mode == PoolMode::Launching =>
+                    (fair_launch.access_rule.clone(), fair_launch.max_buy_per_holder),
+                LaunchType::Random(ref random_launch) if self.mode == PoolMode::Launching =>
+                    (random_launch.access_rule.clone(), random_launch.max_buy_per_holder),
+                _ => (None, None),
+            };
+
+            let access_rule = match access_rule {
+                None => {
+                    if let Some(proof) = buyer_proof {
+                        proof.drop();
+                    }
+                    return;
+                },
+                Some(access_rule) => access_rule,
+            };
+
+            let proof = buyer_proof.expect("Proof required to take part in this launch");
+            let proof_resource_address = proof.resource_address();
+
+            // Find out who the holder is; a fungible proof can't identify a single holder
+            let holder = match access_rule {
+                LaunchAccessRule::Resource(resource_address) => {
+                    let checked_proof = proof.check_with_message(resource_address, "Not allowed to take part in this launch");
+                    assert!(
+                        checked_proof.amount() > Decimal::ZERO,
+                        "Not allowed to take part in this launch",
+                    );
+                    let holder = match resource_address.is_fungible() {
+                        true => None,
+                        false => Some(
+                            NonFungibleGlobalId::new(
+                                resource_address,
+                                checked_proof.as_non_fungible().non_fungible_local_ids().first().unwrap().clone(),
+                            )
+                        ),
+                    };
+                    checked_proof.drop();
+
+                    holder
+                },
+                LaunchAccessRule::NonFungibles(allowed_ids) => {
+
+                    // The allowed non fungibles can belong to different resources, so the proof
+                    // resource is verified by looking for its ids in the list
+                    let checked_proof = proof.skip_checking();
+                    let holder = checked_proof.as_non_fungible().non_fungible_local_ids().into_iter()
+                        .map(|local_id| NonFungibleGlobalId::new(proof_resource_address, local_id))
+                        .find(|global_id| allowed_ids.contains(global_id));
+                    checked_proof.drop();
+                    assert!(
+                        holder.is_some(),
+                        "Not allowed to take part in this launch",
+                    );
+
+                    holder
+                },
+            };
+
+            // RadixPump doesn't allow a per holder cap without a non fungible access rule, so the
+            // holder is always known here
+            if let Some(max_buy_per_holder) = max_buy_per_holder {
+                let holder = holder.expect(SHOULD_NOT_HAPPEN);
+                let spent = match self.launch_purchases.get(&holder) {
+                    None => Decimal::ZERO,
+                    Some(spent) => *spent,
+                } + base_coin_amount;
+                assert!(
+                    spent <= max_buy_per_holder,
+                    "Per holder cap exceeded",
+                );
+                self.launch_purchases.insert(holder, spent);
+            }
+        }
+
+        // Extra buy fee percentage paid to the pool during the opening window of a quick launched
+        // coin; it decreases linearly to zero at the end of the window
+        fn anti_sniping_fee_percentage(&self) -> Decimal {
+            match self.launch {
+                LaunchType::Quick(ref quick_launch) => match quick_launch.anti_sniping_window {
+                    Some(ref window) => {
+                        let remaining_time = quick_launch.anti_sniping_end_time -
+                            Clock::current_time_rounded_to_seconds().seconds_since_unix_epoch;
+
+                        match remaining_time > 0 {
+                            true => window.extra_buy_fee_percentage * remaining_time / window.duration,
+                            false => Decimal::ZERO,
+                        }
+                    },
+                    None => Decimal::ZERO,
+                },
+                _ => Decimal::ZERO,
+            }
+        }
+
+        // During the opening window of a quick launched coin, make sure that the base coins spent
+        // in the current transaction don't exceed the cap set by the creator
+        fn check_anti_sniping_cap(
+            &mut self,
+            base_coin_amount: Decimal,
+        ) {
+            if let LaunchType::Quick(ref mut quick_launch) = self.launch {
+                let max_buy_per_transaction = match quick_launch.anti_sniping_window {
+                    Some(window) if window.max_buy_per_transaction.is_some() &&
+                        Clock::current_time_is_strictly_before(
+                            Instant::new(quick_launch.anti_sniping_end_time),
+                            TimePrecision::Second,
+                        ) => window.max_buy_per_transaction.unwrap(),
+                    _ => return,
+                };
+
+                // Multiple buys in the same transaction are added up
+                let transaction_hash = Runtime::transaction_hash();
+                let spent = match quick_launch.anti_sniping_last_transaction {
+                    Some((hash, spent)) if hash == transaction_hash => spent,
+                    _ => Decimal::ZERO,
+                } + base_coin_amount;
+                assert!(
+                    spent <= max_buy_per_transaction,
+                    "Maximum buy per transaction exceeded during the opening window",
+                );
+                quick_launch.anti_sniping_last_transaction = Some((transaction_hash, spent));
+            }
+        }
+
+        // Compute the result of a buy operation without modifying the pool
+        fn compute_buy(
+            &self,
+            base_coin_amount: Decimal,
+        ) -> (
+            Decimal, // Bought coins
+            Decimal, // Fee paid to the pool
+            Decimal, // Ignored coins to unignore (quick launch only)
+        ) {
+            // Compute the fees owed to the pool, the anti-sniping extra fee included
+            let extra_fee = base_coin_amount * self.anti_sniping_fee_percentage() / dec!(100);
+            let fee = base_coin_amount * self.buy_pool_fee_percentage / dec!(100) + extra_fee;
+
+            match self.mode {
+                PoolMode::Normal => match self.launch {
+
+                    // In case of a quick launch we have to use a different formula if there are
+                    // ignored coins
+                    LaunchType::Quick(ref quick_launch) if quick_launch.ignored_coins > Decimal::ZERO => {
+
+                        // This formula was derived by imposing the price on the bought coins
+                        // equal to the new base coin / coin ratio in the pool after a number of
+                        // ignored coins is unignored.
+                        // Because of the fees the new price will be a little smaller than the
+                        // bought price.
+                        // The anti-sniping extra fee is taken from the base coins before applying
+                        // the formula.
+                        let base_coin_amount = base_coin_amount - extra_fee;
+                        let coin_amount_bought = (PreciseDecimal::from(self.coin_vault.amount() - quick_launch.ignored_coins) * PreciseDecimal::from(base_coin_amount) /
+                            (PreciseDecimal::from(self.base_coin_vault.amount()) + (pdec![2] - UNIGNORE_FACTOR) * PreciseDecimal::from(base_coin_amount)))
+                            .checked_truncate(RoundingMode::ToZero)
+                            .unwrap();
+
+                        // Coins to unignore
+                        let unignored_coins = min(
+                            UNIGNORE_FACTOR * coin_amount_bought,
+                            quick_launch.ignored_coins
+                        );
+
+                        (coin_amount_bought, fee, unignored_coins)
+                    },
+
+                    // Classic constant product formula
+                    _ => (
+                        self.constant_product_buy(PreciseDecimal::from(base_coin_amount - fee)),
+                        fee,
+                        Decimal::ZERO,
+                    ),
+                },
+                PoolMode::Launching => match self.launch {
+
+                    // During the launch phase of a fair launched coin, coins are freshly minted
+                    // and the price is constant; the coins matching the fee are kept by the pool
+                    LaunchType::Fair(ref fair_launch) => {
+                        if let Some(hard_cap) = fair_launch.hard_cap {
+                            assert!(
+                                self.base_coin_vault.amount() + base_coin_amount <= hard_cap,
+                                "Hard cap exceeded",
+                            );
+                        }
+
+                        (
+                            base_coin_amount / self.last_price - fee / self.last_price,
+                            fee,
+                            Decimal::ZERO,
+                        )
+                    },
+
+                    // During the launch phase of a bonding curve launched coin, coins are sold along
+                    // the curve; the pool fee is taken from the base coins before applying it
+                    LaunchType::BondingCurve(ref bonding_curve) => {
+                        let supply = PreciseDecimal::from(bonding_curve.supply_on_curve);
+                        let new_supply = Pool::curve_supply(
+                            bonding_curve,
+                            Pool::curve_reserve(bonding_curve, supply) + PreciseDecimal::from(base_coin_amount - fee),
+                        );
+
+                        (
+                            (new_supply - supply).checked_truncate(RoundingMode::ToZero).unwrap(),
+                            fee,
+                            Decimal::ZERO,
+                        )
+                    },
+
+                    // To take part in a random launch you have to buy a ticket, not the coin
+                    // itself
+                    LaunchType::Random(_) => Runtime::panic("Use buy_ticket instead".to_string()),
+
+                    // To take part in a dutch auction you have to commit a bid
+                    LaunchType::DutchAuction(_) => Runtime::panic("Use commit_bid instead".to_string()),
+
+                    // Only RandomLaunch, FairLaunch, BondingCurve and DutchAuction have a Launching
+                    // phase
+                    _ => Runtime::panic(SHOULD_NOT_HAPPEN.to_string()),
+                },
+
+                // Not allowed in WaitingForLaunch, TerminatingLaunch, Liquidation and Uninitialised
+                // modes
+                _ => Runtime::panic(MODE_NOT_ALLOWED.to_string()),
+            }
+        }
+
+        // Compute the result of a sell operation without modifying the pool
+        fn compute_sell(
+            &self,
+            coin_amount: Decimal,
+        ) -> (
+            Decimal, // Bought base coins, fee excluded
+            Decimal, // Fee paid to the pool
+        ) {
+            match self.mode {
+                PoolMode::Normal => {
+
+                    // In Normal mode use the constant product formula to get the amount of base
+                    // coins bought
+                    let constant_product = PreciseDecimal::from(self.base_coin_vault.amount()) * PreciseDecimal::from(self.coins_in_pool());
+                    let base_coins_in_vault_new = (
+                        constant_product / 
+                        PreciseDecimal::from(coin_amount + self.coins_in_pool())
+                    )
+                    .checked_truncate(RoundingMode::ToZero)
+                    .unwrap();
+                    let bought_base_coins = self.base_coin_vault.amount() - base_coins_in_vault_new;
+
+                    // The fee is taken from the bought base coins
+                    let fee_amount = bought_base_coins * self.sell_pool_fee_percentage / dec!(100);
+
+                    (bought_base_coins - fee_amount, fee_amount)
+                },
+
+                // In Liquidation the price is constant and no fees are paid to the pool
+                PoolMode::Liquidation => (coin_amount * self.last_price, Decimal::ZERO),
+
+                // During the launch phase of a bonding curve launched coin, coins can be sold back
+                // along the curve
+                PoolMode::Launching => match self.launch {
+                    LaunchType::BondingCurve(ref bonding_curve) => {
+                        assert!(
+                            coin_amount <= bonding_curve.supply_on_curve,
+                            "Not enough coins sold along the curve",
+                        );
+                        let supply = PreciseDecimal::from(bonding_curve.supply_on_curve);
+                        let bought_base_coins = (
+                            Pool::curve_reserve(bonding_curve, supply) -
+                            Pool::curve_reserve(bonding_curve, supply - PreciseDecimal::from(coin_amount))
+                        )
+                        .checked_truncate(RoundingMode::ToZero)
+                        .unwrap();
+
+                        // The fee is taken from the bought base coins
+                        let fee_amount = bought_base_coins * self.sell_pool_fee_percentage / dec!(100);
+
+                        (bought_base_coins - fee_amount, fee_amount)
+                    },
+                    _ => Runtime::panic(MODE_NOT_ALLOWED.to_string()),
+                },
+
+                _ => Runtime::panic(MODE_NOT_ALLOWED.to_string()),
+            }
+        }
+
+        // Add to the accumulator the current price multiplied by the time elapsed since the last
+        // update and save a snapshot of the previous value.
+        // This must be called before any operation that can change the price; multiple operations
+        // happening in the same second only update the accumulator once
+        fn update_price_cumulative(&mut self) {
+            let now = Clock::current_time_rounded_to_seconds().seconds_since_unix_epoch;
+            if now <= self.last_observation_time {
+                return;
+            }
+
+            self.price_observations.insert(
+                self.price_observations_count % MAX_PRICE_OBSERVATIONS,
+                PriceObservation {
+                    timestamp: self.last_observation_time,
+                    price_cumulative: self.price_cumulative,
+                }
+            );
+            self.price_observations_count += 1;
+
+            self.price_cumulative += self.current_price() * (now - self.last_observation_time);
+            self.last_observation_time = now;
+        }
+
+        // Fee due to the pool for a flash loan: the fixed fee plus a percentage of the loan value
+        fn flash_loan_fee(
+            &self,
+            fixed_fee: Decimal,
+            loan_value: Decimal,
+        ) -> Decimal {
+            fixed_fee + self.flash_loan_pool_fee_percentage * loan_value / dec!(100)
+        }
+
+        // Return the current price of the coin as shown by get_pool_info
+        fn current_price(&self) -> Decimal {
+            match self.mode {
+                PoolMode::Normal => Pool::spot_price(
+                    self.base_coin_vault.amount(),
+                    self.coins_in_pool(),
+                ),
+                PoolMode::Uninitialised => Decimal::ZERO,
+                PoolMode::Launching => match self.launch {
+                    LaunchType::BondingCurve(ref bonding_curve) => Pool::curve_price(
+                        bonding_curve,
+                        PreciseDecimal::from(bonding_curve.supply_on_curve),
+                    )
+                    .checked_truncate(RoundingMode::ToZero)
+                    .unwrap(),
+                    LaunchType::DutchAuction(ref dutch_auction) => Pool::auction_price(
+                        dutch_auction,
+                        min(
+                            Clock::current_time_rounded_to_seconds().seconds_since_unix_epoch,
+                            dutch_auction.end_launch_time,
+                        ),
+                    ),
+                    _ => self.last_price,
+                },
+                _ => self.last_price,
+            }
+        }
+
+        // Price of a dutch auction at the specified time: it decreases linearly from start_price
+        // to floor_price during the decay period; once the whole supply has been requested it
+        // stops at the clearing price
+        fn auction_price(
+            dutch_auction: &DutchAuctionLaunchDetails,
+            time: i64,
+        ) -> Decimal {
+            if dutch_auction.clearing_price > Decimal::ZERO {
+                return dutch_auction.clearing_price;
+            }
+
+            let elapsed = min(time - dutch_auction.start_launch_time, dutch_auction.decay_period);
+
+            dutch_auction.start_price -
+                (dutch_auction.start_price - dutch_auction.floor_price) * elapsed / dutch_auction.decay_period
+        }
+
+        // Price on the bonding curve when supply coins have been sold along it
+        fn curve_price(
+            bonding_curve: &BondingCurveLaunchDetails,
+            supply: PreciseDecimal,
+        ) -> PreciseDecimal {
+            let start_price = PreciseDecimal::from(bonding_curve.start_price);
+            let slope = PreciseDecimal::from(bonding_curve.slope);
+
+            match bonding_curve.curve {
+                BondingCurveType::Linear => start_price + slope * supply,
+                BondingCurveType::Exponential => start_price * Pool::exp(slope * supply),
+            }
+        }
+
+        // Base coins needed to sell supply coins along the bonding curve starting from zero (the
+        // integral of the price)
+        fn curve_reserve(
+            bonding_curve: &BondingCurveLaunchDetails,
+            supply: PreciseDecimal,
+        ) -> PreciseDecimal {
+            let start_price = PreciseDecimal::from(bonding_curve.start_price);
+            let slope = PreciseDecimal::from(bonding_curve.slope);
+
+            if slope == PreciseDecimal::ZERO {
+                return start_price * supply;
+            }
+
+            match bonding_curve.curve {
+                BondingCurveType::Linear => start_price * supply + slope * supply * supply / pdec!(2),
+                BondingCurveType::Exponential => start_price * (Pool::exp(slope * supply) - pdec!(1)) / slope,
+            }
+        }
+
+        // Inverse of curve_reserve: the number of coins sold along the bonding curve when reserve
+        // base coins have been paid
+        fn curve_supply(
+            bonding_curve: &BondingCurveLaunchDetails,
+            reserve: PreciseDecimal,
+        ) -> PreciseDecimal {
+            let start_price = PreciseDecimal::from(bonding_curve.start_price);
+            let slope = PreciseDecimal::from(bonding_curve.slope);
+
+            if slope == PreciseDecimal::ZERO {
+                return reserve / start_price;
+            }
+
+            match bonding_curve.curve {
+                BondingCurveType::Linear =>
+                    ((start_price * start_price + pdec!(2) * slope * reserve).checked_sqrt().unwrap() - start_price) / slope,
+                BondingCurveType::Exponential =>
+                    Pool::ln(pdec!(1) + reserve * slope / start_price) / slope,
+            }
         }
 
-// ONLY THE RandomComponent IS SUPPOSED TO CALL THE FOLLOWING METHODS AUTHENTICATION IS HANDLED BY A BADGE, MINTED BY
-// random_badge_resource_manager, THAT THIS COMPONENT PASSES TO THE RandomComponent, EXPECT TO RECEIVE BACK AND BURNS
+        // e^x for non negative x
+        fn exp(x: PreciseDecimal) -> PreciseDecimal {
 
-        // When random data are neeeded, at the end o the random launch phase, the RandomComponent
-        // is invoked passing it a badge.
-        // The RandomComponent, asynchronously, call this method to provide the random seed and
-        // returns the random badge
-        pub fn random_callback(
-            &mut self,
+            // Halve x until it is not bigger than 1 so that the Taylor series converges quickly,
+            // then square the result the same number of times
+            let mut reduced_x = x;
+            let mut halvings = 0;
+            while reduced_x > pdec!(1) {
+                reduced_x = reduced_x / pdec!(2);
+                halvings += 1;
+            }
 
-            // The key specified in the RandomComponent invokation, we don't need that
-            _key: u32,
+            let mut result = pdec!(1);
+            let mut term = pdec!(1);
+            for n in 1..SERIES_TERMS {
+                term = term * reduced_x / n;
+                result += term;
+            }
 
-            // The badge provided to the RandomComponent to authenticate this callback
-            badge: FungibleBucket,
+            for _ in 0..halvings {
+                result = result * result;
+            }
 
-            // The random data
-            random_seed: Vec<u8>
-        ) {
-            match self.launch {
-                LaunchType::Random(ref mut random_launch) => {
+            result
+        }
 
-                    // Check the badge and burn it
-                    assert!(
-                        badge.resource_address() == random_launch.random_badge_resource_manager.address() &&
-                        badge.amount() == Decimal::ONE,
-                        "Wrong badge",
-                    );
-                    badge.burn();
+        // Natural logarithm of x for x not smaller than 1
+        fn ln(x: PreciseDecimal) -> PreciseDecimal {
 
-                    // How many tickets are we going to extract?
-                    // No more than MAX_TICKETS_PER_OPERATION to avoid hitting transaction cost
-                    // limits
-                    // We can either extract winners or losers, which number is smaller
-                    let tickets_to_extract = min(
-                        MAX_TICKETS_PER_OPERATION,
-                        match random_launch.extract_winners {
-                            true => random_launch.winning_tickets,
-                            false => random_launch.sold_tickets - random_launch.winning_tickets,
-                        } - random_launch.number_of_extracted_tickets,
-                    );
+            // Write x as m * 2^n with 1 <= m < 2, so that ln(x) = n * ln(2) + ln(m)
+            let mut m = x;
+            let mut n: u32 = 0;
+            while m >= pdec!(2) {
+                m = m / pdec!(2);
+                n += 1;
+            }
 
-                    // Fail quietly if there's nothing left to do
-                    if self.mode != PoolMode::TerminatingLaunch ||
-                        tickets_to_extract == 0 {
-                        return;
-                    }
+            // ln(m) = 2 * atanh(z) with z = (m - 1) / (m + 1); since z < 1/3 the series of atanh
+            // converges quickly
+            let z = (m - pdec!(1)) / (m + pdec!(1));
+            let z_squared = z * z;
+            let mut term = z;
+            let mut atanh = PreciseDecimal::ZERO;
+            for k in 0..SERIES_TERMS {
+                atanh += term / (2 * k + 1);
+                term = term * z_squared;
+            }
 
-                    // Initialise the Random library
-                    let mut random: Random = Random::new(&random_seed);
+            LN_2 * n + pdec!(2) * atanh
+        }
 
-                    // For each ticket to extract
-                    for _i in 0..tickets_to_extract {
+        // Return the base coin / coin ratio or zero if there are no coins
+        fn spot_price(
+            base_coin_amount: Decimal,
+            coin_amount: Decimal,
+        ) -> Decimal {
+            if coin_amount > Decimal::ZERO {
+                (PreciseDecimal::from(base_coin_amount) /
+                    PreciseDecimal::from(coin_amount))
+                    .checked_truncate(RoundingMode::ToZero)
+                    .unwrap()
+            } else {
+                Decimal::ZERO
+            }
+        }
 
-                        // Generate a random number from zero to the number of sold tickets
-                        // excluded (ticket ids start from zero)
-                        let mut ticket_id = random.in_range::<u64>(0, random_launch.sold_tickets.into());
+        // Build a PoolQuote comparing the price after the operation with the current one
+        fn new_quote(
+            &self,
+            amount_in: Decimal,
+            amount_out: Decimal,
+            pool_fee: Decimal,
+            price: Decimal,
+            new_pool_price: Decimal,
+        ) -> PoolQuote {
+            let current_price = self.current_price();
+
+            PoolQuote {
+                amount_in: amount_in,
+                amount_out: amount_out,
+                pool_fee: pool_fee,
+                price: price,
+                new_pool_price: new_pool_price,
+                price_impact_percentage: match current_price > Decimal::ZERO {
+                    true => (new_pool_price - current_price) * dec!(100) / current_price,
+                    false => Decimal::ZERO,
+                },
+                mode: self.mode,
+            }
+        }
 
-                        // If the number has already been extracted, try again
-                        while self.extracted_tickets.get(&ticket_id).is_some() {
-                            ticket_id = random.in_range::<u64>(0, random_launch.sold_tickets.into());
-                        }
+        // Take out of the vaults the coins and base coins corresponding to lp_share and update
+        // total_lp and total_users_lp accordingly
+        fn withdraw_lp_share(
+            &mut self,
 
-                        // Add the extracted number to the KVS specifying if it's a winner or a
-                        // loser
-                        self.extracted_tickets.insert(ticket_id, random_launch.extract_winners);
-                    }
+            // The lp_share of the liquidity to remove
+            lp_share: Decimal,
+        ) -> (
+            FungibleBucket, // Base coins
+            Option<FungibleBucket>, // Coins (if the pool is not in liquidation mode)
+            Decimal, // Amount of coins withdrawn
+        ) {
+            let user_share = PreciseDecimal::from(lp_share) / PreciseDecimal::from(self.total_lp);
 
-                    // Update the count of extracted tokens
-                    random_launch.number_of_extracted_tickets += tickets_to_extract;
+            let (base_coin_bucket, coin_bucket, amount) = match &self.mode {
+                PoolMode::Normal => {
+
+                    // In Normal mode take a user_share ratio of both the coins and the base coins
+                    // out of the vaults
+                    let amount = (user_share * self.coin_vault.amount())
+                    .checked_truncate(RoundingMode::ToZero).unwrap();
 
+                    (
+                        self.base_coin_vault.take_advanced(
+                            (user_share * self.base_coin_vault.amount()).checked_truncate(RoundingMode::ToZero).unwrap(),
+                            WithdrawStrategy::Rounded(RoundingMode::ToZero),
+                        ),
+                        Some(
+                            self.coin_vault.take(amount)
+                        ),
+                        amount,
+                    )
                 },
-                _ => Runtime::panic(TYPE_NOT_ALLOWED.to_string()),
-            }
+                PoolMode::Liquidation => (
+
+                    // In Liquidation mode only take base coins out of the vault
+                    self.base_coin_vault.take_advanced(
+                        self.base_coins_to_lp_providers * (lp_share / self.total_users_lp),
+                        WithdrawStrategy::Rounded(RoundingMode::ToZero),
+                    ),
+                    None,
+                    Decimal::ZERO,
+                ),
+                _ => Runtime::panic(MODE_NOT_ALLOWED.to_string()),
+            };
+
+            self.total_lp -= lp_share;
+            self.total_users_lp -= lp_share;
+
+            (base_coin_bucket, coin_bucket, amount)
         }
 
-        // If a call to random_callback fails, RandomComponent calls this method to notify it.
-        // This method does nothing except burning the random badge: at the next terminate_launch
-        // invocation more call to the RandomComponent will be issued if we didn't extract enough
-        // tickets
-        pub fn random_on_error(
+        // Make sure the bucket contains LP NFTs of this pool and return their numeric ids and
+        // non fungible data
+        fn lp_nfts(
             &self,
+            lp_bucket: &Bucket,
+        ) -> Vec<(u64, LPData)> {
+            assert!(
+                lp_bucket.resource_address() == self.lp_resource_manager.address(),
+                "Unknown LP token",
+            );
+            assert!(
+                lp_bucket.amount() > Decimal::ZERO,
+                "Zero amount not allowed",
+            );
 
-            // The key specified in the RandomComponent invokation, we don't need that
-            _key: u32,
+            lp_bucket.as_non_fungible().non_fungibles::<LPData>().into_iter()
+            .map(|lp_nft|
+                match lp_nft.local_id() {
+                    NonFungibleLocalId::Integer(lp_id) => (lp_id.value(), lp_nft.data()),
+                    _ => Runtime::panic(SHOULD_NOT_HAPPEN.to_string()),
+                }
+            )
+            .collect()
+        }
 
-            // The badge provided to the RandomComponent to authenticate calls to random_callback
-            // (and to this method too)
-            badge: FungibleBucket
+        // Update the mutable non fungible data of an LP NFT
+        fn update_lp_data(
+            &self,
+            id: u64,
+            deposited_coins: Decimal,
+            deposited_base_coins: Decimal,
+            lp_share: Decimal,
+            fee_growth_checkpoint: Decimal,
         ) {
-            match &self.launch {
-                LaunchType::Random(random_launch) => {
-                    assert!(
-                        badge.resource_address() == random_launch.random_badge_resource_manager.address() &&
-                        badge.amount() == Decimal::ONE,
-                        "Wrong badge",
-                    );
+            let lp_id = NonFungibleLocalId::integer(id);
+            self.lp_resource_manager.update_non_fungible_data(&lp_id, "deposited_coins", deposited_coins);
+            self.lp_resource_manager.update_non_fungible_data(&lp_id, "deposited_base_coins", deposited_base_coins);
+            self.lp_resource_manager.update_non_fungible_data(&lp_id, "lp_share", lp_share);
+            self.lp_resource_manager.update_non_fungible_data(&lp_id, "fee_growth_checkpoint", fee_growth_checkpoint);
+        }
 
-                    badge.burn();
-                },
-                _ => Runtime::panic(TYPE_NOT_ALLOWED.to_string()),
+        // Distribute a fee paid to the pool among the liquidity units
+        fn accrue_fee(
+            &mut self,
+            fee: Decimal,
+        ) {
+            if self.total_lp > Decimal::ZERO {
+                self.fee_growth_per_lp += fee / self.total_lp;
             }
         }
 
-// PRIVATE METHODS AND FUNCTIONS
+        // Make sure neither the pool nor the whole component are paused
+        fn check_not_paused(&self) {
+            assert!(
+                !self.paused,
+                "Pool paused",
+            );
+            assert!(
+                !self.globally_paused,
+                "Component paused",
+            );
+        }
 
         // Private method implementing the classic constant product formula, it returns the bought
         // amount of coins
@@ -2555,6 +5431,12 @@ mod pool {
                 withdrawer_updater => rule!(deny_all);
             ))
 
+            // Only this component can update LP tokens data (partial removal, split and merge)
+            .non_fungible_data_update_roles(non_fungible_data_update_roles!(
+                non_fungible_data_updater => rule!(require(global_caller(component_address)));
+                non_fungible_data_updater_updater => rule!(require(owner_badge_address));
+            ))
+
             // No one can recall or freeze LP tokens and the creator can't change that
             .recall_roles(recall_roles!(
                 recaller => rule!(deny_all);
@@ -2592,6 +5474,180 @@ mod pool {
             .create_with_no_initial_supply()
         }
 
+        // Create a resource manager for minting fungible LP units
+        fn fungible_lp_resource_manager(
+
+            // Metadata for the LP units
+            lp_name: String,
+            lp_icon_url: UncheckedUrl,
+
+            // CompositeRequirement identifying the coin creator badge, it will be the owner of the LP
+            // units
+            coin_creator_badge_rule: CompositeRequirement,
+
+            // Component owner badge
+            owner_badge_address: ResourceAddress,
+
+            // The address of the pool componet
+            component_address: ComponentAddress,
+
+            // dApp definition account address to set int he metadata
+            dapp_definition: ComponentAddress,
+
+        ) -> FungibleResourceManager // The resource manager to mint the LP units
+        {
+            ResourceBuilder::new_fungible(
+                OwnerRole::Updatable(AccessRule::Protected(coin_creator_badge_rule.clone()))
+            )
+            .divisibility(DIVISIBILITY_MAXIMUM)
+
+            // Everybody can deposit and withdraw LP units and the creator can't change that
+            .deposit_roles(deposit_roles!(
+                depositor => rule!(allow_all);
+                depositor_updater => rule!(deny_all);
+            ))
+            .withdraw_roles(withdraw_roles!(
+                withdrawer => rule!(allow_all);
+                withdrawer_updater => rule!(deny_all);
+            ))
+
+            // No one can recall or freeze LP units and the creator can't change that
+            .recall_roles(recall_roles!(
+                recaller => rule!(deny_all);
+                recaller_updater => rule!(deny_all);
+            ))
+            .freeze_roles(freeze_roles!(
+                freezer => rule!(deny_all);
+                freezer_updater => rule!(deny_all);
+            ))
+
+            // Only this component can mint and burn LP units
+            .burn_roles(burn_roles!(
+                burner => rule!(require(global_caller(component_address)));
+                burner_updater => rule!(require(owner_badge_address));
+            ))
+            .mint_roles(mint_roles!(
+                minter => rule!(require(global_caller(component_address)));
+                minter_updater => rule!(require(owner_badge_address));
+            ))
+
+            // The creator can set LP units metadata
+            .metadata(metadata!(
+                roles {
+                    metadata_setter => AccessRule::Protected(coin_creator_badge_rule.clone());
+                    metadata_setter_updater => AccessRule::Protected(coin_creator_badge_rule.clone());
+                    metadata_locker => AccessRule::Protected(coin_creator_badge_rule.clone());
+                    metadata_locker_updater => AccessRule::Protected(coin_creator_badge_rule);
+                },
+                init {
+                    "name" => lp_name, locked;
+                    "icon_url" => MetadataValue::Url(lp_icon_url), updatable;
+                    "dapp_definition" => dapp_definition, updatable;
+                }
+            ))
+            .create_with_no_initial_supply()
+        }
+
+        // Pool::buy calls this method after each buy during the launch phase.
+        // If a bonding curve launched coin reached its graduation target, the pool goes from
+        // Launching to Normal mode: the raised base coins become the liquidity of a constant product
+        // pool at the final curve price and no more coins can be minted.
+        // In any other case nothing happens.
+        fn graduate(&mut self) -> (
+            Option<HookArgument>, // Short description of the operation happened, to be used by hooks
+            Option<AnyPoolEvent>, // BondingCurveGraduationEvent or None
+        ) {
+            if self.mode != PoolMode::Launching {
+                return (None, None);
+            }
+
+            // Has the graduation target been reached?
+            let price = match self.launch {
+                LaunchType::BondingCurve(ref bonding_curve) => {
+                    let supply = PreciseDecimal::from(bonding_curve.supply_on_curve);
+                    let price = Pool::curve_price(bonding_curve, supply);
+
+                    let target_reached = match bonding_curve.graduation_target {
+                        GraduationTarget::Raise(target) =>
+                            Pool::curve_reserve(bonding_curve, supply) >= PreciseDecimal::from(target),
+                        GraduationTarget::MarketCap(target) =>
+                            price * supply >= PreciseDecimal::from(target),
+                    };
+                    if !target_reached {
+                        return (None, None);
+                    }
+
+                    price.checked_truncate(RoundingMode::ToZero).unwrap()
+                },
+                _ => return (None, None),
+            };
+
+            // Take note of the price during the launch phase before changing mode
+            self.update_price_cumulative();
+
+            self.mode = PoolMode::Normal;
+            self.last_price = price;
+
+            // Make sure the coin / base coin ratio in the pool matches the final curve price, then
+            // disable mint forever
+            let coins_needed = self.base_coin_vault.amount() / price;
+            let (resource_address, supply) = match self.launch {
+                LaunchType::BondingCurve(ref mut bonding_curve) => {
+                    if coins_needed > self.coin_vault.amount() {
+                        self.coin_vault.put(
+                            bonding_curve.resource_manager.mint(coins_needed - self.coin_vault.amount())
+                        );
+                    } else if coins_needed < self.coin_vault.amount() {
+
+                        // Coins sold back to the curve would make the pool open below the curve
+                        // price
+                        self.coin_vault.take(self.coin_vault.amount() - coins_needed).burn();
+                    }
+
+                    bonding_curve.resource_manager.set_mintable(rule!(deny_all));
+                    bonding_curve.resource_manager.lock_mintable();
+
+                    (
+                        bonding_curve.resource_manager.address(),
+                        bonding_curve.resource_manager.total_supply().unwrap(),
+                    )
+                },
+                _ => Runtime::panic(SHOULD_NOT_HAPPEN.to_string()),
+            };
+
+            // This initial liquidity is not owned by anyone, it belongs to the pool itself
+            self.total_lp = self.coin_vault.amount();
+
+            (
+                Some(
+
+                    // Create the HookArgument that RadixPump will use to call hooks
+                    HookArgument {
+                        component: Runtime::global_address().into(),
+                        coin_address: resource_address,
+                        operation: HookableOperation::BondingCurveGraduation,
+                        amount: Some(supply),
+                        mode: PoolMode::Normal,
+                        price: price,
+                        ids: vec![],
+                    }
+                ),
+                Some(
+
+                    // Create the event but let RadixPump emit it
+                    AnyPoolEvent::BondingCurveGraduationEvent(
+                        BondingCurveGraduationEvent {
+                            resource_address: resource_address,
+                            price: price,
+                            raised_base_coins: self.base_coin_vault.amount(),
+                            supply: supply,
+                            coins_in_pool: self.coin_vault.amount(),
+                        }
+                    )
+                )
+            )
+        }
+
         // This method is called at the end of the launch phase of a random launched coin
         fn terminate_random_launch(&mut self) -> (
             Option<FungibleBucket>, // Sale proceeds for the creator
@@ -2758,6 +5814,11 @@ mod pool {
                 LaunchType::Fair(fair_launch) => fair_launch.locked_vault.amount(),
                 LaunchType::Random(random_launch) => random_launch.locked_vault.amount(),
                 LaunchType::Quick(quick_launch) => quick_launch.ignored_coins,
+
+                // During the launch phase of a bonding curve launched coin the pool vault only
+                // contains the coins sold back along the curve
+                LaunchType::BondingCurve(_) if self.mode == PoolMode::Launching => self.coin_vault.amount(),
+
                 _ => Decimal::ZERO,
             }
         }
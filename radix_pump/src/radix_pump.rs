@@ -15,6 +15,7 @@ use std::ops::DerefMut;
 use scrypto::prelude::*;
 use crate::common::*;
 use crate::pool::pool::*;
+use crate::pool::{check_deadline, check_min_amount_out};
 use crate::hook_helpers::*;
 
 // Metadata for this component
@@ -32,29 +33,257 @@ static MIN_LAUNCH_BUY_FEE: Decimal = dec!("0.1");
 // Metadata for the integrator badge
 static INTEGRATOR_BADGE_NAME: &str = "Integrator badge";
 
+// Metadata for the vesting NFT
+static VESTING_NFT_NAME: &str = "Creator vesting NFT";
+
+// Metadata for the referral badge
+static REFERRAL_BADGE_NAME: &str = "Referral badge";
+
+// Metadata for the liquidity lock certificate
+static LOCK_CERTIFICATE_NAME: &str = "Liquidity lock certificate";
+
+// Maximum number of owner actions that can be waiting for the timelock delay at the same time
+static MAX_PENDING_OWNER_ACTIONS: usize = 20;
+
 // Some common error messsages
 static COIN_NOT_FOUND: &str = "Coin not found";
 static UNKNOWN_HOOK: &str = "Unknown hook";
 static UNEXPECTED_METADATA_TYPE: &str = "Unexpected metadata type";
 static WRONG_BADGE: &str = "Wrong badge";
 static SHOULD_NOT_HAPPEN: &str = "Should not happen";
+static MODE_NOT_ALLOWED: &str = "Not allowed in this mode";
 
-// Flash loan transient NFT data
-#[derive(Debug, ScryptoSbor, NonFungibleData)]
-struct FlashLoanData {
+// A single loan in a flash loan transient NFT
+#[derive(Debug, ScryptoSbor, Clone)]
+struct FlashLoan {
     coin_resource_address: ResourceAddress,
     coin_amount: Decimal,
+    side: FlashLoanSide, // If BaseCoin, coin_amount is the amount of borrowed base coins
+    value: Decimal, // Value of the loan in base coins when it was received
+}
+
+// Flash loan transient NFT data, it lists all of the loans received in a single call
+#[derive(Debug, ScryptoSbor, NonFungibleData)]
+struct FlashLoanData {
+    loans: Vec<FlashLoan>,
 }
 
 // Non fungible data for the integrators badge
 #[derive(Debug, ScryptoSbor, NonFungibleData)]
 struct IntegratorData {
+    #[mutable]
     name: String,
     creation_date: Instant,
     #[mutable]
     active: bool,
 }
 
+// Fees paid by an integrator when the component owner overrides the global ones
+#[derive(Debug, ScryptoSbor, Clone, Copy)]
+struct IntegratorFees {
+    creation_fee_percentage: Decimal,
+    buy_sell_fee_percentage: Decimal,
+    flash_loan_fee: Decimal,
+    flash_loan_fee_percentage: Decimal,
+}
+
+// Lifetime statistics for a fee vault (component owner or integrator)
+#[derive(Debug, ScryptoSbor, Clone, Copy)]
+struct IntegratorStats {
+    volume: Decimal, // Base coins involved in the operations that paid fees to this vault
+    fees: Decimal, // Total fees deposited in the vault, including the already withdrawn ones
+}
+
+// Non fungible data for the vesting NFTs
+// A vesting NFT represents a part of a creator allocation that follows the same vesting schedule
+#[derive(Debug, ScryptoSbor, NonFungibleData)]
+struct VestingData {
+    coin_resource_address: ResourceAddress,
+    creation_date: Instant,
+    allocation: Decimal,
+    #[mutable]
+    unlocked_amount: Decimal,
+}
+
+// This event is emitted when a coin creator moves part of his locked allocation to a vesting NFT
+#[derive(ScryptoSbor, ScryptoEvent)]
+struct VestingSplitEvent {
+    resource_address: ResourceAddress,
+    vesting_nft_id: u64,
+    allocation: Decimal,
+    locked_amount: Decimal,
+}
+
+// Non fungible data for the liquidity lock certificates
+// A lock certificate proves that some LP NFTs can't be withdrawn from the RadixPump component
+// before unlock_date
+#[derive(Debug, ScryptoSbor, NonFungibleData)]
+struct LockCertificateData {
+    coin_resource_address: ResourceAddress,
+    lp_resource_address: ResourceAddress,
+    lp_ids: Vec<NonFungibleLocalId>,
+    lp_share: Decimal,
+    lock_date: Instant,
+    #[mutable]
+    unlock_date: Instant, // It can only be postponed
+}
+
+// This event is emitted when some liquidity is locked or when a lock is extended
+#[derive(ScryptoSbor, ScryptoEvent)]
+struct LiquidityLockEvent {
+    resource_address: ResourceAddress,
+    lock_id: u64,
+    lp_share: Decimal,
+    unlock_date: Instant,
+}
+
+// This event is emitted when the holder of a lock certificate withdraws the LP NFTs after the
+// unlock date
+#[derive(ScryptoSbor, ScryptoEvent)]
+struct LiquidityUnlockEvent {
+    resource_address: ResourceAddress,
+    lock_id: u64,
+    lp_share: Decimal,
+}
+
+// This event is emitted when a pool switches from LP NFTs to fungible LP units
+#[derive(ScryptoSbor, ScryptoEvent)]
+struct FungibleLpEnabledEvent {
+    resource_address: ResourceAddress,
+    lp_resource_address: ResourceAddress,
+}
+
+// A sensitive owner action that can be queued and executed after the timelock delay
+#[derive(ScryptoSbor, Clone)]
+pub enum OwnerAction {
+    UpdateFees {
+        creation_fee_percentage: Decimal,
+        buy_sell_fee_percentage: Decimal,
+        flash_loan_fee: Decimal,
+        flash_loan_fee_percentage: Decimal,
+        max_buy_sell_pool_fee_percentage: Decimal,
+        max_creator_fee_percentage: Decimal,
+        minimum_deposit: Decimal,
+    },
+    UpdateTimeLimits {
+        min_launch_duration: i64,
+        min_lock_duration: i64,
+    },
+    RegisterHook {
+        name: String,
+        operations: Vec<String>,
+        component_address: HookInterfaceScryptoStub,
+    },
+    OwnerEnableHook {
+        name: String,
+        operations: Vec<String>,
+    },
+    UpdateTimelockDelay {
+        timelock_delay: i64,
+    },
+    UpdateBaseCoinFlashLoanFee {
+        base_coin_flash_loan_fee: Decimal,
+    },
+    SetIntegratorFees {
+        integrator_id: u64,
+        creation_fee_percentage: Decimal,
+        buy_sell_fee_percentage: Decimal,
+        flash_loan_fee: Decimal,
+        flash_loan_fee_percentage: Decimal,
+    },
+    UpdateReferralTiers {
+        referral_tiers: Vec<(Decimal, Decimal)>,
+    },
+    RemoveIntegratorFees {
+        integrator_id: u64,
+    },
+}
+
+// An owner action waiting for its timelock delay to expire
+#[derive(ScryptoSbor, Clone)]
+pub struct PendingOwnerAction {
+    pub id: u64,
+    pub action: OwnerAction,
+    pub executable_time: i64, // The action can't be executed before this time
+}
+
+// This event is emitted when the component owner queues a sensitive action
+#[derive(ScryptoSbor, ScryptoEvent)]
+struct OwnerActionQueuedEvent {
+    id: u64,
+    action: OwnerAction,
+    executable_time: i64,
+}
+
+// This event is emitted when a queued owner action is executed
+#[derive(ScryptoSbor, ScryptoEvent)]
+struct OwnerActionExecutedEvent {
+    id: u64,
+}
+
+// This event is emitted when the component owner cancels a queued action
+#[derive(ScryptoSbor, ScryptoEvent)]
+struct OwnerActionCancelledEvent {
+    id: u64,
+}
+
+// This event is emitted when the component owner or the guardian pauses the whole component
+// (resource_address = None) or a single pool
+#[derive(ScryptoSbor, ScryptoEvent)]
+struct PauseEvent {
+    resource_address: Option<ResourceAddress>,
+}
+
+// This event is emitted when the component owner unpauses the whole component (resource_address =
+// None) or a single pool
+#[derive(ScryptoSbor, ScryptoEvent)]
+struct UnpauseEvent {
+    resource_address: Option<ResourceAddress>,
+}
+
+// Non fungible data for the referral badges
+#[derive(Debug, ScryptoSbor, NonFungibleData)]
+struct ReferralData {
+    creation_date: Instant,
+    #[mutable]
+    volume: Decimal, // Base coins traded by the referred users
+    #[mutable]
+    tier: u8, // Index in the referral_tiers list, it can only grow
+}
+
+// This event is emitted every time a referrer is credited for an operation
+#[derive(ScryptoSbor, ScryptoEvent)]
+struct ReferralFeeEvent {
+    referral_id: u64,
+    integrator_id: u64,
+    volume: Decimal,
+    fee: Decimal,
+}
+
+// This event is emitted when the volume generated by a referrer allows him to move to an higher
+// tier
+#[derive(ScryptoSbor, ScryptoEvent)]
+struct ReferralTierUpgradeEvent {
+    referral_id: u64,
+    tier: u8,
+    volume: Decimal,
+}
+
+// This event is emitted when a referrer withdraws his fees
+#[derive(ScryptoSbor, ScryptoEvent)]
+struct ReferralFeeClaimedEvent {
+    referral_id: u64,
+    amount: Decimal,
+}
+
+// This event is emitted when a coin creator withdraws his share of the trading fees
+#[derive(ScryptoSbor, ScryptoEvent)]
+struct CreatorFeeClaimedEvent {
+    resource_address: ResourceAddress,
+    creator_id: u64,
+    amount: Decimal,
+}
+
 // This event is emitted when a coin creator enables an hook for his pool (resource_address is Some), or when the componet owner enables an event globally (resource_address is None)
 #[derive(ScryptoSbor, ScryptoEvent)]
 struct HookEnabledEvent {
@@ -79,6 +308,10 @@ struct PoolStruct {
     component_address: RadixPumpPoolInterfaceScryptoStub,
     enabled_hooks: HooksPerOperation,
     creator_id: u64,
+    creator_fee_percentage: Decimal, // Copy of the pool one, avoids calling the pool on every swap
+    referral_id: Option<u64>, // Referrer of the coin creator, credited when the creation fee is paid
+    integrator_id: u64, // Integrator used to create the coin, it receives the creation fee
+    locked_lp_share: Decimal, // Total lp_share of the LP NFTs locked in the liquidity locker
 }
 
 #[blueprint]
@@ -99,6 +332,26 @@ struct PoolStruct {
     RemoveLiquidityEvent,
     HookEnabledEvent,
     HookDisabledEvent,
+    VestingSplitEvent,
+    CreatorFeeClaimedEvent,
+    ReferralFeeEvent,
+    ReferralTierUpgradeEvent,
+    ReferralFeeClaimedEvent,
+    BondingCurveLaunchEvent,
+    BondingCurveGraduationEvent,
+    DutchAuctionStartEvent,
+    DutchAuctionEndEvent,
+    CommitBidEvent,
+    SoftCapNotReachedEvent,
+    RefundEvent,
+    PauseEvent,
+    UnpauseEvent,
+    OwnerActionQueuedEvent,
+    OwnerActionExecutedEvent,
+    OwnerActionCancelledEvent,
+    FungibleLpEnabledEvent,
+    LiquidityLockEvent,
+    LiquidityUnlockEvent,
 )]
 #[types(
     CreatorData,
@@ -113,14 +366,27 @@ struct PoolStruct {
     IntegratorData,
     u64,
     FungibleVault,
+    VestingData,
+    FlashLoanSide,
+    FlashLoan,
+    ReferralData,
+    IntegratorFees,
+    IntegratorStats,
+    PendingOwnerAction,
+    LockCertificateData,
+    NonFungibleVault,
 )]
 mod radix_pump {
 
     enable_method_auth! {
+        roles {
+            guardian => updatable_by: [OWNER];
+        },
         methods {
             forbid_symbols => restrict_to: [OWNER];
             forbid_names => restrict_to: [OWNER];
             update_fees => restrict_to: [OWNER];
+            update_base_coin_flash_loan_fee => restrict_to: [OWNER];
             owner_set_liquidation_mode => restrict_to: [OWNER];
             update_time_limits => restrict_to: [OWNER];
             register_hook => restrict_to: [OWNER];
@@ -128,35 +394,79 @@ mod radix_pump {
             owner_enable_hook => restrict_to: [OWNER];
             owner_disable_hook => restrict_to: [OWNER];
             new_integrator => restrict_to: [OWNER];
+            update_integrator => restrict_to: [OWNER];
+            set_integrator_fees => restrict_to: [OWNER];
+            remove_integrator_fees => restrict_to: [OWNER];
             update_dapp_definition => restrict_to: [OWNER];
             get_badges => restrict_to: [OWNER];
+            update_referral_tiers => restrict_to: [OWNER];
+            pause => restrict_to: [OWNER, guardian];
+            unpause => restrict_to: [OWNER];
+            pause_pool => restrict_to: [OWNER, guardian];
+            unpause_pool => restrict_to: [OWNER];
+            update_timelock_delay => restrict_to: [OWNER];
+            queue_owner_action => restrict_to: [OWNER];
+            execute_owner_action => restrict_to: [OWNER];
+            cancel_owner_action => restrict_to: [OWNER];
+            enable_fungible_lp => restrict_to: [OWNER];
 
             new_fair_launch => PUBLIC;
             new_quick_launch => PUBLIC;
             new_random_launch => PUBLIC;
+            new_bonding_curve_launch => PUBLIC;
+            new_dutch_auction => PUBLIC;
             new_pool => restrict_to: [OWNER];
             new_launched_pool => restrict_to: [OWNER];
 
             creator_set_liquidation_mode => PUBLIC;
             update_pool_fees => PUBLIC;
+            update_coin_metadata => PUBLIC;
+            get_creator_fees => PUBLIC;
             launch => PUBLIC;
             terminate_launch => PUBLIC;
             unlock => PUBLIC;
+            split_vesting => PUBLIC;
+            unlock_vesting => PUBLIC;
             creator_enable_hook => PUBLIC;
             creator_disable_hook => PUBLIC;
             burn => PUBLIC;
 
             get_flash_loan => PUBLIC;
+            get_flash_loans => PUBLIC;
             return_flash_loan => PUBLIC;
+            get_base_coin_flash_loan => PUBLIC;
+            return_base_coin_flash_loan => PUBLIC;
             buy_ticket => PUBLIC;
             redeem_ticket => PUBLIC;
+            commit_bid => PUBLIC;
+            redeem_bid => PUBLIC;
+            refund => PUBLIC;
             add_liquidity => PUBLIC;
             remove_liquidity => PUBLIC;
+            remove_liquidity_partial => PUBLIC;
+            split_lp => PUBLIC;
+            merge_lp => PUBLIC;
+            zap_in => PUBLIC;
+            zap_out => PUBLIC;
+            lock_liquidity => PUBLIC;
+            extend_liquidity_lock => PUBLIC;
+            unlock_liquidity => PUBLIC;
+            get_locked_lp_rewards => PUBLIC;
             swap => PUBLIC;
+            swap_exact_out => PUBLIC;
 
             get_pool_info => PUBLIC;
+            get_twap => PUBLIC;
+            get_lp_position => PUBLIC;
+            quote_swap => PUBLIC;
+            quote_buy => PUBLIC;
+            quote_sell => PUBLIC;
 
             get_fees => PUBLIC;
+            get_integrator_stats => PUBLIC;
+            get_pending_owner_actions => PUBLIC;
+            new_referral => PUBLIC;
+            get_referral_fees => PUBLIC;
         }
     }
 
@@ -199,9 +509,20 @@ mod radix_pump {
         // loan
         flash_loan_fee: Decimal,
 
+        // Percentage of the value of the borrowed coins paid to the component owner or to an
+        // integrator when doing a flash loan, it adds to flash_loan_fee
+        flash_loan_fee_percentage: Decimal,
+
+        // Fee (in base coins) paid to the component owner or to an integrator when doing a base
+        // coin flash loan; flash_loan_fee_percentage adds to it
+        base_coin_flash_loan_fee: Decimal,
+
         // Coin creators are not allowed to set buy and sell pool fees higher than this
         max_buy_sell_pool_fee_percentage: Decimal,
 
+        // Coin creators are not allowed to set a creator fee higher than this
+        max_creator_fee_percentage: Decimal,
+
         // Minimum duration (in seconds) of the launch phase of a fair launch or random launch
         min_launch_duration: i64,
 
@@ -232,11 +553,70 @@ mod radix_pump {
         // Key N contains the fees paid to the integrator with badge id N
         fee_vaults: KeyValueStore<u64, FungibleVault>,
 
+        // When the component is paused swaps, flash loans, add_liquidity, buy_ticket and commit_bid
+        // are not allowed on any pool
+        paused: bool,
+
+        // Minimum number of seconds between queuing and executing a sensitive owner action; when
+        // it is zero the owner can also call the sensitive methods directly
+        timelock_delay: i64,
+
+        // Owner actions queued and not yet executed or cancelled
+        pending_owner_actions: Vec<PendingOwnerAction>,
+
+        // Numeric id of the next owner action to queue
+        next_owner_action_id: u64,
+
+        // Coin address for each fungible LP resource, used to find the pool when removing
+        // liquidity
+        fungible_lp_coins: KeyValueStore<ResourceAddress, ResourceAddress>,
+
         // Numeric id of the next integrator badge to create
         next_integrator_badge_id: u64,
 
+        // Fees overridden by the component owner for specific integrators
+        // The key is the numeric id of the integrator badge
+        integrator_fees: KeyValueStore<u64, IntegratorFees>,
+
+        // Volume and fees collected by each fee vault (same keys as fee_vaults)
+        integrator_stats: KeyValueStore<u64, IntegratorStats>,
+
         // dApp definition account address for components and resources
         dapp_definition: ComponentAddress,
+
+        // Resource manager to mint vesting NFTs
+        vesting_nft_resource_manager: NonFungibleResourceManager,
+
+        // Numeric id of the next vesting NFT to create
+        next_vesting_nft_id: u64,
+
+        // This KVS contains the vaults where the creator share of the trading fees is collected
+        // The key is the numeric id of the creator badge
+        creator_fee_vaults: KeyValueStore<u64, FungibleVault>,
+
+        // Resource manager to mint referral badges
+        referral_badge_resource_manager: NonFungibleResourceManager,
+
+        // Numeric id of the next referral badge to create
+        next_referral_id: u64,
+
+        // Referral tiers as (minimum volume, percentage of the owner or integrator fee paid to the
+        // referrer), sorted by minimum volume; the first tier always has zero minimum volume
+        referral_tiers: Vec<(Decimal, Decimal)>,
+
+        // This KVS contains the vaults where the referrer fees are collected
+        // The key is the numeric id of the referral badge
+        referral_fee_vaults: KeyValueStore<u64, FungibleVault>,
+
+        // Resource manager to mint liquidity lock certificates
+        lock_certificate_resource_manager: NonFungibleResourceManager,
+
+        // Numeric id of the next lock certificate to create
+        next_lock_id: u64,
+
+        // This KVS contains the vaults where the locked LP NFTs are kept
+        // The key is the numeric id of the lock certificate
+        locked_lp_vaults: KeyValueStore<u64, NonFungibleVault>,
     }
 
     impl RadixPump {
@@ -425,7 +805,69 @@ mod radix_pump {
                 minter_updater => rule!(require(owner_badge_address));
             ))
             .non_fungible_data_update_roles(non_fungible_data_update_roles!(
-                non_fungible_data_updater => rule!(require(owner_badge_address));
+                non_fungible_data_updater => rule!(
+                    require(owner_badge_address) || require(global_caller(component_address))
+                );
+                non_fungible_data_updater_updater => rule!(require(owner_badge_address));
+            ))
+            .burn_roles(burn_roles!(
+                burner => rule!(deny_all);
+                burner_updater => rule!(require(owner_badge_address));
+            ))
+            .create_with_no_initial_supply();
+
+            // Create a ResourceManager for minting vesting NFTs
+            let vesting_nft_resource_manager = <scrypto::prelude::ResourceBuilder as RadixPumpResourceBuilder>::new_integer_non_fungible_with_registered_type::<VestingData>(
+                OwnerRole::Updatable(rule!(require(owner_badge_address)))
+            )
+            .metadata(metadata!(
+                roles {
+                    metadata_setter => rule!(require(owner_badge_address));
+                    metadata_setter_updater => rule!(require(owner_badge_address));
+                    metadata_locker => rule!(require(owner_badge_address));
+                    metadata_locker_updater => rule!(require(owner_badge_address));
+                },
+                init {
+                    "name" => VESTING_NFT_NAME, updatable;
+                    "dapp_definition" => dapp_definition, updatable;
+                }
+            ))
+            .mint_roles(mint_roles!(
+                minter => rule!(require(global_caller(component_address)));
+                minter_updater => rule!(require(owner_badge_address));
+            ))
+            .non_fungible_data_update_roles(non_fungible_data_update_roles!(
+                non_fungible_data_updater => rule!(require(global_caller(component_address)));
+                non_fungible_data_updater_updater => rule!(require(owner_badge_address));
+            ))
+            .burn_roles(burn_roles!(
+                burner => rule!(allow_all);
+                burner_updater => rule!(require(owner_badge_address));
+            ))
+            .create_with_no_initial_supply();
+
+            // Create a ResourceManager for minting referral badges
+            let referral_badge_resource_manager = <scrypto::prelude::ResourceBuilder as RadixPumpResourceBuilder>::new_integer_non_fungible_with_registered_type::<ReferralData>(
+                OwnerRole::Updatable(rule!(require(owner_badge_address)))
+            )
+            .metadata(metadata!(
+                roles {
+                    metadata_setter => rule!(require(owner_badge_address));
+                    metadata_setter_updater => rule!(require(owner_badge_address));
+                    metadata_locker => rule!(require(owner_badge_address));
+                    metadata_locker_updater => rule!(require(owner_badge_address));
+                },
+                init {
+                    "name" => REFERRAL_BADGE_NAME, updatable;
+                    "dapp_definition" => dapp_definition, updatable;
+                }
+            ))
+            .mint_roles(mint_roles!(
+                minter => rule!(require(global_caller(component_address)));
+                minter_updater => rule!(require(owner_badge_address));
+            ))
+            .non_fungible_data_update_roles(non_fungible_data_update_roles!(
+                non_fungible_data_updater => rule!(require(global_caller(component_address)));
                 non_fungible_data_updater_updater => rule!(require(owner_badge_address));
             ))
             .burn_roles(burn_roles!(
@@ -434,6 +876,36 @@ mod radix_pump {
             ))
             .create_with_no_initial_supply();
 
+            // Create a ResourceManager for minting liquidity lock certificates
+            let lock_certificate_resource_manager = <scrypto::prelude::ResourceBuilder as RadixPumpResourceBuilder>::new_integer_non_fungible_with_registered_type::<LockCertificateData>(
+                OwnerRole::Updatable(rule!(require(owner_badge_address)))
+            )
+            .metadata(metadata!(
+                roles {
+                    metadata_setter => rule!(require(owner_badge_address));
+                    metadata_setter_updater => rule!(require(owner_badge_address));
+                    metadata_locker => rule!(require(owner_badge_address));
+                    metadata_locker_updater => rule!(require(owner_badge_address));
+                },
+                init {
+                    "name" => LOCK_CERTIFICATE_NAME, updatable;
+                    "dapp_definition" => dapp_definition, updatable;
+                }
+            ))
+            .mint_roles(mint_roles!(
+                minter => rule!(require(global_caller(component_address)));
+                minter_updater => rule!(require(owner_badge_address));
+            ))
+            .non_fungible_data_update_roles(non_fungible_data_update_roles!(
+                non_fungible_data_updater => rule!(require(global_caller(component_address)));
+                non_fungible_data_updater_updater => rule!(require(owner_badge_address));
+            ))
+            .burn_roles(burn_roles!(
+                burner => rule!(require(global_caller(component_address)));
+                burner_updater => rule!(require(owner_badge_address));
+            ))
+            .create_with_no_initial_supply();
+
             // Instantiate the component
             Self {
                 owner_badge_address: owner_badge_address,
@@ -449,7 +921,10 @@ mod radix_pump {
                 creation_fee_percentage: creation_fee_percentage,
                 buy_sell_fee_percentage: buy_sell_fee_percentage,
                 flash_loan_fee: flash_loan_fee,
+                flash_loan_fee_percentage: Decimal::ZERO,
+                base_coin_flash_loan_fee: flash_loan_fee,
                 max_buy_sell_pool_fee_percentage: dec!(10),
+                max_creator_fee_percentage: dec!(1),
                 min_launch_duration: 604800, // One week
                 min_lock_duration: 7776000, // Three months
                 proxy_badge_vault: FungibleVault::with_bucket(proxy_badge_bucket),
@@ -459,11 +934,33 @@ mod radix_pump {
                 globally_enabled_hooks: HooksPerOperation::new(),
                 integrator_badge_resource_manager: integrator_badge_resource_manager,
                 fee_vaults: <KeyValueStore<u64, FungibleVault> as RadixPumpKeyValueStore>::new_with_registered_type(),
+                paused: false,
+                timelock_delay: 0,
+                pending_owner_actions: vec![],
+                next_owner_action_id: 1,
+                fungible_lp_coins: <KeyValueStore<ResourceAddress, ResourceAddress> as RadixPumpKeyValueStore>::new_with_registered_type(),
                 next_integrator_badge_id: 1,
+                integrator_fees: <KeyValueStore<u64, IntegratorFees> as RadixPumpKeyValueStore>::new_with_registered_type(),
+                integrator_stats: <KeyValueStore<u64, IntegratorStats> as RadixPumpKeyValueStore>::new_with_registered_type(),
                 dapp_definition: dapp_definition,
+                vesting_nft_resource_manager: vesting_nft_resource_manager,
+                next_vesting_nft_id: 1,
+                creator_fee_vaults: <KeyValueStore<u64, FungibleVault> as RadixPumpKeyValueStore>::new_with_registered_type(),
+                referral_badge_resource_manager: referral_badge_resource_manager,
+                next_referral_id: 1,
+                referral_tiers: vec![(Decimal::ZERO, dec!(10))],
+                referral_fee_vaults: <KeyValueStore<u64, FungibleVault> as RadixPumpKeyValueStore>::new_with_registered_type(),
+                lock_certificate_resource_manager: lock_certificate_resource_manager,
+                next_lock_id: 1,
+                locked_lp_vaults: <KeyValueStore<u64, NonFungibleVault> as RadixPumpKeyValueStore>::new_with_registered_type(),
             }
             .instantiate()
             .prepare_to_globalize(OwnerRole::Updatable(rule!(require(owner_badge_address))))
+
+            // The component owner can assign the guardian role later, it can only pause
+            .roles(roles!(
+                guardian => rule!(deny_all);
+            ))
             .with_address(address_reservation)
             .metadata(metadata! {
                 init {
@@ -524,6 +1021,71 @@ mod radix_pump {
             }
         }
 
+        // Verify that the access restrictions specified by a coin creator for a gated launch are
+        // consistent
+        fn check_access_rule(
+            &self,
+            access_rule: &Option<LaunchAccessRule>,
+            max_buy_per_holder: Option<Decimal>,
+        ) {
+            if let Some(LaunchAccessRule::NonFungibles(ids)) = access_rule {
+                assert!(
+                    !ids.is_empty(),
+                    "Empty allowlist",
+                );
+            }
+            if let Some(max_buy_per_holder) = max_buy_per_holder {
+                assert!(
+                    max_buy_per_holder > Decimal::ZERO,
+                    "Per holder cap must be bigger than zero",
+                );
+
+                // A single holder can only be identified by a non fungible
+                assert!(
+                    match access_rule {
+                        None => false,
+                        Some(LaunchAccessRule::Resource(resource_address)) => !resource_address.is_fungible(),
+                        Some(LaunchAccessRule::NonFungibles(_)) => true,
+                    },
+                    "A per holder cap requires a non fungible access rule",
+                );
+            }
+        }
+
+        // Verify that the vesting schedule specified by a coin creator is consistent and return it,
+        // or the default one if the creator didn't specify any
+        fn check_vesting_schedule(
+            &self,
+            vesting_schedule: Option<VestingSchedule>,
+        ) -> VestingSchedule {
+            match vesting_schedule {
+                None => VestingSchedule {
+                    cliff_duration: 0,
+                    vesting_type: VestingType::Linear,
+                    tge_percentage: Decimal::ZERO,
+                },
+                Some(vesting_schedule) => {
+                    assert!(
+                        vesting_schedule.cliff_duration >= 0,
+                        "Cliff duration can't be negative",
+                    );
+                    assert!(
+                        vesting_schedule.tge_percentage >= Decimal::ZERO &&
+                        vesting_schedule.tge_percentage <= dec!(100),
+                        "TGE percentage must be between 0 and 100",
+                    );
+                    if let VestingType::Steps(steps) = vesting_schedule.vesting_type {
+                        assert!(
+                            steps > 0,
+                            "Number of steps must be bigger than zero",
+                        );
+                    }
+
+                    vesting_schedule
+                },
+            }
+        }
+
         // Verify that the metadata specified by a coin creator are acceptable, then register symbol
         // and name as no longer available, finally return a canonical version of the metadata
         fn check_metadata(
@@ -573,12 +1135,29 @@ mod radix_pump {
             );
             self.forbidden_names.insert(uppercase_coin_name, true);
 
-            coin_icon_url = coin_icon_url.trim().to_string();
-            coin_info_url = coin_info_url.trim().to_string();
+            (coin_icon_url, coin_info_url) = self.check_urls(coin_icon_url, coin_info_url);
 
             (coin_symbol, coin_name, coin_icon_url, coin_info_url)
         }
 
+        // Verify and normalize the icon and info URLs specified by a coin creator
+        fn check_urls(
+            &self,
+            coin_icon_url: String,
+            coin_info_url: String,
+        ) -> (
+            String, // icon_url
+            String  // info_url
+        ) {
+            let coin_icon_url = coin_icon_url.trim().to_string();
+            assert!(
+                coin_icon_url.len() > 0,
+                "Coin icon URL can't be empty",
+            );
+
+            (coin_icon_url, coin_info_url.trim().to_string())
+        }
+
         // Instantiate a pool component to perform a random launch 
         pub fn new_random_launch(
             &mut self,
@@ -610,6 +1189,17 @@ mod radix_pump {
             // How many coins a winning ticket will receive
             coins_per_winning_ticket: Decimal,
 
+            // Optional restriction on who can buy tickets during the launch phase (None = everybody)
+            access_rule: Option<LaunchAccessRule>,
+
+            // Optional maximum amount of base coins (fees included) a single holder can spend in
+            // tickets; it requires a non fungible access rule
+            max_buy_per_holder: Option<Decimal>,
+
+            // Optional vesting schedule for the creator allocation (None = linear vesting from the
+            // end of the launch to the unlocking time, no cliff, no TGE share)
+            vesting_schedule: Option<VestingSchedule>,
+
             // Percentage fee paid to the pool when buying coins and tickets
             buy_pool_fee_percentage: Decimal,
 
@@ -619,11 +1209,23 @@ mod radix_pump {
             // Amount of base coins paid to the pool when taking a flash loan
             flash_loan_pool_fee: Decimal,
 
+            // The id of the integrator whose interface has been used
+            integrator_id: u64,
+
+            // Numeric id of the referral badge of the user who referred the coin creator
+            referral_id: Option<u64>,
+
         ) -> NonFungibleBucket // Creator badge
         {
             // Verify that the fees specified by the coin creator are acceptable
             self.check_fees(buy_pool_fee_percentage, sell_pool_fee_percentage, flash_loan_pool_fee, true);
 
+            // Verify that the access restrictions are consistent
+            self.check_access_rule(&access_rule, max_buy_per_holder);
+
+            // Verify that the vesting schedule is consistent
+            let vesting_schedule = self.check_vesting_schedule(vesting_schedule);
+
             // Verify and normaliize the metadata specified by the coin creator
             (coin_symbol, coin_name, coin_icon_url, coin_info_url) =
                 self.check_metadata(coin_symbol, coin_name, coin_icon_url, coin_info_url);
@@ -642,6 +1244,9 @@ mod radix_pump {
                 ticket_price,
                 winning_tickets,
                 coins_per_winning_ticket,
+                access_rule,
+                max_buy_per_holder,
+                vesting_schedule,
                 buy_pool_fee_percentage,
                 sell_pool_fee_percentage,
                 flash_loan_pool_fee,
@@ -657,9 +1262,18 @@ mod radix_pump {
                     component_address: pool.into(),
                     enabled_hooks: HooksPerOperation::new(),
                     creator_id: self.next_creator_badge_id,
+                    creator_fee_percentage: Decimal::ZERO,
+                    referral_id: self.check_referral_id(referral_id),
+                    integrator_id: self.check_integrator_id(integrator_id),
+                    locked_lp_share: Decimal::ZERO,
                 }
             );
 
+            // A pool created while the component is paused starts paused too
+            if self.paused {
+                self.set_pool_globally_paused(coin_resource_address, true);
+            }
+
             // Mint a badge for the coin creator containing some information about the
             // coin and the pool and return it
             self.mint_creator_badge(
@@ -700,6 +1314,25 @@ mod radix_pump {
             // The percentage of coins that will be reserved (locked) for the coin creator
             creator_locked_percentage: Decimal,
 
+            // Optional minimum amount of base coins (fees included) to raise during the launch
+            // phase; if it is not reached buyers can get a refund
+            soft_cap: Option<Decimal>,
+
+            // Optional maximum amount of base coins (fees included) to raise during the launch
+            // phase
+            hard_cap: Option<Decimal>,
+
+            // Optional restriction on who can buy during the launch phase (None = everybody)
+            access_rule: Option<LaunchAccessRule>,
+
+            // Optional maximum amount of base coins (fees included) a single holder can spend
+            // during the launch phase; it requires a non fungible access rule
+            max_buy_per_holder: Option<Decimal>,
+
+            // Optional vesting schedule for the creator allocation (None = linear vesting from the
+            // end of the launch to the unlocking time, no cliff, no TGE share)
+            vesting_schedule: Option<VestingSchedule>,
+
             // Percentage fee paid to the pool when buying coins and tickets
             buy_pool_fee_percentage: Decimal,
 
@@ -709,11 +1342,37 @@ mod radix_pump {
             // Amount of base coins paid to the pool when taking a flash loan
             flash_loan_pool_fee: Decimal,
 
+            // The id of the integrator whose interface has been used
+            integrator_id: u64,
+
+            // Numeric id of the referral badge of the user who referred the coin creator
+            referral_id: Option<u64>,
+
         ) -> NonFungibleBucket // Creator badge
         {
             // Verify that the fees specified by the coin creator are acceptable
             self.check_fees(buy_pool_fee_percentage, sell_pool_fee_percentage, flash_loan_pool_fee, true);
 
+            // Verify that the caps are consistent
+            assert!(
+                soft_cap.is_none() || soft_cap.unwrap() > Decimal::ZERO,
+                "Soft cap must be bigger than zero",
+            );
+            assert!(
+                hard_cap.is_none() || hard_cap.unwrap() > Decimal::ZERO,
+                "Hard cap must be bigger than zero",
+            );
+            assert!(
+                soft_cap.is_none() || hard_cap.is_none() || soft_cap.unwrap() <= hard_cap.unwrap(),
+                "Soft cap can't be bigger than hard cap",
+            );
+
+            // Verify that the access restrictions are consistent
+            self.check_access_rule(&access_rule, max_buy_per_holder);
+
+            // Verify that the vesting schedule is consistent
+            let vesting_schedule = self.check_vesting_schedule(vesting_schedule);
+
             // Verify and normaliize the metadata specified by the coin creator
             (coin_symbol, coin_name, coin_icon_url, coin_info_url) =
                 self.check_metadata(coin_symbol, coin_name, coin_icon_url, coin_info_url);
@@ -731,6 +1390,11 @@ mod radix_pump {
                 coin_social_url,
                 launch_price,
                 creator_locked_percentage,
+                soft_cap,
+                hard_cap,
+                access_rule,
+                max_buy_per_holder,
+                vesting_schedule,
                 buy_pool_fee_percentage,
                 sell_pool_fee_percentage,
                 flash_loan_pool_fee,
@@ -746,9 +1410,18 @@ mod radix_pump {
                     component_address: pool.into(),
                     enabled_hooks: HooksPerOperation::new(),
                     creator_id: self.next_creator_badge_id,
+                    creator_fee_percentage: Decimal::ZERO,
+                    referral_id: self.check_referral_id(referral_id),
+                    integrator_id: self.check_integrator_id(integrator_id),
+                    locked_lp_share: Decimal::ZERO,
                 }
             );
 
+            // A pool created while the component is paused starts paused too
+            if self.paused {
+                self.set_pool_globally_paused(coin_resource_address, true);
+            }
+
             // Mint a badge for the coin creator containing some information about the
             // coin and the pool and return it
             self.mint_creator_badge(
@@ -802,6 +1475,19 @@ mod radix_pump {
             // Amount of base coins paid to the pool when taking a flash loan
             flash_loan_pool_fee: Decimal,
 
+            // Optional opening window: during it buys are capped and pay a decaying extra fee to the
+            // pool
+            anti_sniping_window: Option<AntiSnipingWindow>,
+
+            // Whether the pool has to issue fungible LP units instead of LP NFTs
+            fungible_lp: bool,
+
+            // The id of the integrator whose interface has been used
+            integrator_id: u64,
+
+            // Numeric id of the referral badge of the user who referred the coin creator
+            referral_id: Option<u64>,
+
         ) -> (
             NonFungibleBucket, // Creator badge
             Bucket, // Creator share of the launched coin
@@ -821,13 +1507,20 @@ mod radix_pump {
                 "Insufficient base coin deposit",
             );
 
-            // Pay the component owner share of the coin deposit
-            self.deposit_fee(
-                0,
-                base_coin_bucket.take_advanced(
-                    self.creation_fee_percentage * base_coin_bucket.amount() / 100,
-                    WithdrawStrategy::Rounded(RoundingMode::ToZero),
-                )
+            // Pay the component owner (or integrator) share of the coin deposit, part of it may go
+            // to the referrer
+            let integrator_id = self.check_integrator_id(integrator_id);
+            let referral_id = self.check_referral_id(referral_id);
+            let volume = base_coin_bucket.amount();
+            let fee_bucket = base_coin_bucket.take_advanced(
+                self.integrator_fees(integrator_id).creation_fee_percentage * volume / 100,
+                WithdrawStrategy::Rounded(RoundingMode::ToZero),
+            );
+            self.deposit_fee_with_referral(
+                integrator_id,
+                referral_id,
+                volume,
+                fee_bucket,
             );
 
             // Verify that the fees specified by the coin creator are acceptable
@@ -854,6 +1547,7 @@ mod radix_pump {
                 buy_pool_fee_percentage,
                 sell_pool_fee_percentage,
                 flash_loan_pool_fee,
+                anti_sniping_window,
                 self.next_creator_badge_rule(),
                 self.dapp_definition,
             );
@@ -869,9 +1563,23 @@ mod radix_pump {
                     component_address: pool.into(),
                     enabled_hooks: HooksPerOperation::new(),
                     creator_id: self.next_creator_badge_id,
+                    creator_fee_percentage: Decimal::ZERO,
+                    referral_id: referral_id,
+                    integrator_id: integrator_id,
+                    locked_lp_share: Decimal::ZERO,
                 }
             );
 
+            // A pool created while the component is paused starts paused too
+            if self.paused {
+                self.set_pool_globally_paused(coin_address, true);
+            }
+
+            // Switch the pool to fungible LP units if requested
+            if fungible_lp {
+                self.set_fungible_lp(coin_address);
+            }
+
             // Mint a badge for the coin creator containing some information about the
             // coin and the pool
             let creator_badge_bucket = self.mint_creator_badge(
@@ -893,6 +1601,274 @@ mod radix_pump {
             (creator_badge_bucket, creator_coin_bucket, buckets1, buckets2)
         }
 
+        // Instantiate a pool component to perform a bonding curve launch
+        // Coins are minted when bought along the price curve, no initial deposit is required
+        pub fn new_bonding_curve_launch(
+            &mut self,
+
+            // Symbol of the coin to launch
+            mut coin_symbol: String,
+
+            // Name of the coin to launch
+            mut coin_name: String,
+
+            // Icon_url of the coin to launch
+            mut coin_icon_url: String,
+
+            // Description of the coin to launch (can be empty)
+            coin_description: String,
+
+            // Info_url of the coin to launch (can be empty)
+            mut coin_info_url: String,
+
+            // Array of URLs of the socials of the coin (can be empty)
+            coin_social_url: Vec<String>,
+
+            // Shape of the price curve (Linear or Exponential)
+            curve: BondingCurveType,
+
+            // Price of the first coin sold along the curve
+            start_price: Decimal,
+
+            // How fast the price grows with the supply
+            slope: Decimal,
+
+            // The raise or market cap that makes the pool graduate to a constant product pool
+            graduation_target: GraduationTarget,
+
+            // Percentage fee paid to the pool when buying coins
+            buy_pool_fee_percentage: Decimal,
+
+            // Percentage fee paid to the pool when selling coins
+            sell_pool_fee_percentage: Decimal,
+
+            // Amount of base coins paid to the pool when taking a flash loan
+            flash_loan_pool_fee: Decimal,
+
+            // The id of the integrator whose interface has been used
+            integrator_id: u64,
+
+            // Numeric id of the referral badge of the user who referred the coin creator
+            referral_id: Option<u64>,
+
+        ) -> (
+            NonFungibleBucket, // Creator badge
+            Vec<Bucket>, // Eventual additional buckets created by the BondingCurveLaunch hooks
+        ) {
+            // Verify that the fees specified by the coin creator are acceptable
+            self.check_fees(buy_pool_fee_percentage, sell_pool_fee_percentage, flash_loan_pool_fee, false);
+
+            // Verify that the curve makes sense
+            assert!(
+                start_price > Decimal::ZERO,
+                "Start price must be bigger than zero",
+            );
+            assert!(
+                slope >= Decimal::ZERO,
+                "Slope can't be a negative number",
+            );
+            match graduation_target {
+                GraduationTarget::Raise(target) | GraduationTarget::MarketCap(target) => assert!(
+                    target > Decimal::ZERO,
+                    "Graduation target must be bigger than zero",
+                ),
+            }
+
+            // Verify and normaliize the metadata specified by the coin creator
+            (coin_symbol, coin_name, coin_icon_url, coin_info_url) =
+                self.check_metadata(coin_symbol, coin_name, coin_icon_url, coin_info_url);
+
+            // Instantiate the pool component
+            let (pool, coin_resource_address, lp_resource_address, hook_argument, event) = Pool::new_bonding_curve_launch(
+                self.owner_badge_address,
+                self.proxy_badge_vault.resource_address(),
+                self.hook_badge_vault.resource_address(),
+                coin_symbol.clone(),
+                coin_name.clone(),
+                coin_icon_url.clone(),
+                coin_description,
+                coin_info_url,
+                coin_social_url,
+                curve,
+                start_price,
+                slope,
+                graduation_target,
+                buy_pool_fee_percentage,
+                sell_pool_fee_percentage,
+                flash_loan_pool_fee,
+                self.next_creator_badge_rule(),
+                self.base_coin_address,
+                self.dapp_definition,
+            );
+
+            // Emit a BondingCurveLaunchEvent
+            self.emit_pool_event(event, 0);
+
+            // Add the pool information in the pools KVS
+            self.pools.insert(
+                coin_resource_address,
+                PoolStruct {
+                    component_address: pool.into(),
+                    enabled_hooks: HooksPerOperation::new(),
+                    creator_id: self.next_creator_badge_id,
+                    creator_fee_percentage: Decimal::ZERO,
+                    referral_id: self.check_referral_id(referral_id),
+                    integrator_id: self.check_integrator_id(integrator_id),
+                    locked_lp_share: Decimal::ZERO,
+                }
+            );
+
+            // A pool created while the component is paused starts paused too
+            if self.paused {
+                self.set_pool_globally_paused(coin_resource_address, true);
+            }
+
+            // Mint a badge for the coin creator containing some information about the
+            // coin and the pool
+            let creator_badge_bucket = self.mint_creator_badge(
+                coin_resource_address,
+                coin_name,
+                coin_symbol,
+                lp_resource_address,
+                UncheckedUrl::of(coin_icon_url),
+                PoolMode::Launching,
+            );
+
+            // Execute global hooks for the BondingCurveLaunch operation
+            let buckets = self.execute_hooks(
+                &vec![vec![],vec![],vec![]],
+                &hook_argument,
+            );
+
+            (creator_badge_bucket, buckets)
+        }
+
+        // Instantiate a pool component to perform a dutch auction
+        pub fn new_dutch_auction(
+            &mut self,
+
+            // Symbol of the coin to launch
+            mut coin_symbol: String,
+
+            // Symbol of the coin to launch
+            mut coin_name: String,
+
+            // Icon_url of the coin to launch
+            mut coin_icon_url: String,
+
+            // Description of the coin to launch (can be empty)
+            coin_description: String,
+
+            // Info_url of the coin to launch (can be empty)
+            mut coin_info_url: String,
+
+            // Array of URLs of the socials of the coin (can be empty)
+            coin_social_url: Vec<String>,
+
+            // Price of the coin when the auction starts
+            start_price: Decimal,
+
+            // Minimum price of the coin during the auction
+            floor_price: Decimal,
+
+            // Number of seconds needed for the price to go from start_price to floor_price
+            decay_period: i64,
+
+            // Maximum number of coins to sell during the auction
+            supply_for_sale: Decimal,
+
+            // Percentage fee paid to the pool when committing bids and buying coins
+            buy_pool_fee_percentage: Decimal,
+
+            // Percentage fee paid to the pool when selling coins
+            sell_pool_fee_percentage: Decimal,
+
+            // Amount of base coins paid to the pool when taking a flash loan
+            flash_loan_pool_fee: Decimal,
+
+            // The id of the integrator whose interface has been used
+            integrator_id: u64,
+
+            // Numeric id of the referral badge of the user who referred the coin creator
+            referral_id: Option<u64>,
+
+        ) -> NonFungibleBucket // Creator badge
+        {
+            // Verify that the fees specified by the coin creator are acceptable
+            self.check_fees(buy_pool_fee_percentage, sell_pool_fee_percentage, flash_loan_pool_fee, true);
+
+            // Verify that the auction parameters make sense
+            assert!(
+                floor_price > Decimal::ZERO && floor_price <= start_price,
+                "Floor price must be bigger than zero and not bigger than start price",
+            );
+            assert!(
+                decay_period > 0,
+                "Decay period must be bigger than zero",
+            );
+            assert!(
+                supply_for_sale > Decimal::ZERO,
+                "Supply for sale must be bigger than zero",
+            );
+
+            // Verify and normaliize the metadata specified by the coin creator
+            (coin_symbol, coin_name, coin_icon_url, coin_info_url) =
+                self.check_metadata(coin_symbol, coin_name, coin_icon_url, coin_info_url);
+
+            // Instantiate the pool component
+            let (pool, coin_resource_address, lp_resource_address) = Pool::new_dutch_auction(
+                self.owner_badge_address,
+                self.proxy_badge_vault.resource_address(),
+                self.hook_badge_vault.resource_address(),
+                coin_symbol.clone(),
+                coin_name.clone(),
+                coin_icon_url.clone(),
+                coin_description,
+                coin_info_url,
+                coin_social_url,
+                start_price,
+                floor_price,
+                decay_period,
+                supply_for_sale,
+                buy_pool_fee_percentage,
+                sell_pool_fee_percentage,
+                flash_loan_pool_fee,
+                self.next_creator_badge_rule(),
+                self.base_coin_address,
+                self.dapp_definition,
+            );
+
+            // Add the pool information in the pools KVS
+            self.pools.insert(
+                coin_resource_address,
+                PoolStruct {
+                    component_address: pool.into(),
+                    enabled_hooks: HooksPerOperation::new(),
+                    creator_id: self.next_creator_badge_id,
+                    creator_fee_percentage: Decimal::ZERO,
+                    referral_id: self.check_referral_id(referral_id),
+                    integrator_id: self.check_integrator_id(integrator_id),
+                    locked_lp_share: Decimal::ZERO,
+                }
+            );
+
+            // A pool created while the component is paused starts paused too
+            if self.paused {
+                self.set_pool_globally_paused(coin_resource_address, true);
+            }
+
+            // Mint a badge for the coin creator containing some information about the
+            // coin and the pool and return it
+            self.mint_creator_badge(
+                coin_resource_address,
+                coin_name,
+                coin_symbol,
+                lp_resource_address,
+                UncheckedUrl::of(coin_icon_url),
+                PoolMode::WaitingForLaunch,
+            )
+        }
+
         // This method can emit any event created by a pool component.
         // It can also add information (the integrator id) that are not known to the pool
         fn emit_pool_event(
@@ -928,6 +1904,13 @@ mod radix_pump {
                 AnyPoolEvent::BurnEvent(ref event) => Runtime::emit_event(*event),
                 AnyPoolEvent::AddLiquidityEvent(ref event) => Runtime::emit_event(*event),
                 AnyPoolEvent::RemoveLiquidityEvent(ref event) => Runtime::emit_event(*event),
+                AnyPoolEvent::BondingCurveLaunchEvent(ref event) => Runtime::emit_event(*event),
+                AnyPoolEvent::BondingCurveGraduationEvent(ref event) => Runtime::emit_event(*event),
+                AnyPoolEvent::DutchAuctionStartEvent(ref event) => Runtime::emit_event(*event),
+                AnyPoolEvent::DutchAuctionEndEvent(ref event) => Runtime::emit_event(*event),
+                AnyPoolEvent::CommitBidEvent(ref event) => Runtime::emit_event(*event),
+                AnyPoolEvent::SoftCapNotReachedEvent(ref event) => Runtime::emit_event(*event),
+                AnyPoolEvent::RefundEvent(ref event) => Runtime::emit_event(*event),
             }
         }
 
@@ -976,6 +1959,7 @@ mod radix_pump {
         }
 
         // The component owner can use this method to set the platform fees
+        // If a timelock delay is set, the change must be queued with queue_owner_action instead
         pub fn update_fees(
             &mut self,
 
@@ -990,11 +1974,42 @@ mod radix_pump {
             // flash loan
             flash_loan_fee: Decimal,
 
+            // Percentage of the value of the borrowed coins paid to the component owner or to an
+            // integrator to get a flash loan, in addition to flash_loan_fee
+            flash_loan_fee_percentage: Decimal,
+
             // Upper limit to the buy and sell pool fees a coin creator can set
             max_buy_sell_pool_fee_percentage: Decimal,
 
+            // Upper limit to the creator fee a coin creator can set
+            max_creator_fee_percentage: Decimal,
+
             // Minimum amount of base coins to be deposited to do a quick launch
             minimum_deposit: Decimal,
+        ) {
+            self.check_no_timelock();
+
+            self.apply_update_fees(
+                creation_fee_percentage,
+                buy_sell_fee_percentage,
+                flash_loan_fee,
+                flash_loan_fee_percentage,
+                max_buy_sell_pool_fee_percentage,
+                max_creator_fee_percentage,
+                minimum_deposit,
+            );
+        }
+
+        // Set the platform fees, this is called by update_fees and execute_owner_action
+        fn apply_update_fees(
+            &mut self,
+            creation_fee_percentage: Decimal,
+            buy_sell_fee_percentage: Decimal,
+            flash_loan_fee: Decimal,
+            flash_loan_fee_percentage: Decimal,
+            max_buy_sell_pool_fee_percentage: Decimal,
+            max_creator_fee_percentage: Decimal,
+            minimum_deposit: Decimal,
         ) {
             // Make sure the parameters make sense
             assert!(
@@ -1009,10 +2024,18 @@ mod radix_pump {
                 flash_loan_fee >= Decimal::ZERO,
                 "Flash loan fee can't be a negative number",
             );
+            assert!(
+                flash_loan_fee_percentage >= Decimal::ZERO && flash_loan_fee_percentage < dec!(100),
+                "Flash loan fee percentage can go from 0 (included) to 100 (excluded)",
+            );
             assert!(
                 max_buy_sell_pool_fee_percentage >= Decimal::ZERO && max_buy_sell_pool_fee_percentage <= dec!(100),
                 "Max buy sell pool fee percentage can go from 0 (included) to 100 (included)",
             );
+            assert!(
+                max_creator_fee_percentage >= Decimal::ZERO && max_creator_fee_percentage <= dec!(100),
+                "Max creator fee percentage can go from 0 (included) to 100 (included)",
+            );
             assert!(
                 minimum_deposit > Decimal::ZERO,
                 "Minimum_deposit can't be zero or less",
@@ -1022,42 +2045,385 @@ mod radix_pump {
             self.creation_fee_percentage = creation_fee_percentage;
             self.buy_sell_fee_percentage = buy_sell_fee_percentage;
             self.flash_loan_fee = flash_loan_fee;
+            self.flash_loan_fee_percentage = flash_loan_fee_percentage;
             self.max_buy_sell_pool_fee_percentage = max_buy_sell_pool_fee_percentage;
+            self.max_creator_fee_percentage = max_creator_fee_percentage;
             self.minimum_deposit = minimum_deposit;
         }
 
-        // The component owner can use this method to put a pool in liquidation mode
-        pub fn owner_set_liquidation_mode(
+        // The component owner can use this method to set the fee paid for base coin flash loans
+        // If a timelock delay is set, the change must be queued with queue_owner_action instead
+        pub fn update_base_coin_flash_loan_fee(
             &mut self,
 
-            // address of the coin whose pool will be set in liquidation mode
-            coin_address: ResourceAddress,
+            // Fixed fee (in base coins) paid to the component owner or to an integrator to get a
+            // base coin flash loan
+            base_coin_flash_loan_fee: Decimal,
         ) {
-            // Find information on the pool to operate on
-            let mut pool = self.pools.get_mut(&coin_address).expect(COIN_NOT_FOUND);
-
-            // Use the proxy badge to call the set_liquidation_mode pool method
-            let (mode, event) = self.proxy_badge_vault.authorize_with_amount(
-                1,
-                || pool.component_address.set_liquidation_mode()
-            );
+            self.check_no_timelock();
 
-            // Get the creator badge id then drop the pool variable to avoid borrow conflicts
-            let creator_id = pool.creator_id;
-            drop(pool);
+            self.apply_update_base_coin_flash_loan_fee(base_coin_flash_loan_fee);
+        }
 
-            // Emit a LiquidationEvent
-            self.emit_pool_event(event, 0);
+        // Set the base coin flash loan fee, this is called by update_base_coin_flash_loan_fee and
+        // execute_owner_action
+        fn apply_update_base_coin_flash_loan_fee(
+            &mut self,
+            base_coin_flash_loan_fee: Decimal,
+        ) {
+            assert!(
+                base_coin_flash_loan_fee >= Decimal::ZERO,
+                "Flash loan fee can't be a negative number",
+            );
 
-            // Update pool_mode in the creator badge NFT
-            self.update_mode_in_creator_nft(creator_id, mode);
+            self.base_coin_flash_loan_fee = base_coin_flash_loan_fee;
         }
 
-        // A coin creator can use this method to put his pool in liquidation mode
-        pub fn creator_set_liquidation_mode(
+        // The component owner can use this method to increase the timelock delay
+        // Decreasing it requires queuing an UpdateTimelockDelay action
+        pub fn update_timelock_delay(
             &mut self,
 
-            // Creator badge proof
+            // Minimum number of seconds between queuing and executing an owner action
+            timelock_delay: i64,
+        ) {
+            assert!(
+                timelock_delay >= self.timelock_delay,
+                "The timelock delay can only be decreased through queue_owner_action",
+            );
+
+            self.timelock_delay = timelock_delay;
+        }
+
+        // The component owner can use this method to queue a sensitive action; it will be possible
+        // to execute it after the timelock delay
+        pub fn queue_owner_action(
+            &mut self,
+
+            // The action to queue
+            action: OwnerAction,
+
+        ) -> u64 // Numeric id of the queued action
+        {
+            assert!(
+                self.pending_owner_actions.len() < MAX_PENDING_OWNER_ACTIONS,
+                "Too many pending owner actions, execute or cancel some of them",
+            );
+
+            let id = self.next_owner_action_id;
+            self.next_owner_action_id += 1;
+
+            let executable_time = Clock::current_time_rounded_to_seconds().seconds_since_unix_epoch +
+                self.timelock_delay;
+
+            Runtime::emit_event(
+                OwnerActionQueuedEvent {
+                    id: id,
+                    action: action.clone(),
+                    executable_time: executable_time,
+                }
+            );
+
+            self.pending_owner_actions.push(
+                PendingOwnerAction {
+                    id: id,
+                    action: action,
+                    executable_time: executable_time,
+                }
+            );
+
+            id
+        }
+
+        // The component owner can use this method to execute a queued action once its timelock
+        // delay has expired
+        pub fn execute_owner_action(
+            &mut self,
+
+            // Numeric id of the queued action
+            id: u64,
+        ) {
+            let pending_owner_action = self.remove_pending_owner_action(id);
+            assert!(
+                Clock::current_time_rounded_to_seconds().seconds_since_unix_epoch >=
+                    pending_owner_action.executable_time,
+                "Timelock not expired yet",
+            );
+
+            match pending_owner_action.action {
+                OwnerAction::UpdateFees {
+                    creation_fee_percentage,
+                    buy_sell_fee_percentage,
+                    flash_loan_fee,
+                    flash_loan_fee_percentage,
+                    max_buy_sell_pool_fee_percentage,
+                    max_creator_fee_percentage,
+                    minimum_deposit,
+                } => self.apply_update_fees(
+                    creation_fee_percentage,
+                    buy_sell_fee_percentage,
+                    flash_loan_fee,
+                    flash_loan_fee_percentage,
+                    max_buy_sell_pool_fee_percentage,
+                    max_creator_fee_percentage,
+                    minimum_deposit,
+                ),
+                OwnerAction::UpdateTimeLimits {
+                    min_launch_duration,
+                    min_lock_duration,
+                } => self.apply_update_time_limits(
+                    min_launch_duration,
+                    min_lock_duration,
+                ),
+                OwnerAction::RegisterHook {
+                    name,
+                    operations,
+                    component_address,
+                } => self.apply_register_hook(
+                    name,
+                    operations,
+                    component_address,
+                ),
+                OwnerAction::OwnerEnableHook {
+                    name,
+                    operations,
+                } => self.apply_owner_enable_hook(
+                    name,
+                    operations,
+                ),
+                OwnerAction::UpdateTimelockDelay {
+                    timelock_delay,
+                } => {
+                    assert!(
+                        timelock_delay >= 0,
+                        "The timelock delay can't be negative",
+                    );
+                    self.timelock_delay = timelock_delay;
+                },
+                OwnerAction::UpdateBaseCoinFlashLoanFee {
+                    base_coin_flash_loan_fee,
+                } => self.apply_update_base_coin_flash_loan_fee(base_coin_flash_loan_fee),
+                OwnerAction::SetIntegratorFees {
+                    integrator_id,
+                    creation_fee_percentage,
+                    buy_sell_fee_percentage,
+                    flash_loan_fee,
+                    flash_loan_fee_percentage,
+                } => self.apply_set_integrator_fees(
+                    integrator_id,
+                    creation_fee_percentage,
+                    buy_sell_fee_percentage,
+                    flash_loan_fee,
+                    flash_loan_fee_percentage,
+                ),
+                OwnerAction::UpdateReferralTiers {
+                    referral_tiers,
+                } => self.apply_update_referral_tiers(referral_tiers),
+                OwnerAction::RemoveIntegratorFees {
+                    integrator_id,
+                } => self.apply_remove_integrator_fees(integrator_id),
+            }
+
+            Runtime::emit_event(
+                OwnerActionExecutedEvent {
+                    id: id,
+                }
+            );
+        }
+
+        // The component owner can use this method to cancel a queued action
+        pub fn cancel_owner_action(
+            &mut self,
+
+            // Numeric id of the queued action
+            id: u64,
+        ) {
+            self.remove_pending_owner_action(id);
+
+            Runtime::emit_event(
+                OwnerActionCancelledEvent {
+                    id: id,
+                }
+            );
+        }
+
+        // Read only method to list the owner actions queued and not yet executed or cancelled
+        pub fn get_pending_owner_actions(&self) -> Vec<PendingOwnerAction> {
+            self.pending_owner_actions.clone()
+        }
+
+        // Remove an action from the queue and return it
+        fn remove_pending_owner_action(
+            &mut self,
+            id: u64,
+        ) -> PendingOwnerAction {
+            let index = self.pending_owner_actions.iter()
+                .position(|pending_owner_action| pending_owner_action.id == id)
+                .expect("Owner action not found");
+
+            self.pending_owner_actions.remove(index)
+        }
+
+        // Sensitive owner methods can be called directly only when there's no timelock delay
+        fn check_no_timelock(&self) {
+            assert!(
+                self.timelock_delay == 0,
+                "Timelock active, use queue_owner_action",
+            );
+        }
+
+        // The component owner or the guardian can use this method to pause swaps, flash loans,
+        // add_liquidity, buy_ticket and commit_bid on all of the pools
+        pub fn pause(&mut self) {
+            assert!(
+                !self.paused,
+                "Already paused",
+            );
+
+            self.paused = true;
+            self.set_all_pools_globally_paused(true);
+
+            Runtime::emit_event(
+                PauseEvent {
+                    resource_address: None,
+                }
+            );
+        }
+
+        // The component owner can use this method to unpause the component
+        pub fn unpause(&mut self) {
+            assert!(
+                self.paused,
+                "Not paused",
+            );
+
+            self.paused = false;
+            self.set_all_pools_globally_paused(false);
+
+            Runtime::emit_event(
+                UnpauseEvent {
+                    resource_address: None,
+                }
+            );
+        }
+
+        // The component owner or the guardian can use this method to pause swaps, flash loans,
+        // add_liquidity, buy_ticket and commit_bid on a single pool
+        pub fn pause_pool(
+            &mut self,
+
+            // Address of the coin whose pool will be paused
+            coin_address: ResourceAddress,
+        ) {
+            self.set_pool_paused(coin_address, true);
+
+            Runtime::emit_event(
+                PauseEvent {
+                    resource_address: Some(coin_address),
+                }
+            );
+        }
+
+        // The component owner can use this method to unpause a pool
+        pub fn unpause_pool(
+            &mut self,
+
+            // Address of the coin whose pool will be unpaused
+            coin_address: ResourceAddress,
+        ) {
+            self.set_pool_paused(coin_address, false);
+
+            Runtime::emit_event(
+                UnpauseEvent {
+                    resource_address: Some(coin_address),
+                }
+            );
+        }
+
+        // Use the proxy badge to pause or unpause a pool
+        fn set_pool_paused(
+            &mut self,
+            coin_address: ResourceAddress,
+            paused: bool,
+        ) {
+            let mut pool = self.pools.get_mut(&coin_address).expect(COIN_NOT_FOUND);
+
+            self.proxy_badge_vault.authorize_with_amount(
+                1,
+                || pool.component_address.set_paused(paused)
+            );
+        }
+
+        // Propagate the component pause to all of the pools, this way it also applies to the
+        // operations hooks perform directly on the pools
+        // Every pool has a creator badge containing its coin address
+        fn set_all_pools_globally_paused(
+            &mut self,
+            globally_paused: bool,
+        ) {
+            for creator_id in 1..self.next_creator_badge_id {
+                let coin_address = self.creator_badge_resource_manager.get_non_fungible_data::<CreatorData>(
+                    &NonFungibleLocalId::integer(creator_id)
+                ).coin_resource_address;
+
+                self.set_pool_globally_paused(coin_address, globally_paused);
+            }
+        }
+
+        // Use the proxy badge to pause or unpause a pool because of the component pause
+        fn set_pool_globally_paused(
+            &mut self,
+            coin_address: ResourceAddress,
+            globally_paused: bool,
+        ) {
+            let mut pool = self.pools.get_mut(&coin_address).expect(COIN_NOT_FOUND);
+
+            self.proxy_badge_vault.authorize_with_amount(
+                1,
+                || pool.component_address.set_globally_paused(globally_paused)
+            );
+        }
+
+        // Make sure the component is not paused
+        fn check_not_paused(&self) {
+            assert!(
+                !self.paused,
+                "Component paused",
+            );
+        }
+
+        // The component owner can use this method to put a pool in liquidation mode
+        pub fn owner_set_liquidation_mode(
+            &mut self,
+
+            // address of the coin whose pool will be set in liquidation mode
+            coin_address: ResourceAddress,
+        ) {
+            // Find information on the pool to operate on
+            let mut pool = self.pools.get_mut(&coin_address).expect(COIN_NOT_FOUND);
+
+            // Use the proxy badge to call the set_liquidation_mode pool method
+            let (mode, event) = self.proxy_badge_vault.authorize_with_amount(
+                1,
+                || pool.component_address.set_liquidation_mode()
+            );
+
+            // Get the creator badge id then drop the pool variable to avoid borrow conflicts
+            let creator_id = pool.creator_id;
+            drop(pool);
+
+            // Emit a LiquidationEvent
+            self.emit_pool_event(event, 0);
+
+            // Update pool_mode in the creator badge NFT
+            self.update_mode_in_creator_nft(creator_id, mode);
+        }
+
+        // A coin creator can use this method to put his pool in liquidation mode
+        pub fn creator_set_liquidation_mode(
+            &mut self,
+
+            // Creator badge proof
             creator_proof: Proof,
         ) {
             // Get all of the badge information from the proof
@@ -1097,44 +2463,84 @@ mod radix_pump {
             FungibleBucket, // Coin bucket
             NonFungibleBucket, // Transient NFT
         ) {
-            // Find the pool holding the coin
-            let mut pool = self.pools.get_mut(&coin_address).expect(COIN_NOT_FOUND);
-
-            // Use the proxy badge to call the get_flash_loan pool method
-            let coin_bucket = self.proxy_badge_vault.authorize_with_amount(
-                1,
-                || pool.component_address.get_flash_loan(amount)
-            );
-
-            // Dop the pool variable to avoid borrow conflicts
-            drop(pool);
+            // Borrow the coins from the pool
+            let (coin_bucket, loan_value) = self.borrow_coins(coin_address, amount);
 
             // Mint a transient NFT containing information about the loan
-            self.last_transient_nft_id += 1;
-            let transient_nft_bucket = self.flash_loan_nft_resource_manager.mint_non_fungible(
-                &NonFungibleLocalId::integer(self.last_transient_nft_id),
-                FlashLoanData {
-                    coin_resource_address: coin_address,
-                    coin_amount: amount,
-                }
+            let transient_nft_bucket = self.mint_flash_loan_nft(
+                vec![
+                    FlashLoan {
+                        coin_resource_address: coin_address,
+                        coin_amount: amount,
+                        side: FlashLoanSide::Coin,
+                        value: loan_value,
+                    }
+                ]
             );
 
             // Reurn both buckets to the user
             (coin_bucket, transient_nft_bucket)
         }
 
-        // Use this method to return a flash loan and burn the transient NFT
+        // Use this method to get flash loans of several coins created through this component at once
+        // This method mints a single transient NFT listing all of the loans; it must be burned by
+        // calling the return_flash_loan method or the transaction will fail
+        pub fn get_flash_loans(
+            &mut self,
+
+            // The list of coins to borrow and the amount for each of them
+            loans: Vec<(ResourceAddress, Decimal)>,
+        ) -> (
+            Vec<FungibleBucket>, // Coin buckets, in the same order as the loans list
+            NonFungibleBucket, // Transient NFT
+        ) {
+            assert!(
+                loans.len() > 0,
+                "No loans requested",
+            );
+
+            // Borrow the coins from each pool; asking twice for the same coin will make the
+            // pool panic because of the ongoing loan
+            let mut coin_buckets: Vec<FungibleBucket> = vec![];
+            let mut flash_loans: Vec<FlashLoan> = vec![];
+            for (coin_address, amount) in loans.iter() {
+                let (coin_bucket, loan_value) = self.borrow_coins(*coin_address, *amount);
+                coin_buckets.push(coin_bucket);
+
+                flash_loans.push(
+                    FlashLoan {
+                        coin_resource_address: *coin_address,
+                        coin_amount: *amount,
+                        side: FlashLoanSide::Coin,
+                        value: loan_value,
+                    }
+                );
+            }
+
+            // Mint a transient NFT containing information about all of the loans
+            let transient_nft_bucket = self.mint_flash_loan_nft(flash_loans);
+
+            // Reurn both the coin buckets and the transient NFT to the user
+            (coin_buckets, transient_nft_bucket)
+        }
+
+        // Use this method to return a flash loan (or a bundle of flash loans) and burn the transient
+        // NFT
+        // For each loan the component owner (or integrator) gets flash_loan_fee plus
+        // flash_loan_fee_percentage of the loan value and the pool gets its fee; the pool of the
+        // last returned coin gets the remaining base coins
         pub fn return_flash_loan(
             &mut self,
 
             // A bucket containing a single transient NFT to burn
             transient_nft_bucket: Bucket,
 
-            // A bucket containig base coins to pay both owner (or integrator) and pool fees
+            // A bucket containig base coins to pay both owner (or integrator) and pool fees for all
+            // of the loans
             mut base_coin_bucket: Bucket,
 
-            // A bucket containing the coins to return
-            coin_bucket: Bucket,
+            // The buckets containing the coins to return, in the same order as they were borrowed
+            coin_buckets: Vec<Bucket>,
 
             // The id of the integrator whose interface has been used
             mut integrator_id: u64,
@@ -1154,70 +2560,250 @@ mod radix_pump {
             // Make sure that the integrator_id is valid (or zero)
             integrator_id = self.check_integrator_id(integrator_id);
 
-            // Read loan information from the transient NFT and check them
+            // Read loans information from the transient NFT and check them
             let flash_loan_data = transient_nft_bucket.as_non_fungible().non_fungible::<FlashLoanData>().data();
             assert!(
-                flash_loan_data.coin_resource_address == coin_bucket.resource_address(),
-                "Wrong coin",
-            );
-            assert!(
-                flash_loan_data.coin_amount <= coin_bucket.amount(),
-                "Not enough coins",
+                flash_loan_data.loans.len() == coin_buckets.len(),
+                "Wrong number of coin buckets",
             );
+            for (flash_loan, coin_bucket) in flash_loan_data.loans.iter().zip(coin_buckets.iter()) {
+                assert!(
+                    flash_loan.side == FlashLoanSide::Coin,
+                    "Wrong flash loan side",
+                );
+                assert!(
+                    flash_loan.coin_resource_address == coin_bucket.resource_address(),
+                    "Wrong coin",
+                );
+                assert!(
+                    flash_loan.coin_amount <= coin_bucket.amount(),
+                    "Not enough coins",
+                );
+            }
 
             // Burn the transient NFT
             transient_nft_bucket.burn();
 
-            // Deposit component owner (or integrator) fees
-            self.deposit_fee(
-                integrator_id,
-                FungibleBucket(
-                    base_coin_bucket.take_advanced(
-                        self.creation_fee_percentage * base_coin_bucket.amount() / 100,
-                        WithdrawStrategy::Rounded(RoundingMode::ToZero),
+            let integrator_fees = self.integrator_fees(integrator_id);
+            let mut buckets: Vec<Bucket> = vec![];
+
+            let last_loan_index = flash_loan_data.loans.len() - 1;
+            let mut remaining_base_coin_bucket = Some(base_coin_bucket);
+            for (loan_index, (flash_loan, coin_bucket)) in flash_loan_data.loans.iter().zip(coin_buckets.into_iter()).enumerate() {
+
+                // Deposit component owner (or integrator) fees for this coin
+                let mut pool_base_coin_bucket = remaining_base_coin_bucket.take().unwrap();
+                let fee = integrator_fees.flash_loan_fee +
+                    integrator_fees.flash_loan_fee_percentage * flash_loan.value / dec!(100);
+                assert!(
+                    pool_base_coin_bucket.amount() >= fee,
+                    "Insufficient fee paid",
+                );
+                self.deposit_fee(
+                    integrator_id,
+                    flash_loan.value,
+                    FungibleBucket(
+                        pool_base_coin_bucket.take(fee)
                     )
-                )
-            );
+                );
 
-            // Find the pool to return the loan to
-            let mut pool = self.pools.get_mut(&coin_bucket.resource_address()).unwrap();
+                // Find the pool to return the loan to
+                let mut pool = self.pools.get_mut(&coin_bucket.resource_address()).unwrap();
 
-            // Use the proxy badge to call the return_flash_loan method of the pool
-            let (hook_argument, event) = self.proxy_badge_vault.authorize_with_amount(
-                1,
-                || pool.component_address.return_flash_loan(
-                    base_coin_bucket,
-                    coin_bucket,
-                )
-            );
+                // Use the proxy badge to call the return_flash_loan method of the pool; the pool
+                // takes its fee and gives back the remaining base coins, unless this is the last
+                // loan
+                let (base_coin_bucket, hook_argument, event) =
+                    self.proxy_badge_vault.authorize_with_amount(
+                        1,
+                        || pool.component_address.return_flash_loan(
+                            pool_base_coin_bucket,
+                            coin_bucket,
+                            flash_loan.value,
+                            loan_index == last_loan_index,
+                        )
+                    );
+                remaining_base_coin_bucket = base_coin_bucket;
 
-            // Get the list of hooks enabled for this pool for the ReturnFlashLoan operation
-            let pool_enabled_hooks = pool.enabled_hooks.get_all_hooks(hook_argument.operation);
+                // Get the list of hooks enabled for this pool for the ReturnFlashLoan operation
+                let pool_enabled_hooks = pool.enabled_hooks.get_all_hooks(hook_argument.operation);
 
-            // Drop the pool variable to avoid conflicts with borrows
-            drop(pool);
+                // Drop the pool variable to avoid conflicts with borrows
+                drop(pool);
 
-            // Emit the FlashLoanEvent
-            self.emit_pool_event(event, integrator_id);
+                // Emit the FlashLoanEvent
+                self.emit_pool_event(event, integrator_id);
 
-            // Execute the hooks and return the eventual received buckets
-            self.execute_hooks(
-                &pool_enabled_hooks,
-                &hook_argument,
-            )
+                // Execute the hooks and collect the eventual received buckets
+                buckets.append(
+                    &mut self.execute_hooks(
+                        &pool_enabled_hooks,
+                        &hook_argument,
+                    )
+                );
+            }
+
+            buckets
         }
 
-        // A coin creator can use this method to update the fees of his pool
-        pub fn update_pool_fees(
+        // Use this method to borrow base coins from a pool
+        // This method returns a transient NFT that must be returned to the return_base_coin_flash_loan
+        // method
+        pub fn get_base_coin_flash_loan(
             &mut self,
 
-            // Coin creator badge proof
-            creator_proof: Proof,
+            // The address of the coin whose pool the base coins are borrowed from
+            coin_address: ResourceAddress,
 
-            // New fees to set
-            buy_pool_fee_percentage: Decimal,
+            // The amount of base coins to borrow
+            amount: Decimal
+        ) -> (
+            FungibleBucket, // Base coin bucket
+            NonFungibleBucket, // Transient NFT
+        ) {
+            self.check_not_paused();
+
+            // Find the pool holding the base coins
+            let mut pool = self.pools.get_mut(&coin_address).expect(COIN_NOT_FOUND);
+
+            // Use the proxy badge to call the get_base_coin_flash_loan pool method
+            let base_coin_bucket = self.proxy_badge_vault.authorize_with_amount(
+                1,
+                || pool.component_address.get_base_coin_flash_loan(amount)
+            );
+
+            // Dop the pool variable to avoid borrow conflicts
+            drop(pool);
+
+            // Mint a transient NFT containing information about the loan
+            let transient_nft_bucket = self.mint_flash_loan_nft(
+                vec![
+                    FlashLoan {
+                        coin_resource_address: coin_address,
+                        coin_amount: amount,
+                        side: FlashLoanSide::BaseCoin,
+                        value: amount,
+                    }
+                ]
+            );
+
+            // Reurn both buckets to the user
+            (base_coin_bucket, transient_nft_bucket)
+        }
+
+        // Use this method to return a base coin flash loan and burn the transient NFT
+        pub fn return_base_coin_flash_loan(
+            &mut self,
+
+            // A bucket containing a single transient NFT to burn
+            transient_nft_bucket: Bucket,
+
+            // A bucket containig the borrowed base coins plus the base coins to pay both owner (or
+            // integrator) and pool fees
+            mut base_coin_bucket: Bucket,
+
+            // The id of the integrator whose interface has been used
+            mut integrator_id: u64,
+
+        ) -> Vec<Bucket> // Eventual buckets created by hooks
+        {
+            // Make sure the resources are the correct ones
+            assert!(
+                transient_nft_bucket.resource_address() == self.flash_loan_nft_resource_manager.address(),
+                "Wrong NFT",
+            );
+            assert!(
+                base_coin_bucket.resource_address() == self.base_coin_address,
+                "Wrong base coin",
+            );
+
+            // Make sure that the integrator_id is valid (or zero)
+            integrator_id = self.check_integrator_id(integrator_id);
+
+            // Read loan information from the transient NFT and check them
+            let flash_loan_data = transient_nft_bucket.as_non_fungible().non_fungible::<FlashLoanData>().data();
+            assert!(
+                flash_loan_data.loans.len() == 1,
+                "Wrong number of loans",
+            );
+            let flash_loan = &flash_loan_data.loans[0];
+            assert!(
+                flash_loan.side == FlashLoanSide::BaseCoin,
+                "Wrong flash loan side",
+            );
+            // The fixed part of the fee is the one set for base coin flash loans, unless the
+            // integrator has its own fees
+            let fixed_fee = match self.integrator_fees.get(&integrator_id) {
+                Some(integrator_fees) => integrator_fees.flash_loan_fee,
+                None => self.base_coin_flash_loan_fee,
+            };
+            let fee = fixed_fee +
+                self.integrator_fees(integrator_id).flash_loan_fee_percentage * flash_loan.value / dec!(100);
+            assert!(
+                flash_loan.coin_amount + fee <= base_coin_bucket.amount(),
+                "Not enough base coins",
+            );
+
+            // Burn the transient NFT
+            transient_nft_bucket.burn();
+
+            // Deposit component owner (or integrator) fees
+            self.deposit_fee(
+                integrator_id,
+                flash_loan.value,
+                FungibleBucket(
+                    base_coin_bucket.take(fee)
+                )
+            );
+
+            // Find the pool to return the loan to
+            let mut pool = self.pools.get_mut(&flash_loan.coin_resource_address).unwrap();
+
+            // Use the proxy badge to call the return_base_coin_flash_loan method of the pool
+            let (hook_argument, event) = self.proxy_badge_vault.authorize_with_amount(
+                1,
+                || pool.component_address.return_base_coin_flash_loan(base_coin_bucket)
+            );
+
+            // Get the list of hooks enabled for this pool for the ReturnBaseCoinFlashLoan operation
+            let pool_enabled_hooks = pool.enabled_hooks.get_all_hooks(hook_argument.operation);
+
+            // Drop the pool variable to avoid conflicts with borrows
+            drop(pool);
+
+            // Emit the FlashLoanEvent
+            self.emit_pool_event(event, integrator_id);
+
+            // Execute the hooks and return the eventual received buckets
+            self.execute_hooks(
+                &pool_enabled_hooks,
+                &hook_argument,
+            )
+        }
+
+        // A coin creator can use this method to update the fees of his pool
+        pub fn update_pool_fees(
+            &mut self,
+
+            // Coin creator badge proof
+            creator_proof: Proof,
+
+            // New fees to set
+            buy_pool_fee_percentage: Decimal,
             sell_pool_fee_percentage: Decimal,
             flash_loan_pool_fee: Decimal,
+            base_coin_flash_loan_pool_fee: Decimal,
+
+            // Percentage of the value of the borrowed coins paid to the pool, in addition to the
+            // fixed flash loan fees
+            flash_loan_pool_fee_percentage: Decimal,
+
+            // Maximum percentage of the coins (or base coins) in the pool that can be borrowed
+            // with a flash loan
+            max_flash_loan_percentage: Decimal,
+
+            // Percentage of the base coins bought or sold paid to the coin creator
+            creator_fee_percentage: Decimal,
         ) {
             // Check that the fees are acceptable
             self.check_fees(
@@ -1226,6 +2812,23 @@ mod radix_pump {
                 flash_loan_pool_fee,
                 false,
             );
+            assert!(
+                base_coin_flash_loan_pool_fee >= Decimal::ZERO,
+                "Base coin flash loan pool fee can't be a negative number",
+            );
+            assert!(
+                flash_loan_pool_fee_percentage >= Decimal::ZERO && flash_loan_pool_fee_percentage < dec!(100),
+                "Flash loan pool fee percentage can go from 0 (included) to 100 (excluded)",
+            );
+            assert!(
+                max_flash_loan_percentage >= Decimal::ZERO && max_flash_loan_percentage <= dec!(100),
+                "Max flash loan percentage can go from 0 (included) to 100 (included)",
+            );
+            assert!(
+                creator_fee_percentage >= Decimal::ZERO && creator_fee_percentage <= self.max_creator_fee_percentage,
+                "Creator fee percentage can go from 0 (included) to {} (included)",
+                self.max_creator_fee_percentage,
+            );
 
             // Read the badge information from the proof
             let (_, creator_data) = self.get_creator_data(creator_proof);
@@ -1240,9 +2843,16 @@ mod radix_pump {
                     buy_pool_fee_percentage,
                     sell_pool_fee_percentage,
                     flash_loan_pool_fee,
+                    base_coin_flash_loan_pool_fee,
+                    flash_loan_pool_fee_percentage,
+                    max_flash_loan_percentage,
+                    creator_fee_percentage,
                 )
             );
 
+            // Keep a copy of the creator fee to use it in swaps
+            pool.creator_fee_percentage = creator_fee_percentage;
+
             // DRop the pool variable to avoid borrow conflicts
             drop(pool);
 
@@ -1250,6 +2860,113 @@ mod radix_pump {
             self.emit_pool_event(event, 0);
         }
 
+        // A coin creator can use this method to withdraw his share of the trading fees
+        pub fn get_creator_fees(
+            &mut self,
+
+            // Coin creator badge proof
+            creator_proof: Proof,
+
+        ) -> FungibleBucket // Creator fees collected (base coins)
+        {
+            // Read the badge information from the proof
+            let (creator_id, creator_data) = self.get_creator_data(creator_proof);
+
+            // Empty the creator vault
+            let fee_bucket = self.creator_fee_vaults.get_mut(&creator_id).expect("No fees yet").take_all();
+
+            Runtime::emit_event(
+                CreatorFeeClaimedEvent {
+                    resource_address: creator_data.coin_resource_address,
+                    creator_id: creator_id,
+                    amount: fee_bucket.amount(),
+                }
+            );
+
+            fee_bucket
+        }
+
+        // A coin creator can use this method to update the metadata of his coin and of its LP token
+        // Symbol and name are locked and can't be changed
+        pub fn update_coin_metadata(
+            &mut self,
+
+            // Coin creator badge proof
+            creator_proof: Proof,
+
+            // New icon_url of the coin
+            mut coin_icon_url: String,
+
+            // New description of the coin (can be empty)
+            coin_description: String,
+
+            // New info_url of the coin (empty = remove it)
+            mut coin_info_url: String,
+
+            // New array of URLs of the socials of the coin (empty = remove them)
+            coin_social_url: Vec<String>,
+        ) {
+            // Check the proof or die
+            let creator_proof = creator_proof.check_with_message(
+                self.creator_badge_resource_manager.address(),
+                WRONG_BADGE,
+            )
+            .as_non_fungible();
+            let non_fungible = creator_proof.non_fungible::<CreatorData>();
+            let creator_data = non_fungible.data();
+
+            // If the component owner forbade the symbol or the name of the coin after its creation
+            // the coin creator is not allowed to update the metadata anymore
+            assert!(
+                self.forbidden_symbols.get(&creator_data.coin_symbol).is_some_and(|used| *used),
+                "Symbol forbidden",
+            );
+            assert!(
+                self.forbidden_names.get(&creator_data.coin_name.to_uppercase()).is_some_and(|used| *used),
+                "Name forbidden",
+            );
+
+            // Verify and normalize the URLs
+            (coin_icon_url, coin_info_url) = self.check_urls(coin_icon_url, coin_info_url);
+
+            // The coin creator badge is the metadata setter of both the coin and the LP token
+            let coin_resource_manager = ResourceManager::from(creator_data.coin_resource_address);
+            let lp_resource_manager = ResourceManager::from(creator_data.lp_token_address);
+            creator_proof.authorize(|| {
+                coin_resource_manager.set_metadata("icon_url", UncheckedUrl::of(coin_icon_url.clone()));
+                coin_resource_manager.set_metadata("description", coin_description);
+                match coin_info_url.len() {
+                    0 => {
+                        coin_resource_manager.remove_metadata("info_url");
+                    },
+                    _ => {
+                        coin_resource_manager.set_metadata("info_url", UncheckedUrl::of(coin_info_url));
+                    },
+                }
+                match coin_social_url.len() {
+                    0 => {
+                        coin_resource_manager.remove_metadata("social_url");
+                    },
+                    _ => {
+                        let mut url: Vec<UncheckedUrl> = vec![];
+                        for string in coin_social_url.iter() {
+                            url.push(UncheckedUrl::of(string));
+                        }
+                        coin_resource_manager.set_metadata("social_url", url);
+                    },
+                }
+
+                lp_resource_manager.set_metadata("icon_url", UncheckedUrl::of(coin_icon_url.clone()));
+            });
+
+            // Keep the image of the creator badge in sync with the coin icon
+            self.creator_badge_resource_manager.update_non_fungible_data(
+                non_fungible.local_id(),
+                "key_image_url",
+                UncheckedUrl::of(coin_icon_url),
+            );
+        }
+
         // Read only method to get all of the info about a pool
         pub fn get_pool_info(
             &self,
@@ -1266,20 +2983,183 @@ mod radix_pump {
             // information
             let mut pool_info = pool.component_address.get_pool_info();
 
+            // The pool is paused if the whole component is paused
+            pool_info.paused = pool_info.paused || self.paused;
+
             // Add the RadixPump component fees to the pool fees to get the total fees
-            pool_info.total_buy_fee_percentage = dec!(1000000) / ((100 - pool_info.total_buy_fee_percentage) * (100 - self.buy_sell_fee_percentage)) - dec!(100);
-            pool_info.total_sell_fee_percentage = pool_info.total_sell_fee_percentage + self.buy_sell_fee_percentage * (100 - pool_info.total_sell_fee_percentage) / dec!(100);
+            // The creator fee is paid too
+            pool_info.total_buy_fee_percentage = dec!(100000000) / ((100 - pool_info.total_buy_fee_percentage) * (100 - self.buy_sell_fee_percentage) * (100 - pool_info.creator_fee_percentage)) - dec!(100);
+            pool_info.total_sell_fee_percentage = dec!(100) - (100 - pool_info.total_sell_fee_percentage) * (100 - pool_info.creator_fee_percentage) * (100 - self.buy_sell_fee_percentage) / dec!(10000);
+            // Both the fixed and the percentage owner flash loan fees are charged on each borrowed
+            // coin by return_flash_loan
             pool_info.total_flash_loan_fee = pool_info.total_flash_loan_fee + self.flash_loan_fee;
+            pool_info.total_base_coin_flash_loan_fee = pool_info.total_base_coin_flash_loan_fee + self.base_coin_flash_loan_fee;
+            pool_info.total_flash_loan_fee_percentage = pool_info.total_flash_loan_fee_percentage + self.flash_loan_fee_percentage;
 
             // Add information about the badges used by RadixPump
             pool_info.flash_loan_nft_resource_address = Some(self.flash_loan_nft_resource_manager.address());
             pool_info.hooks_badge_resource_address = Some(self.hook_badge_vault.resource_address());
             pool_info.creator_badge_resource_address = Some(self.creator_badge_resource_manager.address());
+            pool_info.vesting_nft_resource_address = Some(self.vesting_nft_resource_manager.address());
+
+            // Add the liquidity locked in this component
+            pool_info.locked_lp_share = pool.locked_lp_share;
+            if pool_info.total_users_lp > Decimal::ZERO {
+                pool_info.locked_lp_percentage = pool_info.locked_lp_share * dec!(100) / pool_info.total_users_lp;
+            }
 
             // Return the struct cointaining all of the info
             pool_info
         }
 
+        // Read only method to get the time weighted average price of a coin over the last
+        // window_seconds seconds
+        pub fn get_twap(
+            &self,
+
+            // The address of the coin
+            coin_address: ResourceAddress,
+
+            // Length of the period to average the price on
+            window_seconds: i64,
+
+        ) -> Decimal {
+            let pool = self.pools.get(&coin_address).expect(COIN_NOT_FOUND);
+
+            pool.component_address.get_twap(window_seconds)
+        }
+
+        // Read only method to get the redeemable amounts, the earned fees and the impermanent loss
+        // of the liquidity positions represented by some LP NFTs
+        pub fn get_lp_position(
+            &self,
+
+            // The address of the coin
+            coin_address: ResourceAddress,
+
+            // Numeric ids of the LP NFTs
+            ids: Vec<u64>,
+
+        ) -> Vec<LPPosition> {
+            let pool = self.pools.get(&coin_address).expect(COIN_NOT_FOUND);
+
+            pool.component_address.get_lp_position(ids)
+        }
+
+        // Read only method to preview a swap; it returns exactly what the swap method would return
+        // if executed now, unless hooks invoked for the Sell operation modify the pool of coin2
+        pub fn quote_swap(
+            &self,
+
+            // Address and amount of the coins the user wants to sell
+            coin1_address: ResourceAddress,
+            coin1_amount: Decimal,
+
+            // Address of the coins the user wants to receive
+            coin2_address: ResourceAddress,
+
+            // Id of the integrator whose interface will be used for the swap
+            integrator_id: u64,
+
+        ) -> SwapQuote {
+            // Verify that the swap makes sense
+            assert!(
+                coin1_amount > Decimal::ZERO,
+                "Coin1 amount should be positive",
+            );
+            assert!(
+                coin1_address != coin2_address,
+                "Can't swap a coin with itself",
+            );
+
+            let (mut base_coin_amount, sell_quote) = match coin1_address == self.base_coin_address {
+
+                // If coin1 is the base coin there's no sell operation
+                true => (coin1_amount, None),
+
+                false => {
+                    let pool = self.pools.get(&coin1_address).expect("Coin1 not found");
+                    let sell_quote = pool.component_address.quote_sell(coin1_amount);
+
+                    (sell_quote.amount_out, Some(sell_quote))
+                },
+            };
+
+            // The creator of coin1 gets a share of the sell proceeds, unless the pool is not in
+            // Normal mode
+            let mut creator_fee = match &sell_quote {
+                Some(sell_quote) if sell_quote.mode == PoolMode::Normal =>
+                    base_coin_amount * self.creator_fee_percentage(coin1_address) / dec!(100),
+                _ => Decimal::ZERO,
+            };
+            base_coin_amount -= creator_fee;
+
+            // The fees paid to the integrator or the component owner
+            let platform_fee = base_coin_amount *
+                self.integrator_fees(self.check_integrator_id(integrator_id)).buy_sell_fee_percentage / dec!(100);
+            base_coin_amount -= platform_fee;
+
+            // The creator of coin2 gets a share of the base coins used to buy
+            let creator2_fee = base_coin_amount * self.creator_fee_percentage(coin2_address) / dec!(100);
+            base_coin_amount -= creator2_fee;
+            creator_fee += creator2_fee;
+
+            let (amount_out, buy_quote) = match coin2_address == self.base_coin_address {
+
+                // If coin2 is the base coin there's no buy operation
+                true => (base_coin_amount, None),
+
+                false => {
+                    let pool = self.pools.get(&coin2_address).expect("Coin2 not found");
+                    let buy_quote = pool.component_address.quote_buy(base_coin_amount);
+
+                    (buy_quote.amount_out, Some(buy_quote))
+                },
+            };
+
+            SwapQuote {
+                amount_out: amount_out,
+                platform_fee: platform_fee,
+                creator_fee: creator_fee,
+                sell_quote: sell_quote,
+                buy_quote: buy_quote,
+            }
+        }
+
+        // Read only method to preview buying coins with base coins
+        pub fn quote_buy(
+            &self,
+
+            // Address of the coin to buy
+            coin_address: ResourceAddress,
+
+            // Amount of base coins to spend
+            base_coin_amount: Decimal,
+
+            // Id of the integrator whose interface will be used for the swap
+            integrator_id: u64,
+
+        ) -> SwapQuote {
+            self.quote_swap(self.base_coin_address, base_coin_amount, coin_address, integrator_id)
+        }
+
+        // Read only method to preview selling coins for base coins
+        pub fn quote_sell(
+            &self,
+
+            // Address of the coin to sell
+            coin_address: ResourceAddress,
+
+            // Amount of coins to sell
+            coin_amount: Decimal,
+
+            // Id of the integrator whose interface will be used for the swap
+            integrator_id: u64,
+
+        ) -> SwapQuote {
+            self.quote_swap(coin_address, coin_amount, self.base_coin_address, integrator_id)
+        }
+
         // Check a creator badge proof and extract NonFungibleLocalId and NonFungibleData from it
         fn get_creator_data(
             &self,
@@ -1310,6 +3190,7 @@ mod radix_pump {
 
         // The component owner can use this method to update time limits on fair and random
         // launches
+        // If a timelock delay is set, the change must be queued with queue_owner_action instead
         pub fn update_time_limits(
             &mut self,
 
@@ -1319,6 +3200,20 @@ mod radix_pump {
             // Minimum number of seconds the creator allocation will be locked in fair and random
             // launches
             min_lock_duration: i64,
+        ) {
+            self.check_no_timelock();
+
+            self.apply_update_time_limits(
+                min_launch_duration,
+                min_lock_duration,
+            );
+        }
+
+        // Set the time limits, this is called by update_time_limits and execute_owner_action
+        fn apply_update_time_limits(
+            &mut self,
+            min_launch_duration: i64,
+            min_lock_duration: i64,
         ) {
             assert!(
                 min_launch_duration > 0,
@@ -1379,7 +3274,7 @@ mod radix_pump {
             // Drop the pool variable to avoid borrow conflicts
             drop(pool);
 
-            // Emit a FairLaunchStartEvent or a RandomLaunchStartEvent
+            // Emit a FairLaunchStartEvent, a RandomLaunchStartEvent or a DutchAuctionStartEvent
             self.emit_pool_event(event, 0);
 
             // Update pool_mode in the coin creator badge
@@ -1485,6 +3380,16 @@ mod radix_pump {
                             // A round 0 hook can recursively trigger the execution of other hooks
                             for argument in hook_arguments.iter() {
 
+                                // A buy made by the hook may have made a bonding curve launched
+                                // coin graduate, keep its creator badge up to date
+                                if argument.operation == HookableOperation::BondingCurveGraduation {
+                                    let creator_id = self.pools.get(&argument.coin_address)
+                                        .map(|pool| pool.creator_id);
+                                    if let Some(creator_id) = creator_id {
+                                        self.update_mode_in_creator_nft(creator_id, argument.mode);
+                                    }
+                                }
+
                                 // An hook executed on a pool can also trigger hooks on different
                                 // pools!
                                 let pool2 = self.pools.get(&argument.coin_address);
@@ -1492,6 +3397,7 @@ mod radix_pump {
                                 match pool2 {
                                     None => {},
                                     Some(pool2) => {
+
                                         let pool2_enabled_hooks = pool2.enabled_hooks.get_all_hooks(argument.operation);
 
                                         // For execution rounds 1 and 2
@@ -1618,6 +3524,10 @@ mod radix_pump {
                     Some(pool.enabled_hooks.get_all_hooks(hook_argument.operation)),
             };
 
+            // The referrer of the coin creator, if any, and the integrator used to create the coin
+            let referral_id = pool.referral_id;
+            let integrator_id = pool.integrator_id;
+
             // Drop the pool variable to avoid conflicts in multiple borrows
             drop(pool);
 
@@ -1644,16 +3554,25 @@ mod radix_pump {
                 },
             };
 
-            // If the pool returned a base coin bucket extract the fees from it
+            // If the pool returned a base coin bucket extract the fees from it, part of them may go
+            // to the referrer of the coin creator
             match bucket {
                 None => {},
                 Some(ref mut bucket) => {
-                    self.deposit_fee(
-                        0,
-                        bucket.take_advanced(
-                            self.creation_fee_percentage * bucket.amount() / 100,
-                            WithdrawStrategy::Rounded(RoundingMode::ToZero),
-                        )
+
+                    // The integrator may have been disabled in the meantime
+                    let integrator_id = self.check_integrator_id(integrator_id);
+
+                    let volume = bucket.amount();
+                    let fee_bucket = bucket.take_advanced(
+                        self.integrator_fees(integrator_id).creation_fee_percentage * volume / 100,
+                        WithdrawStrategy::Rounded(RoundingMode::ToZero),
+                    );
+                    self.deposit_fee_with_referral(
+                        integrator_id,
+                        referral_id,
+                        volume,
+                        fee_bucket,
                     );
                 }
             }
@@ -1711,12 +3630,20 @@ mod radix_pump {
         // creator badge with the NonFungibleLocalId of the next badge that will be minted, use
         // this CompositeRequirement as owner for the resources that will be created
         fn next_creator_badge_rule(&mut self) -> CompositeRequirement {
+            self.creator_badge_rule(self.next_creator_badge_id)
+        }
+
+        // Same as above for an already minted creator badge
+        fn creator_badge_rule(
+            &self,
+            creator_id: u64,
+        ) -> CompositeRequirement {
             CompositeRequirement::BasicRequirement(
                 BasicRequirement::Require (
                     ResourceOrNonFungible::NonFungible (
                         NonFungibleGlobalId::new(
                             self.creator_badge_resource_manager.address(),
-                            NonFungibleLocalId::integer(self.next_creator_badge_id.into()),
+                            NonFungibleLocalId::integer(creator_id.into()),
                         )
                     )
                 )
@@ -1751,17 +3678,146 @@ mod radix_pump {
                 || pool.component_address.unlock(amount)
             );
 
-            match sell {
+            // Drop the pool variable to prevent problems with borrow
+            drop(pool);
+
+            self.return_unlocked_coins(coin_address, coin_bucket, sell)
+        }
+
+        // A coin creator can use this method to move part of his still locked allocation to a
+        // vesting NFT that can be transferred to someone else (i.e. a team member)
+        // The vesting NFT follows the same vesting schedule as the creator allocation
+        pub fn split_vesting(
+            &mut self,
+
+            // Coin creator badge proof
+            creator_proof: Proof,
+
+            // Amount of still locked coins to move to the vesting NFT
+            amount: Decimal,
+        ) -> NonFungibleBucket // The vesting NFT
+        {
+            // Get the coin address from the creator badge proof
+            let coin_address = self.get_creator_data(creator_proof).1.coin_resource_address;
+
+            // Find the pool of the coin
+            let mut pool = self.pools.get_mut(&coin_address).unwrap();
+
+            // Use the proxy badge to call the split_vesting method of the pool
+            let (allocation, unlocked_amount) = self.proxy_badge_vault.authorize_with_amount(
+                1,
+                || pool.component_address.split_vesting(amount)
+            );
+
+            // Drop the pool variable to prevent problems with borrow
+            drop(pool);
+
+            let vesting_nft = self.vesting_nft_resource_manager.mint_non_fungible(
+                &NonFungibleLocalId::integer(self.next_vesting_nft_id.into()),
+                VestingData {
+                    coin_resource_address: coin_address,
+                    creation_date: Clock::current_time_rounded_to_seconds(),
+                    allocation: allocation,
+                    unlocked_amount: unlocked_amount,
+                }
+            );
+
+            Runtime::emit_event(
+                VestingSplitEvent {
+                    resource_address: coin_address,
+                    vesting_nft_id: self.next_vesting_nft_id,
+                    allocation: allocation,
+                    locked_amount: amount,
+                }
+            );
+
+            self.next_vesting_nft_id += 1;
+
+            vesting_nft
+        }
+
+        // The holder of a vesting NFT can use this method to get (part of) the coins of his
+        // position
+        pub fn unlock_vesting(
+            &mut self,
+
+            // Vesting NFT proof
+            vesting_proof: Proof,
+
+            // Amount required or None (all of the available amount)
+            amount: Option<Decimal>,
+
+            // Whether to sell or not the coins received
+            sell: bool,
+        ) -> (
+            FungibleBucket, // Coins (if sell is false) or base coins (if sell is true)
+            Vec<Bucket>, // Eventual additional buckets created by the hooks
+        ) {
+            // Check the proof or die
+            let non_fungible = vesting_proof.check_with_message(
+                self.vesting_nft_resource_manager.address(),
+                WRONG_BADGE,
+            )
+            .as_non_fungible()
+            .non_fungible::<VestingData>();
+            let vesting_data = non_fungible.data();
+
+            // Find the pool of the coin
+            let mut pool = self.pools.get_mut(&vesting_data.coin_resource_address).unwrap();
+
+            // Use the proxy badge to call the unlock_vesting method of the pool
+            let coin_bucket = self.proxy_badge_vault.authorize_with_amount(
+                1,
+                || pool.component_address.unlock_vesting(
+                    vesting_data.allocation,
+                    vesting_data.unlocked_amount,
+                    amount,
+                )
+            );
+
+            // Drop the pool variable to prevent problems with borrow
+            drop(pool);
+
+            // Keep track of the coins withdrawn by this position
+            self.vesting_nft_resource_manager.update_non_fungible_data(
+                non_fungible.local_id(),
+                "unlocked_amount",
+                vesting_data.unlocked_amount + coin_bucket.amount(),
+            );
+
+            self.return_unlocked_coins(vesting_data.coin_resource_address, coin_bucket, sell)
+        }
+
+        // Return the coins unlocked by a creator or a vesting NFT holder, eventually selling them
+        fn return_unlocked_coins(
+            &mut self,
+
+            // Address of the unlocked coin
+            coin_address: ResourceAddress,
+
+            // The unlocked coins
+            coin_bucket: FungibleBucket,
+
+            // Whether to sell or not the coins received
+            sell: bool,
+        ) -> (
+            FungibleBucket, // Coins (if sell is false) or base coins (if sell is true)
+            Vec<Bucket>, // Eventual additional buckets created by the hooks
+        ) {
+            match sell {
                 // If sell is false just return the received coins and call no hooks
                 false => (coin_bucket, vec![]),
 
                 // If sell is true
                 true => {
+                    // Find the pool of the coin
+                    let mut pool = self.pools.get_mut(&coin_address).unwrap();
+
                     // Use the proxy badge to call the sell method of the pool
                     let (mut base_coin_bucket, hook_argument, event) =
                         self.proxy_badge_vault.authorize_with_amount(
                             1,
-                            || pool.component_address.sell(coin_bucket)
+                            || pool.component_address.sell(coin_bucket, Decimal::ZERO, None)
                         );
 
                     // Get the hook list for the Sell operation on the pool
@@ -1780,10 +3836,12 @@ mod radix_pump {
                     );
 
                     // Take the fees owed to the component owner for the sell operation
+                    let volume = base_coin_bucket.amount();
                     self.deposit_fee(
                         0,
+                        volume,
                         base_coin_bucket.take_advanced(
-                            base_coin_bucket.amount() * self.buy_sell_fee_percentage / dec!(100),
+                            volume * self.buy_sell_fee_percentage / dec!(100),
                             WithdrawStrategy::Rounded(RoundingMode::ToZero),
                         )
                      );
@@ -1797,6 +3855,8 @@ mod radix_pump {
         // to one or more operation.
         // This method doesn't enable the hook: the owner_enable_hook or creator_enable_hook
         // methods must be called after the hook has been registered to actually enable it
+        // If a timelock delay is set, the registration must be queued with queue_owner_action
+        // instead
         pub fn register_hook(
             &mut self,
 
@@ -1809,6 +3869,22 @@ mod radix_pump {
 
             // Address of the hook component
             component_address: HookInterfaceScryptoStub,
+        ) {
+            self.check_no_timelock();
+
+            self.apply_register_hook(
+                name,
+                operations,
+                component_address,
+            );
+        }
+
+        // Register a hook, this is called by register_hook and execute_owner_action
+        fn apply_register_hook(
+            &mut self,
+            name: String,
+            operations: Vec<String>,
+            component_address: HookInterfaceScryptoStub,
         ) {
             // Call the hook to know in which round it wants to be executed and if other hooks
             // trigger his call
@@ -1883,6 +3959,7 @@ mod radix_pump {
         }
 
         // The component owner can use this method to globally enable an hook for all coins
+        // If a timelock delay is set, the change must be queued with queue_owner_action instead
         pub fn owner_enable_hook(
             &mut self,
 
@@ -1892,6 +3969,20 @@ mod radix_pump {
             // Operations to enable the hook for
             // The string operations are converted into HookableOperation
             operations: Vec<String>,
+        ) {
+            self.check_no_timelock();
+
+            self.apply_owner_enable_hook(
+                name,
+                operations,
+            );
+        }
+
+        // Globally enable a hook, this is called by owner_enable_hook and execute_owner_action
+        fn apply_owner_enable_hook(
+            &mut self,
+            name: String,
+            operations: Vec<String>,
         ) {
             // Search the hook by name
             let hook_info = self.registered_hooks.get(&name).expect(UNKNOWN_HOOK);
@@ -2080,6 +4171,7 @@ mod radix_pump {
         // During the launch phase of a random launched coin, users can call this method to buy
         // tickets to take part in the extraction
         // Tickets are non fungibles: an integer amount of tickets must be bought
+        // The buyer pays the platform fee (buy_sell_fee_percentage) on top of the ticket price
         pub fn buy_ticket(
             &mut self,
 
@@ -2092,12 +4184,20 @@ mod radix_pump {
             // Coins to buy the tickets
             coin1_bucket: Bucket,
 
+            // Proof of the allowlisted badge, only needed if the launch is gated
+            buyer_proof: Option<Proof>,
+
+            // Numeric id of the referral badge of the user who referred the buyer
+            referral_id: Option<u64>,
+
         ) -> (
             Bucket, // Eventual excess coins
             NonFungibleBucket, // Tickets
             Vec<Bucket>, // Eventual additional buckets returned by the hooks for the Sell operation
             Vec<Bucket>, // Eventual additional buckets returned by the hooks for the BuyTicket operation
         ) {
+            self.check_not_paused();
+
             // Check input parameters
             assert!(
                 amount > 0,
@@ -2111,17 +4211,20 @@ mod radix_pump {
 
                 false => self.sell(coin1_bucket, 0),
             };
+            let referral_id = self.check_referral_id(referral_id);
+            let base_coin_amount = base_coin_bucket.amount();
  
             // Find the pool
             let mut pool = self.pools.get_mut(&coin_address).expect(COIN_NOT_FOUND);
 
             // Use the proxy badge to call the buy_ticket method of the pool component
-            let (excess_bucket, ticket_bucket, hook_argument, event) =
+            let (mut excess_bucket, ticket_bucket, hook_argument, event) =
                 self.proxy_badge_vault.authorize_with_amount(
                     1,
                     || pool.component_address.buy_ticket(
                         amount,
-                        base_coin_bucket
+                        base_coin_bucket,
+                        buyer_proof,
                     )
                 );
 
@@ -2134,6 +4237,25 @@ mod radix_pump {
             // Emit the BuyTicketEvent event
             self.emit_pool_event(event, 0);
 
+            // The platform fee is computed on the base coins spent in tickets and taken from the
+            // excess base coins; part of it goes to the referrer
+            let volume = base_coin_amount - excess_bucket.amount();
+            let fee = volume * self.buy_sell_fee_percentage / dec!(100);
+            assert!(
+                excess_bucket.amount() >= fee,
+                "Not enough base coins to pay the platform fee",
+            );
+            let fee_bucket = excess_bucket.take_advanced(
+                fee,
+                WithdrawStrategy::Rounded(RoundingMode::ToZero),
+            );
+            self.deposit_fee_with_referral(
+                0,
+                referral_id,
+                volume,
+                FungibleBucket(fee_bucket),
+            );
+
             // Execute the hooks
             let buckets2 = self.execute_hooks(
                 &pool_enabled_hooks,
@@ -2169,144 +4291,842 @@ mod radix_pump {
                 || pool.component_address.redeem_ticket(ticket_bucket)
             );
 
-            // If there were losing tickets, get the hook list for the RedeemLosingTicket
-            // operation
-            let pool_enabled_hooks_lose = match hook_argument_lose {
-                None => None,
-                Some(ref hook_argument) => Some(pool.enabled_hooks.get_all_hooks(hook_argument.operation)),
-            };
+            // If there were losing tickets, get the hook list for the RedeemLosingTicket
+            // operation
+            let pool_enabled_hooks_lose = match hook_argument_lose {
+                None => None,
+                Some(ref hook_argument) => Some(pool.enabled_hooks.get_all_hooks(hook_argument.operation)),
+            };
+
+            // If there were winning tickets, get the hook list for the RedeemWinningTicket
+            // operation
+            let pool_enabled_hooks_win = match hook_argument_win {
+                None => None,
+                Some(ref hook_argument) => Some(pool.enabled_hooks.get_all_hooks(hook_argument.operation)),
+            };
+
+            // Drop the pool variable to avoid conflicting borrows
+            drop(pool);
+
+            // If there were losing tickets, invoke the enabled hooks for the RedeemLosingTicket
+            // operation
+            let lose_buckets = match hook_argument_lose {
+                None => None,
+                Some(hook_argument) => Some(
+                    self.execute_hooks(
+                        &pool_enabled_hooks_lose.unwrap(),
+                        &hook_argument,
+                    )
+                ),
+            };
+
+            // If there were winning tickets, invoke the enabled hooks for the RedeemLosingTicket
+            // operation
+            let win_buckets = match hook_argument_win {
+                None => None,
+                Some(hook_argument) => Some(
+                    self.execute_hooks(
+                        &pool_enabled_hooks_win.unwrap(),
+                        &hook_argument,
+                    )
+                ),
+            };
+
+            // Return all of the buckets to the user
+            (base_coin_bucket, coin_bucket, lose_buckets, win_buckets)
+        }
+
+        // A user can call this method to commit coins to a dutch auction at the current auction
+        // price; he receives a bid NFT to redeem when the auction is over
+        pub fn commit_bid(
+            &mut self,
+
+            // Address of the coin whose dutch auction the user wants to take part in
+            coin_address: ResourceAddress,
+
+            // Coins to commit
+            coin1_bucket: Bucket,
+
+        ) -> (
+            Bucket, // Eventual excess coins
+            NonFungibleBucket, // Bid
+            Vec<Bucket>, // Eventual additional buckets returned by the hooks for the Sell operation
+            Vec<Bucket>, // Eventual additional buckets returned by the hooks for the CommitBid operation
+        ) {
+            self.check_not_paused();
+
+            let (base_coin_bucket, buckets1) = match coin1_bucket.resource_address() == self.base_coin_address {
+
+                // If coin1 is the base coin there's no sell operation
+                true => (FungibleBucket(coin1_bucket), vec![]),
+
+                false => self.sell(coin1_bucket, 0),
+            };
+ 
+            // Find the pool
+            let mut pool = self.pools.get_mut(&coin_address).expect(COIN_NOT_FOUND);
+
+            // Use the proxy badge to call the commit_bid method of the pool component
+            let (excess_bucket, bid_bucket, hook_argument, event) =
+                self.proxy_badge_vault.authorize_with_amount(
+                    1,
+                    || pool.component_address.commit_bid(base_coin_bucket)
+                );
+
+            // Get the list of hooks enabled on the pool for the CommitBid operation
+            let pool_enabled_hooks = pool.enabled_hooks.get_all_hooks(hook_argument.operation);
+
+            // Drop the pool to avoid conflicting borrows
+            drop(pool);
+
+            // Emit the CommitBidEvent event
+            self.emit_pool_event(event, 0);
+
+            // Execute the hooks
+            let buckets2 = self.execute_hooks(
+                &pool_enabled_hooks,
+                &hook_argument,
+            );
+
+            (excess_bucket, bid_bucket, buckets1, buckets2)
+        }
+
+        // When a dutch auction ends, a user can use this method to get his coins and the refund of
+        // the difference between his bid price and the clearing price
+        // The method can handle a bucket of multiple bids
+        // Redeemed bids are burned
+        pub fn redeem_bid(
+            &mut self,
+
+            // The bids to redeem
+            bid_bucket: Bucket,
+        ) -> (
+            FungibleBucket, // Refund (base coins)
+            FungibleBucket, // Dutch auctioned coins
+            Vec<Bucket>, // Eventual buckets returned by the hooks for the RedeemBid operation
+        ) {
+            // Use the bid NonFungibleData to find the pool
+            let bid_id = &bid_bucket.as_non_fungible().non_fungible_local_ids()[0];
+            let bid_data = NonFungibleResourceManager::from(bid_bucket.resource_address()).get_non_fungible_data::<AuctionBidData>(bid_id);
+            let mut pool = self.pools.get_mut(&bid_data.coin_resource_address).expect(COIN_NOT_FOUND);
+
+            // Use the proxy badge to call the redeem_bid method of the pool
+            let (base_coin_bucket, coin_bucket, hook_argument) = self.proxy_badge_vault.authorize_with_amount(
+                1,
+                || pool.component_address.redeem_bid(bid_bucket)
+            );
+
+            // Get the list of hooks enabled on the pool for the RedeemBid operation
+            let pool_enabled_hooks = pool.enabled_hooks.get_all_hooks(hook_argument.operation);
+
+            // Drop the pool variable to avoid conflicting borrows
+            drop(pool);
+
+            // Execute the hooks
+            let buckets = self.execute_hooks(
+                &pool_enabled_hooks,
+                &hook_argument,
+            );
+
+            // Return all of the buckets to the user
+            (base_coin_bucket, coin_bucket, buckets)
+        }
+
+        // If a fair launch terminated without reaching its soft cap, buyers can use this method to
+        // burn their coins and get back the base coins they paid (fees excluded)
+        pub fn refund(
+            &mut self,
+
+            // The coins to return
+            coin_bucket: FungibleBucket,
+        ) -> (
+            FungibleBucket, // Refund (base coins)
+            Vec<Bucket>, // Eventual buckets returned by the hooks for the Refund operation
+        ) {
+            // Find the pool
+            let mut pool = self.pools.get_mut(&coin_bucket.resource_address()).expect(COIN_NOT_FOUND);
+
+            // Use the proxy badge to call the refund method of the pool
+            let (base_coin_bucket, hook_argument, event) = self.proxy_badge_vault.authorize_with_amount(
+                1,
+                || pool.component_address.refund(coin_bucket)
+            );
+
+            // Get the list of hooks enabled on the pool for the Refund operation
+            let pool_enabled_hooks = pool.enabled_hooks.get_all_hooks(hook_argument.operation);
+
+            // Drop the pool variable to avoid conflicting borrows
+            drop(pool);
+
+            // Emit the RefundEvent event
+            self.emit_pool_event(event, 0);
+
+            // Execute the hooks
+            let buckets = self.execute_hooks(
+                &pool_enabled_hooks,
+                &hook_argument,
+            );
+
+            (base_coin_bucket, buckets)
+        }
+
+        // Users can add liquidity to a pool by using this method
+        // The liquidity must include both base coins and coins in the same ratio as the ones
+        // already in the pool. An excess of base coins is accepted
+        pub fn add_liquidity(
+            &mut self,
+
+            // Base coins to add to the pool
+            base_coin_bucket: Bucket,
+
+            // Coins to add to the pool
+            coin_bucket: Bucket,
+        ) -> (
+            Bucket, // LP NFT or fungible LP units representing the added liquidity
+            Option<Bucket>, // Eventual excess coins to return
+            Vec<Bucket>, // Eventual additional buckets returned by the hooks
+        ) {
+            self.check_not_paused();
+
+            // Find the pool
+            let coin_address = coin_bucket.resource_address();
+            let mut pool = self.pools.get_mut(&coin_address).expect(COIN_NOT_FOUND);
+
+            // Use the proxy badge to call the add liquidity method of the pool
+            let (lp_bucket, remainings_bucket, hook_argument, event, mode) =
+                self.proxy_badge_vault.authorize_with_amount(
+                    1,
+                    || pool.component_address.add_liquidity(
+                        base_coin_bucket,
+                        coin_bucket,
+                    )
+                );
+
+            // Get all of the info about the pool before dropping the pool variable
+            let pool_enabled_hooks = pool.enabled_hooks.get_all_hooks(hook_argument.operation);
+            let creator_id = pool.creator_id;
+            drop(pool);
+
+            // Emit the AddLiquidityEvent created by the pool
+            self.emit_pool_event(event, 0);
+
+            // It is possible that the pool was empty (Uninitialised mode); in this case adding
+            // liquidity changes its state to Normal mode so the info in the creator NFT must be updated
+            if mode.is_some() {
+                self.update_mode_in_creator_nft(creator_id, mode.unwrap());
+            }
+
+            // Execute hooks for the AddLiquidity operation
+            let buckets = self.execute_hooks(
+                &pool_enabled_hooks,
+                &hook_argument,
+            );
+
+            (lp_bucket, remainings_bucket, buckets)
+        }
+
+        // Use this method to get back the liquidity previously added to the pool
+        // If the pool is in liquidation mode, only base coins will be returned, all of the coins
+        // are kept by the pool
+        // It is possible to redeem more than one liquidity token in a single operation
+        pub fn remove_liquidity(
+            &mut self,
+
+            // LP NFTs or fungible LP units representing the added liquidity
+            lp_bucket: Bucket,
+        ) -> (
+            FungibleBucket, // Base coins
+            Option<FungibleBucket>, // Coins (if the pool is not in liquidation mode)
+            Vec<Bucket>, // Eventual buckets returned by the hooks
+        ) {
+            // Find the pool
+            let coin_address = self.lp_coin_address(&lp_bucket);
+            let mut pool = self.pools.get_mut(&coin_address).expect(COIN_NOT_FOUND);
+
+            // Use the proxy badge to invoke the remove_liquidity method of the pool
+            let (base_coin_bucket, coin_bucket, hook_argument, event) = self.proxy_badge_vault.authorize_with_amount(
+                1,
+                || pool.component_address.remove_liquidity(lp_bucket)
+            );
+
+            // Get the list of hooks for the RemoveLiquidity operation enabled on the pool and
+            // drop the pool variable
+            let pool_enabled_hooks = pool.enabled_hooks.get_all_hooks(hook_argument.operation);
+            drop(pool);
+
+            // Emit the RemoveLiquidityEvent created by the pool
+            self.emit_pool_event(event, 0);
+
+            // Execute all enabled hooks
+            let buckets = self.execute_hooks(
+                &pool_enabled_hooks,
+                &hook_argument,
+            );
+
+            (base_coin_bucket, coin_bucket, buckets)
+        }
+
+        // Use this method to get back a percentage of the liquidity previously added to the pool
+        // The LP NFTs are updated and returned
+        pub fn remove_liquidity_partial(
+            &mut self,
+
+            // LP NFTs representing the added liquidity
+            lp_bucket: Bucket,
+
+            // Percentage (expressed as a number from 0 to 100) of the liquidity to remove from each
+            // LP NFT
+            percentage: Decimal,
+        ) -> (
+            Bucket, // The updated LP NFTs
+            FungibleBucket, // Base coins
+            Option<FungibleBucket>, // Coins (if the pool is not in liquidation mode)
+            Vec<Bucket>, // Eventual buckets returned by the hooks
+        ) {
+            // Find the pool
+            let coin_address = self.lp_coin_address(&lp_bucket);
+            let mut pool = self.pools.get_mut(&coin_address).expect(COIN_NOT_FOUND);
+
+            // Use the proxy badge to invoke the remove_liquidity_partial method of the pool
+            let (lp_bucket, base_coin_bucket, coin_bucket, hook_argument, event) =
+                self.proxy_badge_vault.authorize_with_amount(
+                    1,
+                    || pool.component_address.remove_liquidity_partial(
+                        lp_bucket,
+                        percentage,
+                    )
+                );
+
+            // Get the list of hooks for the RemoveLiquidity operation enabled on the pool and
+            // drop the pool variable
+            let pool_enabled_hooks = pool.enabled_hooks.get_all_hooks(hook_argument.operation);
+            drop(pool);
+
+            // Emit the RemoveLiquidityEvent created by the pool
+            self.emit_pool_event(event, 0);
+
+            // Execute all enabled hooks
+            let buckets = self.execute_hooks(
+                &pool_enabled_hooks,
+                &hook_argument,
+            );
+
+            (lp_bucket, base_coin_bucket, coin_bucket, buckets)
+        }
+
+        // Use this method to split an LP NFT in two, i.e. to transfer part of a position
+        pub fn split_lp(
+            &mut self,
+
+            // The LP NFT to split
+            lp_bucket: Bucket,
+
+            // Percentage (expressed as a number from 0 to 100) of the liquidity to move to the new
+            // LP NFT
+            percentage: Decimal,
+        ) -> (
+            Bucket, // The updated LP NFT
+            Bucket, // The new LP NFT
+            Vec<Bucket>, // Eventual buckets returned by the hooks
+        ) {
+            // Find the pool
+            let coin_address = self.lp_coin_address(&lp_bucket);
+            let mut pool = self.pools.get_mut(&coin_address).expect(COIN_NOT_FOUND);
+
+            // Use the proxy badge to invoke the split_lp method of the pool
+            let (lp_bucket, new_lp_bucket, hook_argument) =
+                self.proxy_badge_vault.authorize_with_amount(
+                    1,
+                    || pool.component_address.split_lp(
+                        lp_bucket,
+                        percentage,
+                    )
+                );
+
+            // Get the list of hooks for the SplitLiquidity operation enabled on the pool and
+            // drop the pool variable
+            let pool_enabled_hooks = pool.enabled_hooks.get_all_hooks(hook_argument.operation);
+            drop(pool);
+
+            // Execute all enabled hooks
+            let buckets = self.execute_hooks(
+                &pool_enabled_hooks,
+                &hook_argument,
+            );
+
+            (lp_bucket, new_lp_bucket, buckets)
+        }
+
+        // Use this method to merge multiple LP NFTs of the same pool into one
+        pub fn merge_lp(
+            &mut self,
+
+            // The LP NFTs to merge
+            lp_bucket: Bucket,
+        ) -> (
+            Bucket, // The merged LP NFT
+            Vec<Bucket>, // Eventual buckets returned by the hooks
+        ) {
+            // Find the pool
+            let coin_address = self.lp_coin_address(&lp_bucket);
+            let mut pool = self.pools.get_mut(&coin_address).expect(COIN_NOT_FOUND);
+
+            // Use the proxy badge to invoke the merge_lp method of the pool
+            let (lp_bucket, hook_argument) = self.proxy_badge_vault.authorize_with_amount(
+                1,
+                || pool.component_address.merge_lp(lp_bucket)
+            );
+
+            // Get the list of hooks for the MergeLiquidity operation enabled on the pool and
+            // drop the pool variable
+            let pool_enabled_hooks = pool.enabled_hooks.get_all_hooks(hook_argument.operation);
+            drop(pool);
+
+            // Execute all enabled hooks
+            let buckets = self.execute_hooks(
+                &pool_enabled_hooks,
+                &hook_argument,
+            );
+
+            (lp_bucket, buckets)
+        }
+
+        // A user can invoke this method to add liquidity to a pool providing only one of its two
+        // sides: the optimal part of the bucket is swapped for the other side and the result is
+        // added to the pool
+        pub fn zap_in(
+            &mut self,
+
+            // Base coins or coins to add to the pool
+            mut bucket: Bucket,
+
+            // The coin whose pool will receive the liquidity
+            coin_address: ResourceAddress,
+
+            // Minimum lp_share the user accepts to receive
+            min_lp: Decimal,
+
+            // Id of the integrator whose interface allowed this operation
+            integrator_id: u64,
+
+        ) -> (
+            Bucket, // LP NFT or fungible LP units representing the added liquidity
+            FungibleBucket, // Eventual excess base coins
+            Option<Bucket>, // Eventual excess coins
+            Vec<Bucket>, // Eventual buckets returned by the Buy or Sell and AddLiquidity hooks
+        ) {
+            let base_coin_side = bucket.resource_address() == self.base_coin_address;
+            assert!(
+                base_coin_side || bucket.resource_address() == coin_address,
+                "The bucket must contain base coins or coins of the pool",
+            );
+            assert!(
+                bucket.amount() > Decimal::ZERO,
+                "Zero amount not allowed",
+            );
+
+            let pool_info = self.get_pool_info(coin_address);
+            assert!(
+                pool_info.pool_mode == PoolMode::Normal,
+                MODE_NOT_ALLOWED,
+            );
+
+            // Buying with base coins or selling coins, the reserve and the fees to consider
+            // are different
+            let (reserve, fee_fraction) = match base_coin_side {
+                true => (
+                    pool_info.base_coin_amount,
+                    pool_info.total_buy_fee_percentage / (dec!(100) + pool_info.total_buy_fee_percentage),
+                ),
+                false => (
+                    pool_info.coin_amount,
+                    pool_info.total_sell_fee_percentage / dec!(100),
+                ),
+            };
+            let swap_bucket = bucket.take_advanced(
+                RadixPump::zap_swap_amount(bucket.amount(), reserve, fee_fraction),
+                WithdrawStrategy::Rounded(RoundingMode::ToZero),
+            );
+
+            // Swap part of the bucket, this executes the Sell or Buy hooks
+            let (swapped_bucket, mut buckets, mut buckets2) = self.swap(
+                swap_bucket,
+                match base_coin_side {
+                    true => coin_address,
+                    false => self.base_coin_address,
+                },
+                integrator_id,
+                Decimal::ZERO, // min_lp protects the whole operation
+                None,
+                None,
+                None,
+            );
+            buckets.append(&mut buckets2);
+
+            let (mut base_coin_bucket, coin_bucket) = match base_coin_side {
+                true => (FungibleBucket(bucket), Bucket::from(swapped_bucket)),
+                false => (swapped_bucket, bucket),
+            };
+
+            // The pool would keep any excess base coins, so only provide the base coins matching
+            // the coins at the current pool ratio and give back the rest
+            let pool_info = self.get_pool_info(coin_address);
+            let needed_base_coins = coin_bucket.amount() * (pool_info.base_coin_amount / pool_info.coin_amount);
+            let excess_base_coin_bucket = base_coin_bucket.take_advanced(
+                std::cmp::max(base_coin_bucket.amount() - needed_base_coins, Decimal::ZERO),
+                WithdrawStrategy::Rounded(RoundingMode::ToZero),
+            );
+
+            // Add the liquidity, this executes the AddLiquidity hooks
+            let (lp_bucket, excess_coin_bucket, mut buckets3) = self.add_liquidity(
+                base_coin_bucket.into(),
+                coin_bucket,
+            );
+            buckets.append(&mut buckets3);
+
+            let lp_share = self.lp_share(&lp_bucket);
+            assert!(
+                lp_share >= min_lp,
+                "Received lp_share {} is lower than the minimum accepted {}",
+                lp_share,
+                min_lp,
+            );
+
+            (lp_bucket, excess_base_coin_bucket, excess_coin_bucket, buckets)
+        }
+
+        // A user can invoke this method to remove liquidity from a pool and receive only one of its
+        // two sides: the other one is swapped
+        pub fn zap_out(
+            &mut self,
+
+            // LP NFTs or fungible LP units representing the added liquidity
+            lp_bucket: Bucket,
+
+            // Either the base coin or the coin of the pool
+            target_address: ResourceAddress,
+
+            // Minimum amount of target coins the user accepts to receive, net of all fees
+            min_amount_out: Decimal,
+
+            // Id of the integrator whose interface allowed this operation
+            integrator_id: u64,
+
+        ) -> (
+            FungibleBucket, // Target coins
+            Vec<Bucket>, // Eventual buckets returned by the RemoveLiquidity and Sell or Buy hooks
+        ) {
+            let coin_address = self.lp_coin_address(&lp_bucket);
+            assert!(
+                target_address == self.base_coin_address || target_address == coin_address,
+                "The target must be the base coin or the coin of the pool",
+            );
+
+            // Remove the liquidity, this executes the RemoveLiquidity hooks
+            let (mut base_coin_bucket, coin_bucket, mut buckets) = self.remove_liquidity(lp_bucket);
+
+            // Swap the other side, this executes the Sell or Buy hooks
+            let out_bucket = match (target_address == self.base_coin_address, coin_bucket) {
+
+                // In liquidation mode only base coins are returned, nothing to swap
+                (true, None) => base_coin_bucket,
+
+                (true, Some(coin_bucket)) => {
+                    let (bucket, mut buckets1, mut buckets2) = self.swap(
+                        coin_bucket.into(),
+                        self.base_coin_address,
+                        integrator_id,
+                        Decimal::ZERO, // min_amount_out protects the whole operation
+                        None,
+                        None,
+                        None,
+                    );
+                    buckets.append(&mut buckets1);
+                    buckets.append(&mut buckets2);
+
+                    base_coin_bucket.put(bucket);
+                    base_coin_bucket
+                },
+
+                (false, None) => Runtime::panic(MODE_NOT_ALLOWED.to_string()),
+
+                (false, Some(mut coin_bucket)) => {
+                    let (bucket, mut buckets1, mut buckets2) = self.swap(
+                        base_coin_bucket.into(),
+                        coin_address,
+                        integrator_id,
+                        Decimal::ZERO, // min_amount_out protects the whole operation
+                        None,
+                        None,
+                        None,
+                    );
+                    buckets.append(&mut buckets1);
+                    buckets.append(&mut buckets2);
+
+                    coin_bucket.put(bucket);
+                    coin_bucket
+                },
+            };
+
+            assert!(
+                out_bucket.amount() >= min_amount_out,
+                "Received amount {} is lower than the minimum accepted {}",
+                out_bucket.amount(),
+                min_amount_out,
+            );
+
+            (out_bucket, buckets)
+        }
+
+        // Amount of a bucket to swap so that the remaining part and the swapped one are in the same
+        // ratio as the pool reserves
+        // Given the reserve R of the side being swapped, the fee fraction f and the amount a it is
+        // (sqrt(R^2 * (2 - f)^2 + 4 * (1 - f) * a * R) - R * (2 - f)) / (2 * (1 - f))
+        fn zap_swap_amount(
+            amount: Decimal,
+            reserve: Decimal,
+            fee_fraction: Decimal,
+        ) -> Decimal {
+            let amount = PreciseDecimal::from(amount);
+            let reserve = PreciseDecimal::from(reserve);
+            let fee_fraction = PreciseDecimal::from(fee_fraction);
+
+            let b = reserve * (pdec!(2) - fee_fraction);
+
+            (
+                ((b * b + pdec!(4) * (PreciseDecimal::ONE - fee_fraction) * amount * reserve).checked_sqrt().unwrap() - b) /
+                (pdec!(2) * (PreciseDecimal::ONE - fee_fraction))
+            )
+            .checked_truncate(RoundingMode::ToZero).unwrap()
+        }
+
+        // Total lp_share represented by LP NFTs or fungible LP units
+        fn lp_share(
+            &self,
+            lp_bucket: &Bucket,
+        ) -> Decimal {
+            match self.fungible_lp_coins.get(&lp_bucket.resource_address()) {
+
+                // Each fungible LP unit is worth one unit of lp_share
+                Some(_) => lp_bucket.amount(),
+
+                None => lp_bucket.as_non_fungible().non_fungibles::<LPData>().iter()
+                    .fold(Decimal::ZERO, |lp_share, lp_nft| lp_share + lp_nft.data().lp_share),
+            }
+        }
+
+        // Anyone (i.e. a coin creator) can lock LP NFTs in this component until a date, to prove
+        // that the liquidity will not be removed before then
+        // A lock certificate is returned, its holder can get the LP NFTs back after the unlock date
+        pub fn lock_liquidity(
+            &mut self,
+
+            // The LP NFTs to lock, they must belong to the same pool
+            lp_bucket: Bucket,
+
+            // The LP NFTs can't be withdrawn before this date
+            unlock_date: Instant,
+
+        ) -> NonFungibleBucket // The lock certificate
+        {
+            assert!(
+                !lp_bucket.resource_address().is_fungible(),
+                "Only LP NFTs can be locked",
+            );
+            assert!(
+                lp_bucket.amount() > Decimal::ZERO,
+                "Zero amount not allowed",
+            );
+            assert!(
+                Clock::current_time_is_strictly_before(unlock_date, TimePrecision::Second),
+                "The unlock date must be in the future",
+            );
+
+            // Make sure the LP NFTs are not fake
+            let coin_address = self.lp_coin_address(&lp_bucket);
+            let pool = self.pools.get(&coin_address).expect(COIN_NOT_FOUND);
+            assert!(
+                lp_bucket.resource_address() == pool.component_address.get_pool_info().lp_resource_address,
+                "Unknown LP token",
+            );
+            drop(pool);
 
-            // If there were winning tickets, get the hook list for the RedeemWinningTicket
-            // operation
-            let pool_enabled_hooks_win = match hook_argument_win {
-                None => None,
-                Some(ref hook_argument) => Some(pool.enabled_hooks.get_all_hooks(hook_argument.operation)),
-            };
+            let lp_share = self.lp_share(&lp_bucket);
+            let lock_id = self.next_lock_id;
+            self.next_lock_id += 1;
 
-            // Drop the pool variable to avoid conflicting borrows
-            drop(pool);
+            let lock_certificate = self.lock_certificate_resource_manager.mint_non_fungible(
+                &NonFungibleLocalId::integer(lock_id),
+                LockCertificateData {
+                    coin_resource_address: coin_address,
+                    lp_resource_address: lp_bucket.resource_address(),
+                    lp_ids: lp_bucket.as_non_fungible().non_fungible_local_ids().into_iter().collect(),
+                    lp_share: lp_share,
+                    lock_date: Clock::current_time_rounded_to_seconds(),
+                    unlock_date: unlock_date,
+                }
+            );
 
-            // If there were losing tickets, invoke the enabled hooks for the RedeemLosingTicket
-            // operation
-            let lose_buckets = match hook_argument_lose {
-                None => None,
-                Some(hook_argument) => Some(
-                    self.execute_hooks(
-                        &pool_enabled_hooks_lose.unwrap(),
-                        &hook_argument,
-                    )
-                ),
-            };
+            self.locked_lp_vaults.insert(
+                lock_id,
+                NonFungibleVault::with_bucket(lp_bucket.as_non_fungible()),
+            );
+            self.pools.get_mut(&coin_address).unwrap().locked_lp_share += lp_share;
 
-            // If there were winning tickets, invoke the enabled hooks for the RedeemLosingTicket
-            // operation
-            let win_buckets = match hook_argument_win {
-                None => None,
-                Some(hook_argument) => Some(
-                    self.execute_hooks(
-                        &pool_enabled_hooks_win.unwrap(),
-                        &hook_argument,
-                    )
-                ),
-            };
+            Runtime::emit_event(
+                LiquidityLockEvent {
+                    resource_address: coin_address,
+                    lock_id: lock_id,
+                    lp_share: lp_share,
+                    unlock_date: unlock_date,
+                }
+            );
 
-            // Return all of the buckets to the user
-            (base_coin_bucket, coin_bucket, lose_buckets, win_buckets)
+            lock_certificate
         }
 
-        // Users can add liquidity to a pool by using this method
-        // The liquidity must include both base coins and coins in the same ratio as the ones
-        // already in the pool. An excess of base coins is accepted
-        pub fn add_liquidity(
+        // The holder of a lock certificate can postpone its unlock date, it is not possible to
+        // anticipate it
+        pub fn extend_liquidity_lock(
             &mut self,
 
-            // Base coins to add to the pool
-            base_coin_bucket: Bucket,
+            // Lock certificate proof
+            lock_certificate_proof: Proof,
 
-            // Coins to add to the pool
-            coin_bucket: Bucket,
-        ) -> (
-            NonFungibleBucket, // Non fungibles representing the added liquidity
-            Option<Bucket>, // Eventual excess coins to return
-            Vec<Bucket>, // Eventual additional buckets returned by the hooks
+            // The new unlock date
+            unlock_date: Instant,
         ) {
-            // Find the pool
-            let coin_address = coin_bucket.resource_address();
-            let mut pool = self.pools.get_mut(&coin_address).expect(COIN_NOT_FOUND);
+            let (lock_id, lock_data) = self.check_lock_certificate(lock_certificate_proof);
+            assert!(
+                unlock_date.compare(lock_data.unlock_date, TimeComparisonOperator::Gt),
+                "A lock can only be extended",
+            );
 
-            // Use the proxy badge to call the add liquidity method of the pool
-            let (lp_bucket, remainings_bucket, hook_argument, event, mode) =
-                self.proxy_badge_vault.authorize_with_amount(
-                    1,
-                    || pool.component_address.add_liquidity(
-                        base_coin_bucket,
-                        coin_bucket,
-                    )
-                );
+            self.lock_certificate_resource_manager.update_non_fungible_data(
+                &NonFungibleLocalId::integer(lock_id),
+                "unlock_date",
+                unlock_date,
+            );
 
-            // Get all of the info about the pool before dropping the pool variable
-            let pool_enabled_hooks = pool.enabled_hooks.get_all_hooks(hook_argument.operation);
-            let creator_id = pool.creator_id;
-            drop(pool);
+            Runtime::emit_event(
+                LiquidityLockEvent {
+                    resource_address: lock_data.coin_resource_address,
+                    lock_id: lock_id,
+                    lp_share: lock_data.lp_share,
+                    unlock_date: unlock_date,
+                }
+            );
+        }
 
-            // Emit the AddLiquidityEvent created by the pool
-            self.emit_pool_event(event, 0);
+        // The holder of a lock certificate can use this method to get back the LP NFTs after the
+        // unlock date; the lock certificate is burned
+        pub fn unlock_liquidity(
+            &mut self,
 
-            // It is possible that the pool was empty (Uninitialised mode); in this case adding
-            // liquidity changes its state to Normal mode so the info in the creator NFT must be updated
-            if mode.is_some() {
-                self.update_mode_in_creator_nft(creator_id, mode.unwrap());
-            }
+            // The lock certificate
+            lock_certificate_bucket: Bucket,
 
-            // Execute hooks for the AddLiquidity operation
-            let buckets = self.execute_hooks(
-                &pool_enabled_hooks,
-                &hook_argument,
+        ) -> NonFungibleBucket // The LP NFTs
+        {
+            assert!(
+                lock_certificate_bucket.resource_address() == self.lock_certificate_resource_manager.address(),
+                WRONG_BADGE,
+            );
+            let non_fungible = lock_certificate_bucket.as_non_fungible().non_fungible::<LockCertificateData>();
+            let lock_id = match non_fungible.local_id() {
+                NonFungibleLocalId::Integer(lock_id) => lock_id.value(),
+                _ => Runtime::panic(SHOULD_NOT_HAPPEN.to_string()),
+            };
+            let lock_data = non_fungible.data();
+            assert!(
+                !Clock::current_time_is_strictly_before(lock_data.unlock_date, TimePrecision::Second),
+                "Liquidity still locked",
             );
 
-            (lp_bucket, remainings_bucket, buckets)
+            let lp_bucket = self.locked_lp_vaults.get_mut(&lock_id).unwrap().take_all();
+
+            self.pools.get_mut(&lock_data.coin_resource_address).expect(COIN_NOT_FOUND).locked_lp_share -= lock_data.lp_share;
+
+            lock_certificate_bucket.burn();
+
+            Runtime::emit_event(
+                LiquidityUnlockEvent {
+                    resource_address: lock_data.coin_resource_address,
+                    lock_id: lock_id,
+                    lp_share: lock_data.lp_share,
+                }
+            );
+
+            lp_bucket
         }
 
-        // Use this method to get back the liquidity previously added to the pool
-        // If the pool is in liquidation mode, only base coins will be returned, all of the coins
-        // are kept by the pool
-        // It is possible to redeem more than one liquidity token in a single operation
-        pub fn remove_liquidity(
+        // Locked LP NFTs keep taking part in liquidity campaigns; the holder of a lock certificate
+        // can use this method to get the rewards without unlocking them
+        pub fn get_locked_lp_rewards(
             &mut self,
 
-            // LP tokens representing the added liquidity
-            lp_bucket: Bucket,
-        ) -> (
-            FungibleBucket, // Base coins
-            Option<FungibleBucket>, // Coins (if the pool is not in liquidation mode)
-            Vec<Bucket>, // Eventual buckets returned by the hooks
-        ) {
-            // Use the LP token NonFungibleData to find the pool
-            let lp_id = &lp_bucket.as_non_fungible().non_fungible_local_ids()[0];
-            let lp_data = NonFungibleResourceManager::from(lp_bucket.resource_address()).get_non_fungible_data::<LPData>(lp_id);
-            let mut pool = self.pools.get_mut(&lp_data.coin_resource_address).expect(COIN_NOT_FOUND);
+            // Lock certificate proof
+            lock_certificate_proof: Proof,
 
-            // Use the proxy badge to invoke the remove_liquidity method of the pool
-            let (base_coin_bucket, coin_bucket, hook_argument, event) = self.proxy_badge_vault.authorize_with_amount(
-                1,
-                || pool.component_address.remove_liquidity(lp_bucket)
-            );
+            // The name of the registered hook managing the liquidity campaign (i.e. LpRewardsHook)
+            hook_name: String,
 
-            // Get the list of hooks for the RemoveLiquidity operation enabled on the pool and
-            // drop the pool variable
-            let pool_enabled_hooks = pool.enabled_hooks.get_all_hooks(hook_argument.operation);
-            drop(pool);
+        ) -> Bucket // The rewards
+        {
+            let (lock_id, lock_data) = self.check_lock_certificate(lock_certificate_proof);
 
-            // Emit the RemoveLiquidityEvent created by the pool
-            self.emit_pool_event(event, 0);
+            let hook_address = ComponentAddress::try_from(
+                self.registered_hooks.get(&hook_name).expect(UNKNOWN_HOOK).component_address
+            )
+            .unwrap();
 
-            // Execute all enabled hooks
-            let buckets = self.execute_hooks(
-                &pool_enabled_hooks,
-                &hook_argument,
-            );
+            // Show the hook a proof of the locked LP NFTs, they can't be moved by it
+            let lp_proof: Proof = self.locked_lp_vaults.get(&lock_id).unwrap()
+                .create_proof_of_non_fungibles(&lock_data.lp_ids.into_iter().collect())
+                .into();
 
-            (base_coin_bucket, coin_bucket, buckets)
+            Global::<AnyComponent>::from(hook_address).call_raw::<Bucket>(
+                "get_rewards",
+                scrypto_args!(lp_proof),
+            )
+        }
+
+        // Check a lock certificate proof and extract its numeric id and NonFungibleData
+        fn check_lock_certificate(
+            &self,
+            lock_certificate_proof: Proof,
+        ) -> (
+            u64, // Lock id
+            LockCertificateData,
+        ) {
+            let non_fungible = lock_certificate_proof.check_with_message(
+                self.lock_certificate_resource_manager.address(),
+                WRONG_BADGE,
+            )
+            .as_non_fungible()
+            .non_fungible::<LockCertificateData>();
+
+            match non_fungible.local_id() {
+                NonFungibleLocalId::Integer(lock_id) => (lock_id.value(), non_fungible.data()),
+                _ => Runtime::panic(SHOULD_NOT_HAPPEN.to_string()),
+            }
+        }
+
+        // Find the coin an LP token belongs to by looking at the fungible LP resources or at the
+        // LP NFT NonFungibleData
+        fn lp_coin_address(
+            &self,
+            lp_bucket: &Bucket,
+        ) -> ResourceAddress {
+            match self.fungible_lp_coins.get(&lp_bucket.resource_address()) {
+                Some(coin_address) => *coin_address,
+                None => {
+                    let lp_id = &lp_bucket.as_non_fungible().non_fungible_local_ids()[0];
+                    NonFungibleResourceManager::from(lp_bucket.resource_address())
+                        .get_non_fungible_data::<LPData>(lp_id)
+                        .coin_resource_address
+                },
+            }
         }
 
         // A user can invoke this method to:
@@ -2325,11 +5145,26 @@ mod radix_pump {
             // Id of the integrator whose interface allowed this operation
             mut integrator_id: u64,
 
+            // Minimum amount of coin2 the user accepts to receive, net of all fees
+            min_amount_out: Decimal,
+
+            // The swap fails if executed after this time (None = no deadline)
+            deadline: Option<Instant>,
+
+            // Proof of the allowlisted badge, only needed to buy during the launch phase of a gated
+            // launch
+            buyer_proof: Option<Proof>,
+
+            // Numeric id of the referral badge of the user who referred the trader
+            referral_id: Option<u64>,
+
         ) -> (
             FungibleBucket, // Coin2
             Vec<Bucket>, // Eventual buckets returned by hooks invoked for the Sell operation
             Vec<Bucket> // Eventual buckets returned by hooks invoked for the Buy operation
         ) {
+            self.check_not_paused();
+
             // Verify that the swap makes sense
             assert!(
                 coin1_bucket.amount() > Decimal::ZERO,
@@ -2340,11 +5175,21 @@ mod radix_pump {
                 coin1_address != coin2_address,
                 "Can't swap a coin with itself",
             );
+            check_deadline(deadline);
 
             // Verify that the integrator is valid and existing, or replace it with 0 (the component
             // owner)
             integrator_id = self.check_integrator_id(integrator_id);
 
+            // If coin1 has to be sold, quote the whole route and check the minimum amount out
+            // before the Sell hooks run
+            if coin1_address != self.base_coin_address {
+                check_min_amount_out(
+                    self.quote_swap(coin1_address, coin1_bucket.amount(), coin2_address, integrator_id).amount_out,
+                    min_amount_out,
+                );
+            }
+
             let (mut base_coin_bucket, buckets1) = match coin1_address == self.base_coin_address {
 
                 // If coin1 is the base coin there's no sell operation
@@ -2354,13 +5199,18 @@ mod radix_pump {
             };
  
             // Whatever coin1 was, now we have a bucket of base coins, use this to pay the fees to
-            // the integrator or the component owner
-            self.deposit_fee(
+            // the integrator or the component owner and eventually to the referrer
+            let referral_id = self.check_referral_id(referral_id);
+            let volume = base_coin_bucket.amount();
+            let fee_bucket = base_coin_bucket.take_advanced(
+                volume * self.integrator_fees(integrator_id).buy_sell_fee_percentage / dec!(100),
+                WithdrawStrategy::Rounded(RoundingMode::ToZero),
+            );
+            self.deposit_fee_with_referral(
                 integrator_id,
-                base_coin_bucket.take_advanced(
-                    base_coin_bucket.amount() * self.buy_sell_fee_percentage / dec!(100),
-                    WithdrawStrategy::Rounded(RoundingMode::ToZero),
-                )
+                referral_id,
+                volume,
+                fee_bucket,
             );
 
             let coin2_bucket: FungibleBucket;
@@ -2368,17 +5218,27 @@ mod radix_pump {
 
             if coin2_address == self.base_coin_address {
 
-                // If coin2 is the base coin there's no buy operation
+                // If coin2 is the base coin there's no buy operation, just check the amount net
+                // of fees
+                check_min_amount_out(base_coin_bucket.amount(), min_amount_out);
                 coin2_bucket = base_coin_bucket;
+
+                // No allowlist to check when selling
+                if let Some(proof) = buyer_proof {
+                    proof.drop();
+                }
             } else {
 
+                // Pay the creator fee for coin2
+                self.deposit_creator_fee(coin2_address, &mut base_coin_bucket);
+
                 // If coin2 has to be bought, find its pool
                 let mut pool = self.pools.get_mut(&coin2_address).expect("Coin2 not found");
 
                 // Use the proxy badge to call the buy method of the pool of coin2
-                let (bucket, hook_argument, event) = self.proxy_badge_vault.authorize_with_amount(
+                let (bucket, hook_argument, event, graduation) = self.proxy_badge_vault.authorize_with_amount(
                     1,
-                    || pool.component_address.buy(base_coin_bucket)
+                    || pool.component_address.buy(base_coin_bucket, min_amount_out, deadline, buyer_proof)
                 );
                 coin2_bucket = bucket;
 
@@ -2394,11 +5254,110 @@ mod radix_pump {
                     &pool_enabled_hooks,
                     &hook_argument,
                 );
+
+                // A buy during the launch phase of a bonding curve launched coin may have triggered
+                // its graduation
+                if let Some((graduation_hook_argument, graduation_event)) = graduation {
+                    buckets2.append(
+                        &mut self.graduate(graduation_hook_argument, graduation_event)
+                    );
+                }
             }
 
             (coin2_bucket, buckets1, buckets2)
         }
 
+        // A user can invoke this method to receive an exact amount of coin2 spending as few coin1
+        // as possible; the unused part of coin1 is returned to the user
+        pub fn swap_exact_out(
+            &mut self,
+
+            // Maximum amount of coins the user is willing to sell
+            mut max_in_bucket: Bucket,
+
+            // Address of the coins the user wants to receive
+            coin2_address: ResourceAddress,
+
+            // Amount of coin2 the user wants to receive, net of all fees
+            amount_out: Decimal,
+
+            // Id of the integrator whose interface allowed this operation
+            integrator_id: u64,
+
+            // Numeric id of the referral badge of the user who referred the trader
+            referral_id: Option<u64>,
+
+        ) -> (
+            FungibleBucket, // Coin2
+            Bucket, // Unused coin1
+            Vec<Bucket>, // Eventual buckets returned by hooks invoked for the Sell operation
+            Vec<Bucket> // Eventual buckets returned by hooks invoked for the Buy operation
+        ) {
+            self.check_not_paused();
+
+            // Verify that the swap makes sense
+            assert!(
+                amount_out > Decimal::ZERO,
+                "Amount out must be positive",
+            );
+            let coin1_address = max_in_bucket.resource_address();
+            assert!(
+                coin1_address != coin2_address,
+                "Can't swap a coin with itself",
+            );
+
+            // The fees depend on the integrator
+            let buy_sell_fee_percentage =
+                self.integrator_fees(self.check_integrator_id(integrator_id)).buy_sell_fee_percentage;
+
+            // Compute the base coins needed to get amount_out coin2, pool fees included
+            let base_coin_amount = match coin2_address == self.base_coin_address {
+                true => amount_out,
+                false => {
+                    let pool = self.pools.get(&coin2_address).expect("Coin2 not found");
+                    pool.component_address.get_buy_amount_in(amount_out)
+                },
+            };
+
+            // Add the fees owed to the integrator or the component owner and to the coin creators
+            let base_coin_amount = (
+                PreciseDecimal::from(base_coin_amount) /
+                (pdec!(1) - PreciseDecimal::from(buy_sell_fee_percentage) / pdec!(100)) /
+                (pdec!(1) - PreciseDecimal::from(self.sell_creator_fee_percentage(coin1_address)) / pdec!(100)) /
+                (pdec!(1) - PreciseDecimal::from(self.creator_fee_percentage(coin2_address)) / pdec!(100))
+            )
+                .checked_truncate(RoundingMode::ToPositiveInfinity)
+                .unwrap();
+
+            // Compute the coin1 needed to get these base coins
+            let coin1_amount = match coin1_address == self.base_coin_address {
+                true => base_coin_amount,
+                false => {
+                    let pool = self.pools.get(&coin1_address).expect("Coin1 not found");
+                    pool.component_address.get_sell_amount_in(base_coin_amount)
+                },
+            };
+            assert!(
+                coin1_amount <= max_in_bucket.amount(),
+                "Not enough coins to get {} coin2, {} needed",
+                amount_out,
+                coin1_amount,
+            );
+
+            // Do the swap with the needed coin1 only
+            let (coin2_bucket, buckets1, buckets2) = self.swap(
+                max_in_bucket.take(coin1_amount),
+                coin2_address,
+                integrator_id,
+                amount_out,
+                None,
+                None,
+                referral_id,
+            );
+
+            (coin2_bucket, max_in_bucket, buckets1, buckets2)
+        }
+
         // The component owner can call this method to create a pool for an already existing coin
         // The created pool will be empty (Uninitialised mode), it will need someone to
         // add_liquidity for it to be usable
@@ -2413,6 +5372,9 @@ mod radix_pump {
             sell_pool_fee_percentage: Decimal,
             flash_loan_pool_fee: Decimal,
 
+            // Whether the pool has to issue fungible LP units instead of LP NFTs
+            fungible_lp: bool,
+
         ) -> NonFungibleBucket // Creator badge for the pool
         {
             // Make sure there isn't already a pool for this coin
@@ -2475,9 +5437,23 @@ mod radix_pump {
                     component_address: pool.into(),
                     enabled_hooks: HooksPerOperation::new(),
                     creator_id: self.next_creator_badge_id,
+                    creator_fee_percentage: Decimal::ZERO,
+                    referral_id: None,
+                    integrator_id: 0,
+                    locked_lp_share: Decimal::ZERO,
                 }
             );
 
+            // A pool created while the component is paused starts paused too
+            if self.paused {
+                self.set_pool_globally_paused(coin_address, true);
+            }
+
+            // Switch the pool to fungible LP units if requested
+            if fungible_lp {
+                self.set_fungible_lp(coin_address);
+            }
+
             // Mint a creator badge and return it to the user
             self.mint_creator_badge(
                 coin_address,
@@ -2489,6 +5465,65 @@ mod radix_pump {
             )
         }
 
+        // The component owner can use this method to switch an existing pool from LP NFTs to
+        // fungible LP units
+        // The LP NFTs already minted can still be used to remove liquidity
+        // The switch is refused while the coin creator has a RemoveLiquidity hook enabled on the
+        // pool (i.e. a liquidity campaign is running): fungible LP units can't take part in it
+        pub fn enable_fungible_lp(
+            &mut self,
+
+            // The coin whose pool has to issue fungible LP units
+            coin_address: ResourceAddress,
+
+        ) -> ResourceAddress // Resource address of the fungible LP units
+        {
+            let pool = self.pools.get(&coin_address).expect(COIN_NOT_FOUND);
+            assert!(
+                pool.enabled_hooks.get_all_hooks(HookableOperation::RemoveLiquidity).iter()
+                    .all(|round_hooks| round_hooks.is_empty()),
+                "Liquidity campaign in progress, disable the RemoveLiquidity hooks first",
+            );
+            drop(pool);
+
+            self.set_fungible_lp(coin_address)
+        }
+
+        // Private method to switch a pool to fungible LP units and remember the coin the new
+        // resource belongs to
+        fn set_fungible_lp(
+            &mut self,
+
+            // The coin whose pool has to issue fungible LP units
+            coin_address: ResourceAddress,
+
+        ) -> ResourceAddress // Resource address of the fungible LP units
+        {
+            let pool = self.pools.get(&coin_address).expect(COIN_NOT_FOUND);
+            let coin_creator_badge_rule = self.creator_badge_rule(pool.creator_id);
+
+            let lp_resource_address = self.proxy_badge_vault.authorize_with_amount(
+                1,
+                || pool.component_address.enable_fungible_lp(
+                    coin_creator_badge_rule,
+                    self.owner_badge_address,
+                    self.dapp_definition,
+                )
+            );
+            drop(pool);
+
+            self.fungible_lp_coins.insert(lp_resource_address, coin_address);
+
+            Runtime::emit_event(
+                FungibleLpEnabledEvent {
+                    resource_address: coin_address,
+                    lp_resource_address: lp_resource_address,
+                }
+            );
+
+            lp_resource_address
+        }
+
         // This method allows the owner to add an externally instantiated component as a pool.
         // It can also replace an existing pool with a new one.
         pub fn new_launched_pool(
@@ -2523,6 +5558,10 @@ mod radix_pump {
                             component_address: component_address,
                             enabled_hooks: HooksPerOperation::new(),
                             creator_id: self.next_creator_badge_id,
+                            creator_fee_percentage: Decimal::ZERO,
+                            referral_id: None,
+                            integrator_id: 0,
+                            locked_lp_share: Decimal::ZERO,
                         }
                     );
                 }
@@ -2531,6 +5570,9 @@ mod radix_pump {
                     // If yes, replace the old one with the new one
                     pool.component_address = component_address;
                     pool.creator_id = self.next_creator_badge_id;
+                    pool.creator_fee_percentage = Decimal::ZERO;
+                    pool.referral_id = None;
+                    pool.integrator_id = 0;
 
                     drop(opt_pool);
 
@@ -2546,6 +5588,11 @@ mod radix_pump {
                 },
             }
 
+            // A pool created while the component is paused starts paused too
+            if self.paused {
+                self.set_pool_globally_paused(coin_address, true);
+            }
+
             // Mint a creator badge and return it to the user
             self.mint_creator_badge(
                 coin_address,
@@ -2557,6 +5604,40 @@ mod radix_pump {
             )
         }
 
+        // This private method completes the graduation of a bonding curve launched coin after the
+        // pool switched to the Normal mode during a buy: it emits the event, updates the creator
+        // badge and executes the BondingCurveGraduation hooks
+        fn graduate(
+            &mut self,
+
+            // The HookArgument and the event returned by the pool buy method
+            hook_argument: HookArgument,
+            event: AnyPoolEvent,
+
+        ) -> Vec<Bucket> // Eventual buckets returned by hooks invoked for the BondingCurveGraduation
+                         // operation
+        {
+            // Find the pool that graduated
+            let pool = self.pools.get(&hook_argument.coin_address).unwrap();
+            let pool_enabled_hooks = pool.enabled_hooks.get_all_hooks(hook_argument.operation);
+            let creator_id = pool.creator_id;
+
+            // Drop the pool variable to avoid conflicts in multiple borrows
+            drop(pool);
+
+            // Emit the BondingCurveGraduationEvent
+            self.emit_pool_event(event, 0);
+
+            // Update the pool_mode field in the coin creator badge
+            self.update_mode_in_creator_nft(creator_id, hook_argument.mode);
+
+            // Execute hooks for the BondingCurveGraduation operation
+            self.execute_hooks(
+                &pool_enabled_hooks,
+                &hook_argument,
+            )
+        }
+
         // The creator badge containd useful informations about the coin and the pool; most of these
         // informations are fixed, only pool_mode is mutable
         // This private method is used to update pool_mode in the NFT every time che pool changes
@@ -2589,7 +5670,7 @@ mod radix_pump {
         // badge that has not been disabled
         // If the chack fails, 0 is returned (it represents the componet owner himself)
         fn check_integrator_id(
-            &mut self,
+            &self,
 
             // The integrator_id to check
             mut integrator_id: u64,
@@ -2629,6 +5710,9 @@ mod radix_pump {
             // Numeric id of the integrator badge (or zero for the component owner)
             integrator_id: u64,
 
+            // Base coins involved in the operation that generated the fee
+            volume: Decimal,
+
             // Thee fee to deposit
             fee_bucket: FungibleBucket,
         ) {
@@ -2637,10 +5721,144 @@ mod radix_pump {
                 self.fee_vaults.insert(integrator_id, FungibleVault::new(self.base_coin_address));
             }
 
+            // Update the lifetime statistics for this vault
+            let mut integrator_stats = self.integrator_stats.get(&integrator_id)
+                .map(|stats| *stats)
+                .unwrap_or(IntegratorStats { volume: Decimal::ZERO, fees: Decimal::ZERO });
+            integrator_stats.volume += volume;
+            integrator_stats.fees += fee_bucket.amount();
+            self.integrator_stats.insert(integrator_id, integrator_stats);
+
             // Deposit the fee bucket in the vault
             self.fee_vaults.get_mut(&integrator_id).unwrap().put(fee_bucket);
         }
 
+        // Return the referral_id if the referral badge exists, None otherwise
+        // The caller is not known, so a user can specify his own referral badge: self-referral is
+        // allowed and works as a discount equal to the referrer share
+        fn check_referral_id(
+            &self,
+            referral_id: Option<u64>,
+        ) -> Option<u64> {
+            referral_id.filter(|id|
+                self.referral_badge_resource_manager.non_fungible_exists(
+                    &NonFungibleLocalId::integer(*id)
+                )
+            )
+        }
+
+        // This private method is used to deposit fees owed to the component owner or to an
+        // integrator when a referrer may be entitled to a share of them
+        // The volume of the operation is credited to the referrer and may upgrade his tier
+        fn deposit_fee_with_referral(
+            &mut self,
+
+            // Numeric id of the integrator badge (or zero for the component owner)
+            integrator_id: u64,
+
+            // Numeric id of the referral badge, it must have been checked already
+            referral_id: Option<u64>,
+
+            // Base coins involved in the operation
+            volume: Decimal,
+
+            // The fee to split between the referrer and the owner or integrator
+            mut fee_bucket: FungibleBucket,
+        ) {
+            if let Some(referral_id) = referral_id {
+                let id = NonFungibleLocalId::integer(referral_id);
+                let referral_data = self.referral_badge_resource_manager.get_non_fungible_data::<ReferralData>(&id);
+
+                // The tier list may have been shortened by the component owner, in this case the
+                // last tier is used but the tier stored in the badge is left untouched
+                let mut tier = referral_data.tier as usize;
+                let lookup_tier = std::cmp::min(tier, self.referral_tiers.len() - 1);
+
+                // The referrer share is computed with the tier he reached before this operation
+                let referral_fee_bucket = fee_bucket.take_advanced(
+                    fee_bucket.amount() * self.referral_tiers[lookup_tier].1 / dec!(100),
+                    WithdrawStrategy::Rounded(RoundingMode::ToZero),
+                );
+
+                Runtime::emit_event(
+                    ReferralFeeEvent {
+                        referral_id: referral_id,
+                        integrator_id: integrator_id,
+                        volume: volume,
+                        fee: referral_fee_bucket.amount(),
+                    }
+                );
+
+                // Create the referrer vault if it doesn't exist
+                if self.referral_fee_vaults.get(&referral_id).is_none() {
+                    self.referral_fee_vaults.insert(referral_id, FungibleVault::new(self.base_coin_address));
+                }
+                self.referral_fee_vaults.get_mut(&referral_id).unwrap().put(referral_fee_bucket);
+
+                // Update the volume and move to the highest reached tier
+                let total_volume = referral_data.volume + volume;
+                self.referral_badge_resource_manager.update_non_fungible_data(
+                    &id,
+                    "volume",
+                    total_volume,
+                );
+                while tier + 1 < self.referral_tiers.len() && self.referral_tiers[tier + 1].0 <= total_volume {
+                    tier += 1;
+                }
+                if tier as u8 > referral_data.tier {
+                    self.referral_badge_resource_manager.update_non_fungible_data(
+                        &id,
+                        "tier",
+                        tier as u8,
+                    );
+
+                    Runtime::emit_event(
+                        ReferralTierUpgradeEvent {
+                            referral_id: referral_id,
+                            tier: tier as u8,
+                            volume: total_volume,
+                        }
+                    );
+                }
+            }
+
+            self.deposit_fee(integrator_id, volume, fee_bucket);
+        }
+
+        // Fees paid by an integrator: the ones set by the component owner for him if any, the
+        // global ones otherwise
+        fn integrator_fees(
+            &self,
+
+            // Numeric id of the integrator badge, it must have been checked already
+            integrator_id: u64,
+
+        ) -> IntegratorFees {
+            match self.integrator_fees.get(&integrator_id) {
+                Some(integrator_fees) => *integrator_fees,
+                None => IntegratorFees {
+                    creation_fee_percentage: self.creation_fee_percentage,
+                    buy_sell_fee_percentage: self.buy_sell_fee_percentage,
+                    flash_loan_fee: self.flash_loan_fee,
+                    flash_loan_fee_percentage: self.flash_loan_fee_percentage,
+                },
+            }
+        }
+
+        // Make sure that an integrator badge exists and return its NonFungibleLocalId
+        fn integrator_local_id(
+            &self,
+            integrator_id: u64,
+        ) -> NonFungibleLocalId {
+            let id = NonFungibleLocalId::integer(integrator_id);
+            assert!(
+                integrator_id > 0 && self.integrator_badge_resource_manager.non_fungible_exists(&id),
+                "Integrator not found",
+            );
+
+            id
+        }
+
         // This private method is used to mint a new integrator badge
         pub fn new_integrator(
             &mut self,
@@ -2667,6 +5885,253 @@ mod radix_pump {
             integrator_badge
         }
 
+        // The component owner can use this method to rename an integrator and to enable or disable
+        // it; the fees generated through a disabled integrator go to the component owner
+        pub fn update_integrator(
+            &mut self,
+
+            // Numeric id of the integrator badge
+            integrator_id: u64,
+
+            // The new name, if it has to be changed
+            name: Option<String>,
+
+            // The new status, if it has to be changed
+            active: Option<bool>,
+        ) {
+            let id = self.integrator_local_id(integrator_id);
+
+            if name.is_some() {
+                self.integrator_badge_resource_manager.update_non_fungible_data(
+                    &id,
+                    "name",
+                    name.unwrap(),
+                );
+            }
+
+            if active.is_some() {
+                self.integrator_badge_resource_manager.update_non_fungible_data(
+                    &id,
+                    "active",
+                    active.unwrap(),
+                );
+            }
+        }
+
+        // The component owner can use this method to set fees specific to an integrator instead
+        // of the global ones
+        // If a timelock delay is set, the change must be queued with queue_owner_action instead
+        pub fn set_integrator_fees(
+            &mut self,
+
+            // Numeric id of the integrator badge
+            integrator_id: u64,
+
+            // Percentage fees paid to the integrator to create a new coin
+            creation_fee_percentage: Decimal,
+
+            // Percentage fees paid to the integrator to buy and sell coins
+            buy_sell_fee_percentage: Decimal,
+
+            // Fixed fee (in base coins) paid to the integrator to get a flash loan
+            flash_loan_fee: Decimal,
+
+            // Percentage of the value of the borrowed coins paid to the integrator to get a flash
+            // loan, in addition to flash_loan_fee
+            flash_loan_fee_percentage: Decimal,
+        ) {
+            self.check_no_timelock();
+
+            self.apply_set_integrator_fees(
+                integrator_id,
+                creation_fee_percentage,
+                buy_sell_fee_percentage,
+                flash_loan_fee,
+                flash_loan_fee_percentage,
+            );
+        }
+
+        // Set the fees of an integrator, this is called by set_integrator_fees and
+        // execute_owner_action
+        fn apply_set_integrator_fees(
+            &mut self,
+            integrator_id: u64,
+            creation_fee_percentage: Decimal,
+            buy_sell_fee_percentage: Decimal,
+            flash_loan_fee: Decimal,
+            flash_loan_fee_percentage: Decimal,
+        ) {
+            self.integrator_local_id(integrator_id);
+
+            // Make sure the parameters make sense
+            assert!(
+                creation_fee_percentage >= Decimal::ZERO && creation_fee_percentage < dec!(100),
+                "Creation fee percentage can go from 0 (included) to 100 (excluded)",
+            );
+            assert!(
+                buy_sell_fee_percentage >= Decimal::ZERO && buy_sell_fee_percentage < dec!(100),
+                "Buy & sell fee percentage can go from 0 (included) to 100 (excluded)",
+            );
+            assert!(
+                flash_loan_fee >= Decimal::ZERO,
+                "Flash loan fee can't be a negative number",
+            );
+            assert!(
+                flash_loan_fee_percentage >= Decimal::ZERO && flash_loan_fee_percentage < dec!(100),
+                "Flash loan fee percentage can go from 0 (included) to 100 (excluded)",
+            );
+
+            self.integrator_fees.insert(
+                integrator_id,
+                IntegratorFees {
+                    creation_fee_percentage: creation_fee_percentage,
+                    buy_sell_fee_percentage: buy_sell_fee_percentage,
+                    flash_loan_fee: flash_loan_fee,
+                    flash_loan_fee_percentage: flash_loan_fee_percentage,
+                }
+            );
+        }
+
+        // The component owner can use this method to make an integrator pay the global fees again
+        // If a timelock delay is set, the change must be queued with queue_owner_action instead
+        pub fn remove_integrator_fees(
+            &mut self,
+
+            // Numeric id of the integrator badge
+            integrator_id: u64,
+        ) {
+            self.check_no_timelock();
+
+            self.apply_remove_integrator_fees(integrator_id);
+        }
+
+        // Remove the fees of an integrator, this is called by remove_integrator_fees and
+        // execute_owner_action
+        fn apply_remove_integrator_fees(
+            &mut self,
+            integrator_id: u64,
+        ) {
+            self.integrator_fees.remove(&integrator_id);
+        }
+
+        // Read only method to get the lifetime statistics of a fee vault
+        pub fn get_integrator_stats(
+            &self,
+
+            // Numeric id of the integrator badge (or zero for the component owner)
+            integrator_id: u64,
+
+        ) -> (
+            Decimal, // Base coins involved in the operations that paid fees to the vault
+            Decimal, // Total fees deposited in the vault
+            Decimal, // Fees that can be withdrawn now
+        ) {
+            let (volume, fees) = match self.integrator_stats.get(&integrator_id) {
+                Some(integrator_stats) => (integrator_stats.volume, integrator_stats.fees),
+                None => (Decimal::ZERO, Decimal::ZERO),
+            };
+
+            let available_fees = match self.fee_vaults.get(&integrator_id) {
+                Some(fee_vault) => fee_vault.amount(),
+                None => Decimal::ZERO,
+            };
+
+            (volume, fees, available_fees)
+        }
+
+        // Anyone can use this method to get a referral badge; the badge id can then be shared with
+        // other users that will specify it when trading or launching coins
+        pub fn new_referral(&mut self) -> NonFungibleBucket // The minted referral badge
+        {
+            let referral_badge = self.referral_badge_resource_manager.mint_non_fungible(
+                &NonFungibleLocalId::integer(self.next_referral_id.into()),
+                ReferralData {
+                    creation_date: Clock::current_time_rounded_to_seconds(),
+                    volume: Decimal::ZERO,
+                    tier: 0,
+                }
+            );
+
+            // Prepare for the next one
+            self.next_referral_id += 1;
+
+            referral_badge
+        }
+
+        // A referrer can use this method to withdraw the fees he earned
+        pub fn get_referral_fees(
+            &mut self,
+
+            // Referral badge proof
+            referral_proof: Proof,
+
+        ) -> FungibleBucket // Fees collected (base coins)
+        {
+            let checked_proof = referral_proof.check_with_message(
+                self.referral_badge_resource_manager.address(),
+                WRONG_BADGE,
+            );
+
+            let referral_id = match checked_proof.as_non_fungible().non_fungible_local_id() {
+                NonFungibleLocalId::Integer(id) => id.value(),
+                _ => Runtime::panic(SHOULD_NOT_HAPPEN.to_string()),
+            };
+
+            // Empty the referrer vault
+            let fee_bucket = self.referral_fee_vaults.get_mut(&referral_id).expect("No fees yet").take_all();
+
+            Runtime::emit_event(
+                ReferralFeeClaimedEvent {
+                    referral_id: referral_id,
+                    amount: fee_bucket.amount(),
+                }
+            );
+
+            fee_bucket
+        }
+
+        // The component owner can use this method to change the referral tiers
+        // Referrers never move to a lower tier, even if the minimum volumes are increased
+        // If a timelock delay is set, the change must be queued with queue_owner_action instead
+        pub fn update_referral_tiers(
+            &mut self,
+
+            // List of (minimum volume, percentage of the owner or integrator fee paid to the
+            // referrer) sorted by minimum volume
+            referral_tiers: Vec<(Decimal, Decimal)>,
+        ) {
+            self.check_no_timelock();
+
+            self.apply_update_referral_tiers(referral_tiers);
+        }
+
+        // Set the referral tiers, this is called by update_referral_tiers and execute_owner_action
+        fn apply_update_referral_tiers(
+            &mut self,
+            referral_tiers: Vec<(Decimal, Decimal)>,
+        ) {
+            assert!(
+                referral_tiers.len() > 0 && referral_tiers.len() <= 256,
+                "There must be from 1 to 256 tiers",
+            );
+            assert!(
+                referral_tiers[0].0 == Decimal::ZERO,
+                "The first tier must have zero minimum volume",
+            );
+            for (index, (min_volume, fee_percentage)) in referral_tiers.iter().enumerate() {
+                assert!(
+                    index == 0 || *min_volume > referral_tiers[index - 1].0,
+                    "Tiers must be sorted by minimum volume",
+                );
+                assert!(
+                    *fee_percentage >= Decimal::ZERO && *fee_percentage <= dec!(100),
+                    "Referral fee percentage can go from 0 (included) to 100 (included)",
+                );
+            }
+
+            self.referral_tiers = referral_tiers;
+        }
+
         // This component sets the same dapp_definition metadata on all of the components and resources
         // Unfortunately there'a a limit an the number of objects a dApp definiton account can
         // handle, so this method update the dapp_definition value that will be set on the next created objects
@@ -2711,9 +6176,9 @@ mod radix_pump {
             let mut pool = self.pools.get_mut(&coin_address).expect("Coin not found");
 
             // Use the proxy badge to call the sell method of the pool of coin1
-            let (bucket, hook_argument, event) = self.proxy_badge_vault.authorize_with_amount(
+            let (mut bucket, hook_argument, event) = self.proxy_badge_vault.authorize_with_amount(
                 1,
-                || pool.component_address.sell(FungibleBucket(coin_bucket))
+                || pool.component_address.sell(FungibleBucket(coin_bucket), Decimal::ZERO, None)
             );
 
             // Find the hooks for the Sell operation and drop the pool variable
@@ -2723,6 +6188,11 @@ mod radix_pump {
             // Emit the SellEvent
             self.emit_pool_event(event, integrator_id);
 
+            // Pay the creator fee, no fees are paid in liquidation mode
+            if hook_argument.mode == PoolMode::Normal {
+                self.deposit_creator_fee(coin_address, &mut bucket);
+            }
+
             // Execute hooks for the Sell operation
             let buckets = self.execute_hooks(
                 &pool_enabled_hooks,
@@ -2731,5 +6201,97 @@ mod radix_pump {
 
             (bucket, buckets)
         }
+
+        // Creator fee percentage for a coin, zero for the base coin
+        fn creator_fee_percentage(
+            &self,
+            coin_address: ResourceAddress,
+        ) -> Decimal {
+            match self.pools.get(&coin_address) {
+                Some(pool) => pool.creator_fee_percentage,
+                None => Decimal::ZERO,
+            }
+        }
+
+        // Creator fee percentage paid when selling a coin, zero for the base coin and for pools not
+        // in Normal mode (see sell)
+        fn sell_creator_fee_percentage(
+            &self,
+            coin_address: ResourceAddress,
+        ) -> Decimal {
+            match self.pools.get(&coin_address) {
+                Some(pool) if pool.component_address.get_pool_info().pool_mode == PoolMode::Normal =>
+                    pool.creator_fee_percentage,
+                _ => Decimal::ZERO,
+            }
+        }
+
+        // Take the creator fee from a bucket of base coins and put it in the creator vault
+        fn deposit_creator_fee(
+            &mut self,
+
+            // The coin whose creator has to receive the fee
+            coin_address: ResourceAddress,
+
+            // The base coins to take the fee from
+            base_coin_bucket: &mut FungibleBucket,
+        ) {
+            let pool = self.pools.get(&coin_address).unwrap();
+            let creator_id = pool.creator_id;
+            let creator_fee_percentage = pool.creator_fee_percentage;
+            drop(pool);
+
+            if creator_fee_percentage == Decimal::ZERO {
+                return;
+            }
+
+            let fee_bucket = base_coin_bucket.take_advanced(
+                base_coin_bucket.amount() * creator_fee_percentage / dec!(100),
+                WithdrawStrategy::Rounded(RoundingMode::ToZero),
+            );
+
+            // Create the creator vault if it doesn't exist
+            if self.creator_fee_vaults.get(&creator_id).is_none() {
+                self.creator_fee_vaults.insert(creator_id, FungibleVault::new(self.base_coin_address));
+            }
+
+            self.creator_fee_vaults.get_mut(&creator_id).unwrap().put(fee_bucket);
+        }
+
+        // Get a flash loan of coins from a pool
+        fn borrow_coins(
+            &mut self,
+            coin_address: ResourceAddress,
+            amount: Decimal,
+        ) -> (
+            FungibleBucket, // Borrowed coins
+            Decimal, // Value of the borrowed coins in base coins
+        ) {
+            self.check_not_paused();
+
+            // Find the pool holding the coin
+            let mut pool = self.pools.get_mut(&coin_address).expect(COIN_NOT_FOUND);
+
+            // Use the proxy badge to call the get_flash_loan pool method
+            self.proxy_badge_vault.authorize_with_amount(
+                1,
+                || pool.component_address.get_flash_loan(amount)
+            )
+        }
+
+        // Mint a transient NFT listing the received flash loans
+        fn mint_flash_loan_nft(
+            &mut self,
+            loans: Vec<FlashLoan>,
+        ) -> NonFungibleBucket {
+            self.last_transient_nft_id += 1;
+
+            self.flash_loan_nft_resource_manager.mint_non_fungible(
+                &NonFungibleLocalId::integer(self.last_transient_nft_id),
+                FlashLoanData {
+                    loans: loans,
+                }
+            )
+        }
     }
 }
@@ -87,6 +87,9 @@ export price=10
 export buy_pool_fee=0.1
 export sell_pool_fee=0.1
 export flash_loan_pool_fee=1
+export integrator_id=0
+export anti_sniping_window=None
+export fungible_lp=false
 export ape_in_deposit=$(($launches_per_buyer * $base_coins_per_launch))
 export iterations=90
 for I in $(seq ${iterations})
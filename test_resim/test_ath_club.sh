@@ -53,6 +53,7 @@ get_pool_info () {
         'Enum::[3],') echo pool_mode: Normal ;;
         'Enum::[4],') echo pool_mode: Liquidation ;;
         'Enum::[5],') echo pool_mode: Uninitialised ;;
+        'Enum::[6],') echo pool_mode: Refunding ;;
       esac
       read lp_resource_address
       read coin_lp_ratio
@@ -193,6 +194,9 @@ export price=10
 export buy_pool_fee=0.01
 export sell_pool_fee=0.01
 export flash_loan_pool_fee=0.01
+export integrator_id=0
+export anti_sniping_window=None
+export fungible_lp=false
 echo run manifests/new_quick_launch.rtm
 resim run manifests/new_quick_launch.rtm >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
 export quick_launched_coin=$(grep 'Resource:' $OUTPUTFILE | head -n 1 | cut -d ' ' -f 3)
@@ -212,8 +216,13 @@ echo
 update_wallet_amounts
 export integrator_id=0
 export base_coin_amount=20
-echo resim call-method ${radix_pump_component} swap ${base_coin}:${base_coin_amount} ${quick_launched_coin} ${integrator_id}
-resim call-method ${radix_pump_component} swap ${base_coin}:${base_coin_amount} ${quick_launched_coin} ${integrator_id} >$OUTPUTFILE
+export min_amount_out=0
+export deadline=None
+export coin1=${base_coin}
+export coin1_amount=${base_coin_amount}
+export coin2=${quick_launched_coin}
+echo resim run manifests/swap.rtm
+resim run manifests/swap.rtm >$OUTPUTFILE
 export bought_coins=$(increase_in_wallet ${quick_launched_coin})
 export ath_club_nft_id="#$(grep -A 1 "ResAddr: ${ath_club_nft}" $OUTPUTFILE | tail -n 1 | cut -d '#' -f 2)#"
 echo "Bought ${bought_coins} ${quick_launched_coin}, received the ${ath_club_nft_id} ATH Club NFT"
@@ -224,8 +233,11 @@ get_pool_info ${quick_launched_coin}
 
 echo
 update_wallet_amounts
-echo resim call-method ${radix_pump_component} swap ${quick_launched_coin}:${bought_coins} ${base_coin} ${integrator_id}
-resim call-method ${radix_pump_component} swap ${quick_launched_coin}:${bought_coins} ${base_coin} ${integrator_id}>$OUTPUTFILE
+export coin1=${quick_launched_coin}
+export coin1_amount=${bought_coins}
+export coin2=${base_coin}
+echo resim run manifests/swap.rtm
+resim run manifests/swap.rtm >$OUTPUTFILE
 echo "Sold ${bought_coins} ${quick_launched_coin}, received $(increase_in_wallet ${base_coin}) base coin"
 grep 'Transaction Cost: ' $OUTPUTFILE
 
@@ -234,8 +246,11 @@ get_pool_info ${quick_launched_coin}
 
 echo
 update_wallet_amounts
-echo resim call-method ${radix_pump_component} swap ${base_coin}:$((${base_coin_amount} / 2)) ${quick_launched_coin} ${integrator_id}
-resim call-method ${radix_pump_component} swap ${base_coin}:$((${base_coin_amount} / 2)) ${quick_launched_coin} ${integrator_id} >$OUTPUTFILE
+export coin1=${base_coin}
+export coin1_amount=$((${base_coin_amount} / 2))
+export coin2=${quick_launched_coin}
+echo resim run manifests/swap.rtm
+resim run manifests/swap.rtm >$OUTPUTFILE
 export bought_coins=$(increase_in_wallet ${quick_launched_coin})
 export ath_club_nft_id="#$(grep -A 1 "ResAddr: ${ath_club_nft}" $OUTPUTFILE | tail -n 1 | cut -d '#' -f 2)#" || true
 echo "Bought ${bought_coins} ${quick_launched_coin}, received the ${ath_club_nft_id} ATH Club NFT (should be none)"
@@ -246,8 +261,11 @@ get_pool_info ${quick_launched_coin}
 
 echo
 update_wallet_amounts
-echo resim call-method ${radix_pump_component} swap ${base_coin}:${base_coin_amount} ${quick_launched_coin} ${integrator_id}
-resim call-method ${radix_pump_component} swap ${base_coin}:${base_coin_amount} ${quick_launched_coin} ${integrator_id} >$OUTPUTFILE
+export coin1=${base_coin}
+export coin1_amount=${base_coin_amount}
+export coin2=${quick_launched_coin}
+echo resim run manifests/swap.rtm
+resim run manifests/swap.rtm >$OUTPUTFILE
 export bought_coins=$(increase_in_wallet ${quick_launched_coin})
 export ath_club_nft_id="#$(grep -A 1 "ResAddr: ${ath_club_nft}" $OUTPUTFILE | tail -n 1 | cut -d '#' -f 2)#"
 echo "Bought ${bought_coins} ${quick_launched_coin}, received the ${ath_club_nft_id} ATH Club NFT"
@@ -66,9 +66,10 @@ echo resim call-function ${radix_pump_package} RadixPump new ${owner_badge} ${ba
 resim call-function ${radix_pump_package} RadixPump new ${owner_badge} ${base_coin} ${minimum_deposit} ${creation_fee_percentage} ${buy_sell_fee_percentage} ${flash_loan_fee} ${account} >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
 export radix_pump_component=$(grep 'Component:' $OUTPUTFILE | cut -d ' ' -f 3)
 export creator_badge=$(grep 'Resource:' $OUTPUTFILE | head -n 1 | cut -d ' ' -f 3)
+export flash_loan_nft=$(grep 'Resource:' $OUTPUTFILE | head -n 2 | tail -n 1 | cut -d ' ' -f 3)
 export hook_badge=$(grep 'Resource:' $OUTPUTFILE | head -n 3 | tail -n 1 | cut -d ' ' -f 3)
 export proxy_badge=$(grep 'Resource:' $OUTPUTFILE | head -n 4 | tail -n 1 | cut -d ' ' -f 3)
-echo -e "RadixPump component: ${radix_pump_component}\nCreator badge: ${creator_badge}\nHook badge: ${hook_badge}\nProxy badge: ${proxy_badge}"
+echo -e "RadixPump component: ${radix_pump_component}\nCreator badge: ${creator_badge}\nFlash loan transient NFT: ${flash_loan_nft}\nHook badge: ${hook_badge}\nProxy badge: ${proxy_badge}"
 grep 'Transaction Cost: ' $OUTPUTFILE
 
 echo
@@ -128,6 +129,9 @@ export price=10
 export buy_pool_fee=0.1
 export sell_pool_fee=0.1
 export flash_loan_pool_fee=1
+export integrator_id=0
+export anti_sniping_window=None
+export fungible_lp=false
 echo run manifests/new_quick_launch.rtm
 resim run manifests/new_quick_launch.rtm >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
 export quick_launched_coin1=$(grep 'Resource:' $OUTPUTFILE | head -n 1 | cut -d ' ' -f 3)
@@ -233,6 +237,23 @@ resim run manifests/dca_add_funds.rtm >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1
 echo Deposited additional ${quick_launched_coin1} in the Dca component
 grep 'Transaction Cost: ' $OUTPUTFILE
 
+echo
+echo resim run manifests/pause.rtm
+resim run manifests/pause.rtm >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+echo The whole RadixPump component has been paused
+grep 'Transaction Cost: ' $OUTPUTFILE
+
+echo
+echo resim call-method ${timer_component} alarm_clock ${timer_badge_number} --proofs ${alarm_clock_badge}:1
+resim call-method ${timer_component} alarm_clock ${timer_badge_number} --proofs ${alarm_clock_badge}:1 >$OUTPUTFILE && ( echo "This transaction was supposed to fail!" ; cat $OUTPUTFILE ; exit 1 )
+echo The DCA hook invoked by the Timer could not buy ${quick_launched_coin2} while the component is paused, the transaction failed as expected
+
+echo
+echo resim run manifests/unpause.rtm
+resim run manifests/unpause.rtm >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+echo The RadixPump component has been unpaused
+grep 'Transaction Cost: ' $OUTPUTFILE
+
 echo
 echo resim call-method ${timer_component} alarm_clock ${timer_badge_number} --proofs ${alarm_clock_badge}:1
 resim call-method ${timer_component} alarm_clock ${timer_badge_number} --proofs ${alarm_clock_badge}:1 >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
@@ -253,3 +274,22 @@ echo resim call-method ${timer_component} get_owner_fee --proofs ${owner_badge}:
 resim call-method ${timer_component} get_owner_fee --proofs ${owner_badge}:${owner_badge_id} >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
 echo Collected owner fees: $(increase_in_wallet $xrd)
 grep 'Transaction Cost: ' $OUTPUTFILE
+
+echo
+update_wallet_amounts
+export coin1=${quick_launched_coin1}
+export coin2=${quick_launched_coin2}
+export loan_amount1=1000
+export loan_amount2=2000
+export fee=3
+export integrator_id=0
+echo resim run manifests/get_flash_loans.rtm
+resim run manifests/get_flash_loans.rtm >$OUTPUTFILE && ( echo "This transaction was supposed to fail!" ; cat $OUTPUTFILE ; exit 1 )
+echo "Tried to return the loans paying only ${fee} ${base_coin}, not enough for the component owner and pool fees of both coins, the transaction failed as expected"
+
+echo
+export fee=5
+echo resim run manifests/get_flash_loans.rtm
+resim run manifests/get_flash_loans.rtm >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+echo "Borrowed ${loan_amount1} ${coin1} and ${loan_amount2} ${coin2} with a single transient NFT and returned them paying ${fee} ${base_coin}: ${flash_loan_fee} ${base_coin} per coin to the component owner, ${flash_loan_pool_fee} ${base_coin} to the ${coin1} pool and the rest to the ${coin2} pool"
+grep 'Transaction Cost: ' $OUTPUTFILE
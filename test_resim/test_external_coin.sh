@@ -53,6 +53,7 @@ get_pool_info () {
         'Enum::[3],') echo pool_mode: Normal ;;
         'Enum::[4],') echo pool_mode: Liquidation ;;
         'Enum::[5],') echo pool_mode: Uninitialised ;;
+        'Enum::[6],') echo pool_mode: Refunding ;;
       esac
       read lp_resource_address
       read coin_lp_ratio
@@ -171,8 +172,8 @@ echo
 export buy_pool_fee=0.1
 export sell_pool_fee=0.1
 export flash_loan_pool_fee=1
-echo "resim call-method ${radix_pump_component} new_pool $met $buy_pool_fee $sell_pool_fee $flash_loan_pool_fee --proofs ${owner_badge}:${owner_badge_id}"
-resim call-method ${radix_pump_component} new_pool $met $buy_pool_fee $sell_pool_fee $flash_loan_pool_fee --proofs ${owner_badge}:${owner_badge_id} >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+echo "resim call-method ${radix_pump_component} new_pool $met $buy_pool_fee $sell_pool_fee $flash_loan_pool_fee false --proofs ${owner_badge}:${owner_badge_id}"
+resim call-method ${radix_pump_component} new_pool $met $buy_pool_fee $sell_pool_fee $flash_loan_pool_fee false --proofs ${owner_badge}:${owner_badge_id} >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
 export lp_met=$(grep 'Resource:' $OUTPUTFILE | cut -d ' ' -f 3)
 export creator_badge_id="#$(grep -A 1 "ResAddr: ${creator_badge}" $OUTPUTFILE | tail -n 1 | cut -d '#' -f 2)#"
 echo Pool for $met created, LP token ${lp_met}, creator badge id ${creator_badge_id}
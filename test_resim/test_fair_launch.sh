@@ -53,6 +53,7 @@ get_pool_info () {
         'Enum::[3],') echo pool_mode: Normal ;;
         'Enum::[4],') echo pool_mode: Liquidation ;;
         'Enum::[5],') echo pool_mode: Uninitialised ;;
+        'Enum::[6],') echo pool_mode: Refunding ;;
       esac
       read lp_resource_address
       read coin_lp_ratio
@@ -236,9 +237,15 @@ export social_url='Array<String>()'
 export info_url=""
 export price=100
 export creator_locked_percentage=10
+export soft_cap=None
+export hard_cap=None
+export access_rule=None
+export max_buy_per_holder=None
+export vesting_schedule=None
 export buy_pool_fee=5
 export sell_pool_fee=0.1
 export flash_loan_pool_fee=1
+export integrator_id=0
 echo resim run manifests/new_fair_launch.rtm
 resim run manifests/new_fair_launch.rtm >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
 export fair_launched_coin=$(grep 'Resource:' $OUTPUTFILE | head -n 1 | cut -d ' ' -f 3)
@@ -294,8 +301,13 @@ echo
 update_wallet_amounts
 export payment=1000
 export integrator_id=0
-echo resim call-method ${radix_pump_component} swap ${base_coin}:$payment ${fair_launched_coin} ${integrator_id}
-resim call-method ${radix_pump_component} swap ${base_coin}:$payment ${fair_launched_coin} ${integrator_id} >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+export min_amount_out=0
+export deadline=None
+export coin1=${base_coin}
+export coin1_amount=$payment
+export coin2=${fair_launched_coin}
+echo resim run manifests/swap.rtm
+resim run manifests/swap.rtm >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
 echo Bought $(increase_in_wallet ${fair_launched_coin}) ${fair_launched_coin} for $payment ${base_coin}
 echo TestHook2 coin received: $(increase_in_wallet ${test_hook2_coin})
 grep 'Transaction Cost: ' $OUTPUTFILE
@@ -303,8 +315,11 @@ grep 'Transaction Cost: ' $OUTPUTFILE
 echo
 update_wallet_amounts
 export payment=1000
-echo resim call-method ${radix_pump_component} swap ${base_coin}:$payment ${fair_launched_coin} ${integrator_id}
-resim call-method ${radix_pump_component} swap ${base_coin}:$payment ${fair_launched_coin} ${integrator_id} >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+export coin1=${base_coin}
+export coin1_amount=$payment
+export coin2=${fair_launched_coin}
+echo resim run manifests/swap.rtm
+resim run manifests/swap.rtm >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
 echo Bought $(increase_in_wallet ${fair_launched_coin}) ${fair_launched_coin} for $payment ${base_coin}
 echo TestHook2 coin received: $(increase_in_wallet ${test_hook2_coin})
 grep 'Transaction Cost: ' $OUTPUTFILE
@@ -314,8 +329,11 @@ get_pool_info ${fair_launched_coin}
 
 echo
 export payment=1
-echo resim call-method ${radix_pump_component} swap ${fair_launched_coin}:$payment ${base_coin} ${integrator_id}
-resim call-method ${radix_pump_component} swap ${fair_launched_coin}:$payment ${base_coin} ${integrator_id} >$OUTPUTFILE && ( echo "This transaction was supposed to fail!" ; cat $OUTPUTFILE ; exit 1 )
+export coin1=${fair_launched_coin}
+export coin1_amount=$payment
+export coin2=${base_coin}
+echo resim run manifests/swap.rtm
+resim run manifests/swap.rtm >$OUTPUTFILE && ( echo "This transaction was supposed to fail!" ; cat $OUTPUTFILE ; exit 1 )
 echo Tried to sellf ${fair_launched_coin} during fair launch, it is forbidden so the transaction failed
 
 echo
@@ -344,8 +362,11 @@ get_pool_info ${fair_launched_coin}
 echo
 update_wallet_amounts
 export payment=1
-echo resim call-method ${radix_pump_component} swap ${fair_launched_coin}:$payment ${base_coin} ${integrator_id}
-resim call-method ${radix_pump_component} swap ${fair_launched_coin}:$payment ${base_coin} ${integrator_id} >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+export coin1=${fair_launched_coin}
+export coin1_amount=$payment
+export coin2=${base_coin}
+echo resim run manifests/swap.rtm
+resim run manifests/swap.rtm >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
 echo Sold $payment ${fair_launched_coin} for $(increase_in_wallet ${base_coin}) ${base_coin}
 echo TestHook2 coin received: $(increase_in_wallet ${test_hook2_coin})
 grep 'Transaction Cost: ' $OUTPUTFILE
@@ -353,6 +374,12 @@ grep 'Transaction Cost: ' $OUTPUTFILE
 echo
 get_pool_info ${fair_launched_coin}
 
+echo
+export twap_window=3600
+echo resim call-method ${radix_pump_component} get_twap ${fair_launched_coin} $twap_window
+resim call-method ${radix_pump_component} get_twap ${fair_launched_coin} $twap_window >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+echo "TWAP over the last $twap_window seconds: $(grep -m 1 '─ Decimal(' $OUTPUTFILE | cut -d '"' -f 2) (should be the launch price, the sell happened in the current second)"
+
 echo
 unix_epoch=$(($end_launch_time + 604800))
 date=$(date -u -d @$unix_epoch +"%Y-%m-%dT%H:%M:%SZ")
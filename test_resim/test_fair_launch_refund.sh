@@ -0,0 +1,267 @@
+#!/bin/bash
+
+update_wallet_amounts() {
+  resim show |
+    grep ' resource_sim' |
+    tr -d : |
+    awk '{print $2 " " $3}' >$WALLETFILE
+}
+
+increase_in_wallet() {
+  old_amount=$(grep $1 $WALLETFILE | cut -d ' ' -f 2)
+  if [ "$old_amount" = "" ]
+  then
+    old_amount=0
+  fi
+
+  amount=$(resim show | grep $1 | cut -d ' ' -f 3)
+  if [ "$amount" = "" ]
+  then
+    amount=0
+  fi
+
+  echo $amount - $old_amount | bc
+}
+
+get_pool_info () {
+  echo PoolInfo for $1
+  resim call-method $radix_pump_component get_pool_info $1 |
+    grep -A 100 '├─ Tuple(' | (
+      read x
+      read x
+      read base_coin_amount
+      echo base_coin_amount: $(echo $base_coin_amount | cut -d '"' -f 2)
+      read coin_amount
+      echo coin_amount: $(echo $coin_amount | cut -d '"' -f 2)
+      read last_price
+      echo last_price: $(echo $last_price | cut -d '"' -f 2)
+      read price
+      echo price: $(echo $price | cut -d '"' -f 2)
+      read circulating_supply
+      echo circulating_supply: $(echo $circulating_supply | cut -d '"' -f 2)
+      read total_buy_fee_percentage
+      echo total_buy_fee_percentage: $(echo $total_buy_fee_percentage | cut -d '"' -f 2)
+      read total_sell_fee_percentage
+      echo total_sell_fee_percentage: $(echo $total_sell_fee_percentage | cut -d '"' -f 2)
+      read total_flash_loan_fee
+      echo total_flash_loan_fee: $(echo $total_flash_loan_fee | cut -d '"' -f 2)
+      read pool_mode
+      case $pool_mode in 
+        'Enum::[0],') echo pool_mode: WaitingForLaunch ;;
+        'Enum::[1],') echo pool_mode: Launching ;;
+        'Enum::[2],') echo pool_mode: TerminatingLaunch ;;
+        'Enum::[3],') echo pool_mode: Normal ;;
+        'Enum::[4],') echo pool_mode: Liquidation ;;
+        'Enum::[5],') echo pool_mode: Uninitialised ;;
+        'Enum::[6],') echo pool_mode: Refunding ;;
+      esac
+      read lp_resource_address
+      read coin_lp_ratio
+      echo coin_lp_ratio : $(echo $coin_lp_ratio | cut -d '"' -f 2)
+      read end_launch_time
+      if [ "$end_launch_time" = "Enum::[1](" ]
+      then
+	read end_launch_time
+	read x
+	echo end_launch_time: $end_launch_time
+      fi
+      read unlocking_time
+      if [ "$unlocking_time" = "Enum::[1](" ]
+      then
+	read unlocking_time
+	read x
+	echo unlocking_time: $unlocking_time
+      fi
+      read initial_locked_amount
+      if [ "$initial_locked_amount" = "Enum::[1](" ]
+      then
+	read initial_locked_amount
+	read x
+	echo initial_locked_amount: $(echo $initial_locked_amount | cut -d '"' -f 2)
+      fi
+      read unlocked_amount
+      if [ "$unlocked_amount" = "Enum::[1](" ]
+      then
+	read unlocked_amount
+	read x
+	echo unlocked_amount: $(echo $unlocked_amount | cut -d '"' -f 2)
+      fi
+      read ticket_price
+      if [ "$ticket_price" = "Enum::[1](" ]
+      then
+	read ticket_price
+	read x
+	echo ticket_price: $(echo $ticket_price | cut -d '"' -f 2)
+      fi
+      read winning_tickets
+      if [ "$winning_tickets" = "Enum::[1](" ]
+      then
+        read winning_tickets
+	read x
+        echo winning_tickets: $winning_tickets
+      fi
+      read coins_per_winning_ticket
+      if [ "$coins_per_winning_ticket" = "Enum::[1](" ]
+      then
+	read coins_per_winning_ticket
+	read x
+	echo coins_per_winning_ticket: $(echo $coins_per_winning_ticket | cut -d '"' -f 2)
+      fi
+    )
+}
+
+OUTPUTFILE=$(mktemp)
+WALLETFILE=$(mktemp)
+
+set -e
+
+clear
+resim reset
+
+echo
+resim new-account >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+export account=$(grep 'Account component address:' $OUTPUTFILE | cut -d ' ' -f 4)
+export owner_badge=$(grep 'Owner badge:' $OUTPUTFILE | cut -d ':' -f 2 | tr -d '[:space:]')
+export owner_badge_id=$(grep 'Owner badge:' $OUTPUTFILE | cut -d ':' -f 3)
+echo -e "Account address: $account\nOwner badge: $owner_badge\nOwner badge id: ${owner_badge_id}"
+
+echo
+resim publish ../random_component >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+export random_component_package=$(grep 'Success! New Package:' $OUTPUTFILE | cut -d ' ' -f 4)
+echo RandomComponent package: ${random_component_package}
+
+echo
+echo resim call-function ${random_component_package} RandomComponent new
+resim call-function ${random_component_package} RandomComponent new >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+export random_component=$(grep 'Component:' $OUTPUTFILE | cut -d ' ' -f 3)
+echo RandomComponent: ${random_component}
+grep 'Transaction Cost: ' $OUTPUTFILE
+
+echo
+resim publish ../radix_pump >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+export radix_pump_package=$(grep 'Success! New Package:' $OUTPUTFILE | cut -d ' ' -f 4)
+echo RadixPump package: ${radix_pump_package}
+
+echo
+export base_coin=resource_sim1tknxxxxxxxxxradxrdxxxxxxxxx009923554798xxxxxxxxxakj8n3
+export minimum_deposit=1000
+export creation_fee_percentage=0.1
+export buy_sell_fee_percentage=0.1
+export flash_loan_fee=1
+echo resim call-function ${radix_pump_package} RadixPump new ${owner_badge} ${base_coin} ${minimum_deposit} ${creation_fee_percentage} ${buy_sell_fee_percentage} ${flash_loan_fee} ${account}
+resim call-function ${radix_pump_package} RadixPump new ${owner_badge} ${base_coin} ${minimum_deposit} ${creation_fee_percentage} ${buy_sell_fee_percentage} ${flash_loan_fee} ${account} >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+export radix_pump_component=$(grep 'Component:' $OUTPUTFILE | cut -d ' ' -f 3)
+export creator_badge=$(grep 'Resource:' $OUTPUTFILE | head -n 1 | cut -d ' ' -f 3)
+export flash_loan_nft=$(grep 'Resource:' $OUTPUTFILE | head -n 2 | tail -n 1 | cut -d ' ' -f 3)
+export proxy_badge=$(grep 'Resource:' $OUTPUTFILE | head -n 4 | tail -n 1 | cut -d ' ' -f 3)
+export integrator_badge=$(grep 'Resource:' $OUTPUTFILE | head -n 5 | tail -n 1 | cut -d ' ' -f 3)
+echo -e "RadixPump component: ${radix_pump_component}\nCreator badge: ${creator_badge}\nFlash loan transient NFT: ${flash_loan_nft}\nProxy badge: ${proxy_badge}\nIntegrator badge: ${integrator_badge}"
+
+echo
+export min_launch_duration=604800
+export min_lock_duration=5184000
+echo "resim call-method ${radix_pump_component} update_time_limits $min_launch_duration $min_lock_duration --proofs ${owner_badge}:${owner_badge_id}"
+resim call-method ${radix_pump_component} update_time_limits $min_launch_duration $min_lock_duration --proofs ${owner_badge}:${owner_badge_id} >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+echo Set limits min_launch_duration: 604800 min_lock_duration: 5184000
+grep 'Transaction Cost: ' $OUTPUTFILE
+
+echo
+export symbol=SC
+export name=SoftCapCoin
+export icon=https://fairitaly.org/fair/wp-content/uploads/2023/03/logofairtondo.png
+export description="Fair launched coin with soft and hard cap"
+export social_url='Array<String>()'
+export info_url=""
+export price=100
+export creator_locked_percentage=10
+export soft_cap='Some(Decimal("5000"))'
+export hard_cap='Some(Decimal("6000"))'
+export access_rule=None
+export max_buy_per_holder=None
+export vesting_schedule=None
+export buy_pool_fee=5
+export sell_pool_fee=0.1
+export flash_loan_pool_fee=1
+export integrator_id=0
+echo resim run manifests/new_fair_launch.rtm
+resim run manifests/new_fair_launch.rtm >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+export fair_launched_coin=$(grep 'Resource:' $OUTPUTFILE | head -n 1 | cut -d ' ' -f 3)
+export creator_badge_id="#$(grep -A 1 "ResAddr: ${creator_badge}" $OUTPUTFILE | tail -n 1 | cut -d '#' -f 2)#"
+echo Fair launched ${fair_launched_coin} with soft cap 5000 and hard cap 6000
+echo Creator badge id: ${creator_badge_id}
+grep 'Transaction Cost: ' $OUTPUTFILE
+
+echo
+export unix_epoch=1800000000
+date=$(date -u -d @${unix_epoch} +"%Y-%m-%dT%H:%M:%SZ")
+resim set-current-time $date
+echo Date is now $unix_epoch
+export end_launch_time=$(($unix_epoch + $min_launch_duration))
+export unlocking_time=$(($unix_epoch + $min_launch_duration + $min_lock_duration))
+echo resim run manifests/launch.rtm
+resim run manifests/launch.rtm >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+echo Fair sale launched for ${fair_launched_coin}
+grep 'Transaction Cost: ' $OUTPUTFILE
+
+echo
+update_wallet_amounts
+export payment=1000
+export integrator_id=0
+export min_amount_out=0
+export deadline=None
+export coin1=${base_coin}
+export coin1_amount=$payment
+export coin2=${fair_launched_coin}
+echo resim run manifests/swap.rtm
+resim run manifests/swap.rtm >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+export bought_coins=$(increase_in_wallet ${fair_launched_coin})
+echo Bought ${bought_coins} ${fair_launched_coin} for $payment ${base_coin}
+grep 'Transaction Cost: ' $OUTPUTFILE
+
+echo
+export payment=5001
+export coin1_amount=$payment
+echo resim run manifests/swap.rtm
+resim run manifests/swap.rtm >$OUTPUTFILE && ( echo "This transaction was supposed to fail!" ; cat $OUTPUTFILE ; exit 1 )
+echo Tried to buy over the hard cap, the transaction failed as expected
+
+echo
+get_pool_info ${fair_launched_coin}
+
+echo
+echo resim run manifests/terminate_launch.rtm
+resim run manifests/terminate_launch.rtm >$OUTPUTFILE && ( echo "This transaction was supposed to fail!" ; cat $OUTPUTFILE ; exit 1 )
+echo Tried to terminate the launch ahead of time, the transaction failed as expected
+
+echo
+unix_epoch=$(($unix_epoch + $min_launch_duration))
+date=$(date -u -d @$unix_epoch +"%Y-%m-%dT%H:%M:%SZ")
+resim set-current-time $date
+echo Date is now $unix_epoch
+update_wallet_amounts
+echo resim run manifests/terminate_launch.rtm
+resim run manifests/terminate_launch.rtm >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+echo Fair launch terminated below the soft cap, creator received $(increase_in_wallet ${base_coin}) ${base_coin}
+grep 'Transaction Cost: ' $OUTPUTFILE
+
+echo
+get_pool_info ${fair_launched_coin}
+
+echo
+export payment=1000
+export coin1=${base_coin}
+export coin1_amount=$payment
+export coin2=${fair_launched_coin}
+echo resim run manifests/swap.rtm
+resim run manifests/swap.rtm >$OUTPUTFILE && ( echo "This transaction was supposed to fail!" ; cat $OUTPUTFILE ; exit 1 )
+echo Tried to buy while the pool is refunding, the transaction failed as expected
+
+echo
+update_wallet_amounts
+echo resim call-method ${radix_pump_component} refund ${fair_launched_coin}:${bought_coins}
+resim call-method ${radix_pump_component} refund ${fair_launched_coin}:${bought_coins} >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+echo Returned ${bought_coins} ${fair_launched_coin}, received $(increase_in_wallet ${base_coin}) ${base_coin}
+grep 'Transaction Cost: ' $OUTPUTFILE
+
+echo
+get_pool_info ${fair_launched_coin}
@@ -108,6 +108,9 @@ export price=10
 export buy_pool_fee=0.1
 export sell_pool_fee=0.1
 export flash_loan_pool_fee=1
+export integrator_id=0
+export anti_sniping_window=None
+export fungible_lp=false
 echo run manifests/new_quick_launch.rtm
 resim run manifests/new_quick_launch.rtm >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
 export quick_launched_coin=$(grep 'Resource:' $OUTPUTFILE | head -n 1 | cut -d ' ' -f 3)
@@ -148,8 +151,13 @@ echo Done ${orders} orders, some of them were matched immediately so there are o
 
 echo
 export integrator_id=0
-echo resim call-method ${radix_pump_component} swap ${quick_launched_coin}:${quick_launched_coin_received} ${base_coin} ${integrator_id}
-resim call-method ${radix_pump_component} swap ${quick_launched_coin}:${quick_launched_coin_received} ${base_coin} ${integrator_id} >$OUTPUTFILE
+export min_amount_out=0
+export deadline=None
+export coin1=${quick_launched_coin}
+export coin1_amount=${quick_launched_coin_received}
+export coin2=${base_coin}
+echo resim run manifests/swap.rtm
+resim run manifests/swap.rtm >$OUTPUTFILE
 first=$(grep -n filled_orders_id $OUTPUTFILE | head -n 1 | cut -d : -f 1)
 last=$(grep -n filled_orders_id $OUTPUTFILE | tail -n 1 | cut -d : -f 1)
 export filled_orders=$(($last - $first - 2))
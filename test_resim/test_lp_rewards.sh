@@ -69,6 +69,7 @@ export creator_badge=$(grep 'Resource:' $OUTPUTFILE | head -n 1 | cut -d ' ' -f
 export flash_loan_nft=$(grep 'Resource:' $OUTPUTFILE | head -n 2 | tail -n 1 | cut -d ' ' -f 3)
 export proxy_badge=$(grep 'Resource:' $OUTPUTFILE | head -n 4 | tail -n 1 | cut -d ' ' -f 3)
 export integrator_badge=$(grep 'Resource:' $OUTPUTFILE | head -n 5 | tail -n 1 | cut -d ' ' -f 3)
+export lock_certificate=$(grep 'Resource:' $OUTPUTFILE | head -n 8 | tail -n 1 | cut -d ' ' -f 3)
 echo -e "RadixPump component: ${radix_pump_component}\nCreator badge: ${creator_badge}\nFlash loan transient NFT: ${flash_loan_nft}\nProxy badge: ${proxy_badge}\n\nIntegrator badge: ${integrator_badge}"
 grep 'Transaction Cost: ' $OUTPUTFILE
 
@@ -88,7 +89,7 @@ grep 'Transaction Cost: ' $OUTPUTFILE
 echo
 export hook_name=LpRewards
 export test_hook_component=${lp_rewards_component}
-export operations='"AddLiquidity", "RemoveLiquidity"'
+export operations='"AddLiquidity", "RemoveLiquidity", "SplitLiquidity", "MergeLiquidity"'
 echo resim run manifests/register_hook.rtm
 resim run manifests/register_hook.rtm >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
 echo Registered hook ${hook_name} for operations ${operations}
@@ -115,6 +116,9 @@ export price=10
 export buy_pool_fee=0.1
 export sell_pool_fee=0.1
 export flash_loan_pool_fee=1
+export integrator_id=0
+export anti_sniping_window=None
+export fungible_lp=false
 echo run manifests/new_quick_launch.rtm
 resim run manifests/new_quick_launch.rtm >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
 export quick_launched_coin=$(grep 'Resource:' $OUTPUTFILE | head -n 1 | cut -d ' ' -f 3)
@@ -133,12 +137,17 @@ export reward_coin=$(grep 'Resource:' $OUTPUTFILE | head -n 1 | cut -d ' ' -f 3)
 echo Created RewardCoin ${reward_coin}
 
 echo
-export enabled_operations='"RemoveLiquidity"'
+export enabled_operations='"RemoveLiquidity", "SplitLiquidity", "MergeLiquidity"'
 echo resim run manifests/creator_enable_hook.rtm
 resim run manifests/creator_enable_hook.rtm >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
 echo Enabled hook ${hook_name} for operations ${enabled_operations} on ${quick_launched_coin}
 grep 'Transaction Cost: ' $OUTPUTFILE
 
+echo
+echo resim call-method ${radix_pump_component} enable_fungible_lp ${quick_launched_coin} --proofs ${owner_badge}:${owner_badge_id}
+resim call-method ${radix_pump_component} enable_fungible_lp ${quick_launched_coin} --proofs ${owner_badge}:${owner_badge_id} >$OUTPUTFILE && ( echo "This transaction was supposed to fail!" ; cat $OUTPUTFILE ; exit 1 )
+echo Tried to switch ${quick_launched_coin} to fungible LP units while the RemoveLiquidity hook is enabled and the transaction failed as expected
+
 echo
 export unix_epoch=1800000000
 date=$(date -u -d @${unix_epoch} +"%Y-%m-%dT%H:%M:%SZ")
@@ -192,6 +201,90 @@ date=$(date -u -d @${unix_epoch} +"%Y-%m-%dT%H:%M:%SZ")
 resim set-current-time $date
 echo Date is now $unix_epoch
 
+echo
+update_wallet_amounts
+export percentage=30
+echo resim call-method ${radix_pump_component} remove_liquidity_partial ${lp_quick}:${lp_id1} ${percentage}
+resim call-method ${radix_pump_component} remove_liquidity_partial ${lp_quick}:${lp_id1} ${percentage} >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+echo Removed ${percentage}% of the liquidity of LP token ${lp_id1}, received $(increase_in_wallet ${quick_launched_coin}) ${quick_launched_coin} and $(increase_in_wallet ${reward_coin}) ${reward_coin}
+grep 'Transaction Cost: ' $OUTPUTFILE
+
+echo
+update_wallet_amounts
+export percentage=50
+echo resim call-method ${radix_pump_component} split_lp ${lp_quick}:${lp_id2} ${percentage}
+resim call-method ${radix_pump_component} split_lp ${lp_quick}:${lp_id2} ${percentage} >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+export lp_id3="#$(grep -A 1 "ResAddr: ${lp_quick}" $OUTPUTFILE | tail -n 1 | cut -d '#' -f 2)#"
+echo Split LP token ${lp_id2}, ${percentage}% of its liquidity moved to LP token ${lp_id3}, received $(increase_in_wallet ${reward_coin}) ${reward_coin}
+grep 'Transaction Cost: ' $OUTPUTFILE
+
+echo
+echo resim call-method ${radix_pump_component} merge_lp ${lp_quick}:${lp_id2},${lp_id3}
+resim call-method ${radix_pump_component} merge_lp ${lp_quick}:${lp_id2},${lp_id3} >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+echo Merged LP tokens ${lp_id2} and ${lp_id3} into ${lp_id2}
+grep 'Transaction Cost: ' $OUTPUTFILE
+
+echo
+export coin=${quick_launched_coin}
+export lp_ids="$(echo ${lp_id1} | tr -d '#')u64, $(echo ${lp_id2} | tr -d '#')u64"
+echo resim run manifests/get_lp_position.rtm
+resim run manifests/get_lp_position.rtm >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+echo Got redeemable amounts, fees accrued and impermanent loss for LP tokens ${lp_id1} and ${lp_id2}
+grep -A 40 'Outputs' $OUTPUTFILE | grep 'Decimal' | head -n 14
+grep 'Transaction Cost: ' $OUTPUTFILE
+
+echo
+export lp_token=${lp_quick}
+export lp_id=${lp_id2}
+export unlock_date=$((${unix_epoch} + 3600))
+echo resim run manifests/lock_liquidity.rtm
+resim run manifests/lock_liquidity.rtm >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+export lock_id="#$(grep -A 1 "ResAddr: ${lock_certificate}" $OUTPUTFILE | tail -n 1 | cut -d '#' -f 2)#"
+echo Locked LP token ${lp_id2} until ${unlock_date}, received lock certificate ${lock_id}
+grep 'Transaction Cost: ' $OUTPUTFILE
+
+echo
+echo resim call-method ${radix_pump_component} get_pool_info ${quick_launched_coin}
+resim call-method ${radix_pump_component} get_pool_info ${quick_launched_coin} >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+echo Locked lp_share and percentage: $(grep 'Decimal' $OUTPUTFILE | tail -n 2 | cut -d '"' -f 2 | tr '\n' ' ')
+
+echo
+echo resim call-method ${radix_pump_component} unlock_liquidity ${lock_certificate}:${lock_id}
+resim call-method ${radix_pump_component} unlock_liquidity ${lock_certificate}:${lock_id} >$OUTPUTFILE && ( echo "This transaction was supposed to fail!" ; cat $OUTPUTFILE ; exit 1 )
+echo Tried to unlock the liquidity before the unlock date and the transaction failed as expected
+
+echo
+export unlock_date=$((${unlock_date} - 60))
+echo resim run manifests/extend_liquidity_lock.rtm
+resim run manifests/extend_liquidity_lock.rtm >$OUTPUTFILE && ( echo "This transaction was supposed to fail!" ; cat $OUTPUTFILE ; exit 1 )
+echo Tried to anticipate the unlock date and the transaction failed as expected
+
+echo
+export unlock_date=$((${unlock_date} + 3600))
+echo resim run manifests/extend_liquidity_lock.rtm
+resim run manifests/extend_liquidity_lock.rtm >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+echo Extended the lock ${lock_id} until ${unlock_date}
+grep 'Transaction Cost: ' $OUTPUTFILE
+
+echo
+update_wallet_amounts
+echo resim run manifests/get_locked_lp_rewards.rtm
+resim run manifests/get_locked_lp_rewards.rtm >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+echo Withdraw rewards for the locked LP token ${lp_id2}, $(increase_in_wallet ${reward_coin}) ${reward_coin} received
+grep 'Transaction Cost: ' $OUTPUTFILE
+
+echo
+export unix_epoch=${unlock_date}
+date=$(date -u -d @${unix_epoch} +"%Y-%m-%dT%H:%M:%SZ")
+resim set-current-time $date
+echo Date is now $unix_epoch
+
+echo
+echo resim call-method ${radix_pump_component} unlock_liquidity ${lock_certificate}:${lock_id}
+resim call-method ${radix_pump_component} unlock_liquidity ${lock_certificate}:${lock_id} >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+echo Unlocked the liquidity, LP token ${lp_id2} is back in the wallet
+grep 'Transaction Cost: ' $OUTPUTFILE
+
 echo
 update_wallet_amounts
 echo resim call-method ${radix_pump_component} remove_liquidity ${lp_quick}:${lp_id1}
@@ -223,3 +316,57 @@ echo resim run manifests/terminate_liquidity_campaign.rtm
 resim run manifests/terminate_liquidity_campaign.rtm >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
 echo Coin creator terminated liquidity campaign, received back $(increase_in_wallet ${reward_coin}) ${reward_coin}
 grep 'Transaction Cost: ' $OUTPUTFILE
+
+echo
+export reward_coin_amount=1
+export start_time=${unix_epoch}
+export end_time=$((${unix_epoch} + 172800))
+echo resim run manifests/new_liquidity_campaign.rtm
+resim run manifests/new_liquidity_campaign.rtm >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+echo Created an underfunded liquidity campaign: ${daily_reward_per_coin} ${reward_coin} per coin per day, only ${reward_coin_amount} ${reward_coin} deposited
+grep 'Transaction Cost: ' $OUTPUTFILE
+
+echo
+export quick_coin_amount=$(echo ${quick_coin_amount} / 4 | bc)
+echo resim call-method ${radix_pump_component} add_liquidity ${base_coin}:${base_coin_amount} ${quick_launched_coin}:${quick_coin_amount}
+resim call-method ${radix_pump_component} add_liquidity ${base_coin}:${base_coin_amount} ${quick_launched_coin}:${quick_coin_amount} >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+export lp_id4="#$(grep -A 1 "ResAddr: ${lp_quick}" $OUTPUTFILE | tail -n 1 | cut -d '#' -f 2)#"
+echo Added ${quick_coin_amount} ${quick_launched_coin} to the pool, LP id ${lp_id4} received
+grep 'Transaction Cost: ' $OUTPUTFILE
+
+echo
+echo resim call-method ${radix_pump_component} add_liquidity ${base_coin}:${base_coin_amount} ${quick_launched_coin}:${quick_coin_amount}
+resim call-method ${radix_pump_component} add_liquidity ${base_coin}:${base_coin_amount} ${quick_launched_coin}:${quick_coin_amount} >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+export lp_id5="#$(grep -A 1 "ResAddr: ${lp_quick}" $OUTPUTFILE | tail -n 1 | cut -d '#' -f 2)#"
+echo Added ${quick_coin_amount} ${quick_launched_coin} to the pool, LP id ${lp_id5} received
+grep 'Transaction Cost: ' $OUTPUTFILE
+
+echo
+export unix_epoch=$((${unix_epoch} + 86400))
+date=$(date -u -d @${unix_epoch} +"%Y-%m-%dT%H:%M:%SZ")
+resim set-current-time $date
+echo Date is now $unix_epoch
+
+echo
+update_wallet_amounts
+echo resim call-method ${radix_pump_component} merge_lp ${lp_quick}:${lp_id4},${lp_id5}
+resim call-method ${radix_pump_component} merge_lp ${lp_quick}:${lp_id4},${lp_id5} >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+grep -q OutOfFundsEvent $OUTPUTFILE || ( echo "An OutOfFundsEvent was expected!" ; cat $OUTPUTFILE ; exit 1 )
+echo Merged LP tokens ${lp_id4} and ${lp_id5} into ${lp_id4} while the campaign is out of funds, received $(increase_in_wallet ${reward_coin}) ${reward_coin}
+grep 'Transaction Cost: ' $OUTPUTFILE
+
+echo
+export reward_coin_amount=$((${supply} / 4))
+echo resim run manifests/update_liquidity_campaign.rtm
+resim run manifests/update_liquidity_campaign.rtm >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+echo Added ${reward_coin_amount} ${reward_coin} to the liquidity campaign
+grep 'Transaction Cost: ' $OUTPUTFILE
+
+echo
+update_wallet_amounts
+export lp_token=${lp_quick}
+export lp_id=${lp_id4}
+echo resim run manifests/get_rewards.rtm
+resim run manifests/get_rewards.rtm >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+echo Withdraw rewards for LP token ${lp_id}, the ones not paid during the merge included: $(increase_in_wallet ${reward_coin}) ${reward_coin} received
+grep 'Transaction Cost: ' $OUTPUTFILE
@@ -53,6 +53,7 @@ get_pool_info () {
         'Enum::[3],') echo pool_mode: Normal ;;
         'Enum::[4],') echo pool_mode: Liquidation ;;
         'Enum::[5],') echo pool_mode: Uninitialised ;;
+        'Enum::[6],') echo pool_mode: Refunding ;;
       esac
       read lp_resource_address
       read coin_lp_ratio
@@ -154,7 +155,8 @@ export creator_badge=$(grep 'Resource:' $OUTPUTFILE | head -n 1 | cut -d ' ' -f
 export flash_loan_nft=$(grep 'Resource:' $OUTPUTFILE | head -n 2 | tail -n 1 | cut -d ' ' -f 3)
 export proxy_badge=$(grep 'Resource:' $OUTPUTFILE | head -n 4 | tail -n 1 | cut -d ' ' -f 3)
 export integrator_badge=$(grep 'Resource:' $OUTPUTFILE | head -n 5 | tail -n 1 | cut -d ' ' -f 3)
-echo -e "RadixPump component: ${radix_pump_component}\nCreator badge: ${creator_badge}\nFlash loan transient NFT: ${flash_loan_nft}\nProxy badge: ${proxy_badge}\nIntegrator badge: ${integrator_badge}"
+export referral_badge=$(grep 'Resource:' $OUTPUTFILE | head -n 7 | tail -n 1 | cut -d ' ' -f 3)
+echo -e "RadixPump component: ${radix_pump_component}\nCreator badge: ${creator_badge}\nFlash loan transient NFT: ${flash_loan_nft}\nProxy badge: ${proxy_badge}\nIntegrator badge: ${integrator_badge}\nReferral badge: ${referral_badge}"
 grep 'Transaction Cost: ' $OUTPUTFILE
 
 echo
@@ -254,6 +256,9 @@ export price=10
 export buy_pool_fee=1
 export sell_pool_fee=1
 export flash_loan_pool_fee=1
+export integrator_id=0
+export anti_sniping_window=None
+export fungible_lp=false
 echo run manifests/new_quick_launch.rtm
 resim run manifests/new_quick_launch.rtm >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
 export quick_launched_coin=$(grep 'Resource:' $OUTPUTFILE | head -n 1 | cut -d ' ' -f 3)
@@ -321,8 +326,22 @@ echo
 update_wallet_amounts
 export payment=1000
 export integrator_id=0
-echo resim call-method ${radix_pump_component} swap ${base_coin}:$payment ${quick_launched_coin} ${integrator_id}
-resim call-method ${radix_pump_component} swap ${base_coin}:$payment ${quick_launched_coin} ${integrator_id} >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+echo resim call-method ${radix_pump_component} quote_buy ${quick_launched_coin} $payment ${integrator_id}
+resim call-method ${radix_pump_component} quote_buy ${quick_launched_coin} $payment ${integrator_id} >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+echo "Expected coins for $payment ${base_coin}: $(grep -A 1 '├─ Tuple(' $OUTPUTFILE | tail -n 1 | cut -d '"' -f 2)"
+
+export coin1=${base_coin}
+export coin1_amount=$payment
+export coin2=${quick_launched_coin}
+export min_amount_out=1000000000
+export deadline=None
+echo "Trying to buy with an unreachable min_amount_out"
+echo resim run manifests/swap.rtm
+resim run manifests/swap.rtm >$OUTPUTFILE && ( echo "This transaction was supposed to fail!" ; cat $OUTPUTFILE ; exit 1 )
+echo Transaction failed as expected
+export min_amount_out=0
+echo resim run manifests/swap.rtm
+resim run manifests/swap.rtm >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
 export collected_fees=$(echo "${collected_fees} + $payment * ${buy_sell_fee_percentage} / 100" | bc)
 echo Bought $(increase_in_wallet ${quick_launched_coin}) ${quick_launched_coin} for $payment ${base_coin}
 echo $(increase_in_wallet ${test_hook2_coin}) TestHook2 coin received
@@ -350,8 +369,18 @@ grep 'Transaction Cost: ' $OUTPUTFILE
 echo
 update_wallet_amounts
 export payment=10
-echo resim call-method ${radix_pump_component} swap ${quick_launched_coin}:$payment ${base_coin} ${integrator_id}
-resim call-method ${radix_pump_component} swap ${quick_launched_coin}:$payment ${base_coin} ${integrator_id} >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+export coin1=${quick_launched_coin}
+export coin1_amount=$payment
+export coin2=${base_coin}
+export min_amount_out=1000000000
+echo "Trying to sell with an unreachable min_amount_out"
+echo resim run manifests/swap.rtm
+resim run manifests/swap.rtm >$OUTPUTFILE && ( echo "This transaction was supposed to fail!" ; cat $OUTPUTFILE ; exit 1 )
+grep -q 'is lower than the minimum accepted' $OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+echo Transaction failed as expected, the route was quoted before the Sell hooks
+export min_amount_out=0
+echo resim run manifests/swap.rtm
+resim run manifests/swap.rtm >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
 echo Sold $payment ${quick_launched_coin} for $(increase_in_wallet ${base_coin}) ${base_coin}
 echo $(increase_in_wallet ${test_hook2_coin}) TestHook2 coin received
 grep 'Transaction Cost: ' $OUTPUTFILE
@@ -380,8 +409,11 @@ get_pool_info ${quick_launched_coin}
 echo
 update_wallet_amounts
 export payment=1000
-echo resim call-method ${radix_pump_component} swap ${base_coin}:$payment ${quick_launched_coin} ${integrator_id}
-resim call-method ${radix_pump_component} swap ${base_coin}:$payment ${quick_launched_coin} ${integrator_id} >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+export coin1=${base_coin}
+export coin1_amount=$payment
+export coin2=${quick_launched_coin}
+echo resim run manifests/swap.rtm
+resim run manifests/swap.rtm >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
 export collected_fees=$(echo "${collected_fees} + $payment * ${buy_sell_fee_percentage} / 100" | bc)
 echo Bought $(increase_in_wallet ${quick_launched_coin}) ${quick_launched_coin} for $payment ${base_coin}
 echo $(increase_in_wallet ${test_hook2_coin}) TestHook2 coin received
@@ -390,6 +422,21 @@ grep 'Transaction Cost: ' $OUTPUTFILE
 echo
 get_pool_info ${quick_launched_coin}
 
+echo
+update_wallet_amounts
+export coin1=${base_coin}
+export coin1_amount=1000
+export coin2=${quick_launched_coin}
+export amount_out=100
+echo resim run manifests/swap_exact_out.rtm
+resim run manifests/swap_exact_out.rtm >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+echo "Bought $(increase_in_wallet ${quick_launched_coin}) ${quick_launched_coin} (should be ${amount_out}), ${base_coin} in wallet changed by $(increase_in_wallet ${base_coin})"
+echo $(increase_in_wallet ${test_hook2_coin}) TestHook2 coin received
+grep 'Transaction Cost: ' $OUTPUTFILE
+
+echo
+get_pool_info ${quick_launched_coin}
+
 echo
 echo resim call-method ${radix_pump_component} owner_set_liquidation_mode ${quick_launched_coin} --proofs ${owner_badge}:${owner_badge_id}
 resim call-method ${radix_pump_component} owner_set_liquidation_mode ${quick_launched_coin} --proofs ${owner_badge}:${owner_badge_id} >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
@@ -402,8 +449,11 @@ get_pool_info ${quick_launched_coin}
 echo
 update_wallet_amounts
 export payment=1
-echo resim call-method ${radix_pump_component} swap ${quick_launched_coin}:$payment ${base_coin} ${integrator_id}
-resim call-method ${radix_pump_component} swap ${quick_launched_coin}:$payment ${base_coin} ${integrator_id} >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+export coin1=${quick_launched_coin}
+export coin1_amount=$payment
+export coin2=${base_coin}
+echo resim run manifests/swap.rtm
+resim run manifests/swap.rtm >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
 echo Sold $payment ${quick_launched_coin} for $(increase_in_wallet ${base_coin}) ${base_coin}
 echo $(increase_in_wallet ${test_hook2_coin}) TestHook2 coin received
 grep 'Transaction Cost: ' $OUTPUTFILE
@@ -414,8 +464,11 @@ get_pool_info ${quick_launched_coin}
 echo
 update_wallet_amounts
 export payment=1
-echo resim call-method ${radix_pump_component} swap ${quick_launched_coin}:$payment ${base_coin} ${integrator_id}
-resim call-method ${radix_pump_component} swap ${quick_launched_coin}:$payment ${base_coin} ${integrator_id} >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+export coin1=${quick_launched_coin}
+export coin1_amount=$payment
+export coin2=${base_coin}
+echo resim run manifests/swap.rtm
+resim run manifests/swap.rtm >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
 echo "Sold $payment ${quick_launched_coin} for $(increase_in_wallet ${base_coin}) ${base_coin} (price should not have changed)"
 echo $(increase_in_wallet ${test_hook2_coin}) TestHook2 coin received
 grep 'Transaction Cost: ' $OUTPUTFILE
@@ -429,3 +482,319 @@ echo resim run manifests/integrator_get_fees.rtm
 resim run manifests/integrator_get_fees.rtm >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
 echo Integrator ${integrator_id} withdrawed $(increase_in_wallet ${base_coin}) ${base_coin}
 grep 'Transaction Cost: ' $OUTPUTFILE
+
+echo
+export icon=https://upload.wikimedia.org/wikipedia/commons/thumb/2/2f/Radix_logo.svg/200px-Radix_logo.svg.png
+export description="Quick launched coin with updated metadata"
+export info_url=https://www.radixdlt.com/
+export social_url='Array<String>("https://x.com/radixdlt")'
+echo resim run manifests/update_coin_metadata.rtm
+resim run manifests/update_coin_metadata.rtm >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+echo Updated the metadata of ${quick_launched_coin}
+grep 'Transaction Cost: ' $OUTPUTFILE
+
+echo
+export forbidden_symbols='"QL"'
+echo resim run manifests/forbid_symbols.rtm
+resim run manifests/forbid_symbols.rtm >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+echo Symbols ${forbidden_symbols} forbidden
+grep 'Transaction Cost: ' $OUTPUTFILE
+
+echo
+echo resim run manifests/update_coin_metadata.rtm
+resim run manifests/update_coin_metadata.rtm >$OUTPUTFILE && ( echo "This transaction was supposed to fail!" ; cat $OUTPUTFILE ; exit 1 )
+echo Tried to update the metadata of a coin whose symbol has been forbidden, the transaction failed as expected
+
+echo
+echo resim run manifests/new_referral.rtm
+resim run manifests/new_referral.rtm >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+export referral_id="$(grep -A 1 "ResAddr: ${referral_badge}" $OUTPUTFILE | tail -n 1 | cut -d '#' -f 2)"
+export referral_badge_id="#${referral_id}#"
+echo Referral badge ${referral_badge_id} minted
+grep 'Transaction Cost: ' $OUTPUTFILE
+
+echo
+update_wallet_amounts
+export payment=10
+export coin1=${base_coin}
+export coin1_amount=$payment
+export coin2=${quick_launched_coin}
+echo resim run manifests/swap_with_referral.rtm
+resim run manifests/swap_with_referral.rtm >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+echo Bought $(increase_in_wallet ${quick_launched_coin}) ${quick_launched_coin} for $payment ${base_coin} specifying the referral badge ${referral_badge_id}
+grep 'Transaction Cost: ' $OUTPUTFILE
+
+echo
+update_wallet_amounts
+echo resim run manifests/get_referral_fees.rtm
+resim run manifests/get_referral_fees.rtm >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+echo Referrer ${referral_id} withdrawed $(increase_in_wallet ${base_coin}) ${base_coin}
+grep 'Transaction Cost: ' $OUTPUTFILE
+
+echo
+export integrator_creation_fee_percentage=0.2
+export integrator_buy_sell_fee_percentage=0.1
+export integrator_flash_loan_fee=0
+export integrator_flash_loan_fee_percentage=0
+echo resim run manifests/set_integrator_fees.rtm
+resim run manifests/set_integrator_fees.rtm >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+echo Creation fee for integrator ${integrator_id} set to ${integrator_creation_fee_percentage}%, buy and sell fee set to ${integrator_buy_sell_fee_percentage}%
+grep 'Transaction Cost: ' $OUTPUTFILE
+
+echo
+update_wallet_amounts
+export payment=10
+export coin1=${base_coin}
+export coin1_amount=$payment
+export coin2=${quick_launched_coin}
+echo resim run manifests/swap.rtm
+resim run manifests/swap.rtm >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+echo Bought $(increase_in_wallet ${quick_launched_coin}) ${quick_launched_coin} for $payment ${base_coin} paying the integrator ${integrator_id} fee
+grep 'Transaction Cost: ' $OUTPUTFILE
+
+echo
+echo resim call-method ${radix_pump_component} get_integrator_stats ${integrator_id}
+resim call-method ${radix_pump_component} get_integrator_stats ${integrator_id} >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+echo "Integrator ${integrator_id} volume, collected fees and withdrawable fees: $(grep -A 3 '├─ Tuple(' $OUTPUTFILE | tail -n 3 | cut -d '"' -f 2 | paste -sd ' ')"
+
+echo
+export integrator_name='Some("disabled test")'
+export integrator_active='Some(false)'
+echo resim run manifests/update_integrator.rtm
+resim run manifests/update_integrator.rtm >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+echo Integrator ${integrator_id} renamed and disabled
+grep 'Transaction Cost: ' $OUTPUTFILE
+
+echo
+update_wallet_amounts
+echo resim run manifests/swap.rtm
+resim run manifests/swap.rtm >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+echo Bought $(increase_in_wallet ${quick_launched_coin}) ${quick_launched_coin} for $payment ${base_coin}, the fee went to the component owner because integrator ${integrator_id} is disabled
+grep 'Transaction Cost: ' $OUTPUTFILE
+
+echo
+update_wallet_amounts
+export base_coin_amount=${minimum_deposit}
+export symbol=QLAS
+export name=AntiSnipingCoin
+export description="Quick launched coin with an opening window"
+export social_url='Array<String>()'
+export anti_sniping_window='Some(Tuple(600i64, Some(Decimal("10")), Decimal("50")))'
+echo resim run manifests/new_quick_launch.rtm
+resim run manifests/new_quick_launch.rtm >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+export anti_sniping_coin=$(grep 'Resource:' $OUTPUTFILE | head -n 1 | cut -d ' ' -f 3)
+echo Quick launched ${anti_sniping_coin} with a 10 minutes opening window, max 10 ${base_coin} per transaction and 50% initial extra buy fee
+grep 'Transaction Cost: ' $OUTPUTFILE
+
+echo
+get_pool_info ${anti_sniping_coin}
+
+echo
+export payment=20
+export coin1=${base_coin}
+export coin1_amount=$payment
+export coin2=${anti_sniping_coin}
+echo resim run manifests/swap.rtm
+resim run manifests/swap.rtm >$OUTPUTFILE && ( echo "This transaction was supposed to fail!" ; cat $OUTPUTFILE ; exit 1 )
+echo Tried to buy $payment ${base_coin} of ${anti_sniping_coin} during the opening window and the transaction failed as expected
+
+echo
+update_wallet_amounts
+export payment=5
+export coin1_amount=$payment
+echo resim run manifests/swap.rtm
+resim run manifests/swap.rtm >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+echo Bought $(increase_in_wallet ${anti_sniping_coin}) ${anti_sniping_coin} for $payment ${base_coin} paying the extra fee
+grep 'Transaction Cost: ' $OUTPUTFILE
+
+echo
+export coin=${quick_launched_coin}
+echo resim run manifests/pause_pool.rtm
+resim run manifests/pause_pool.rtm >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+echo The ${quick_launched_coin} pool has been paused
+grep 'Transaction Cost: ' $OUTPUTFILE
+
+echo
+get_pool_info ${quick_launched_coin}
+
+echo
+export payment=10
+export coin1=${base_coin}
+export coin1_amount=$payment
+export coin2=${quick_launched_coin}
+echo resim run manifests/swap.rtm
+resim run manifests/swap.rtm >$OUTPUTFILE && ( echo "This transaction was supposed to fail!" ; cat $OUTPUTFILE ; exit 1 )
+echo Tried to buy ${quick_launched_coin} while its pool is paused and the transaction failed as expected
+
+echo
+echo resim run manifests/unpause_pool.rtm
+resim run manifests/unpause_pool.rtm >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+echo The ${quick_launched_coin} pool has been unpaused
+grep 'Transaction Cost: ' $OUTPUTFILE
+
+echo
+echo resim run manifests/pause.rtm
+resim run manifests/pause.rtm >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+echo The whole component has been paused
+grep 'Transaction Cost: ' $OUTPUTFILE
+
+echo
+echo resim run manifests/swap.rtm
+resim run manifests/swap.rtm >$OUTPUTFILE && ( echo "This transaction was supposed to fail!" ; cat $OUTPUTFILE ; exit 1 )
+echo Tried to buy ${quick_launched_coin} while the component is paused and the transaction failed as expected
+
+echo
+echo resim run manifests/unpause.rtm
+resim run manifests/unpause.rtm >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+echo The component has been unpaused
+grep 'Transaction Cost: ' $OUTPUTFILE
+
+echo
+update_wallet_amounts
+echo resim run manifests/swap.rtm
+resim run manifests/swap.rtm >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+echo Bought $(increase_in_wallet ${quick_launched_coin}) ${quick_launched_coin} for $payment ${base_coin} after unpausing
+grep 'Transaction Cost: ' $OUTPUTFILE
+
+echo
+export unix_epoch=1800000000
+date=$(date -u -d @${unix_epoch} +"%Y-%m-%dT%H:%M:%SZ")
+resim set-current-time $date
+echo Date is now $unix_epoch
+
+echo
+export timelock_delay=86400
+echo resim run manifests/update_timelock_delay.rtm
+resim run manifests/update_timelock_delay.rtm >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+echo The timelock delay has been set to $timelock_delay seconds
+grep 'Transaction Cost: ' $OUTPUTFILE
+
+echo
+export min_launch_duration=1209600
+export min_lock_duration=1209600
+echo resim run manifests/update_time_limits.rtm
+resim run manifests/update_time_limits.rtm >$OUTPUTFILE && ( echo "This transaction was supposed to fail!" ; cat $OUTPUTFILE ; exit 1 )
+echo Tried to update the time limits directly while the timelock is active and the transaction failed as expected
+
+echo
+export integrator_buy_sell_fee_percentage=0
+echo resim run manifests/set_integrator_fees.rtm
+resim run manifests/set_integrator_fees.rtm >$OUTPUTFILE && ( echo "This transaction was supposed to fail!" ; cat $OUTPUTFILE ; exit 1 )
+echo Tried to change the fees of integrator ${integrator_id} directly while the timelock is active and the transaction failed as expected
+
+echo
+echo resim call-method ${radix_pump_component} remove_integrator_fees ${integrator_id} --proofs ${owner_badge}:${owner_badge_id}
+resim call-method ${radix_pump_component} remove_integrator_fees ${integrator_id} --proofs ${owner_badge}:${owner_badge_id} >$OUTPUTFILE && ( echo "This transaction was supposed to fail!" ; cat $OUTPUTFILE ; exit 1 )
+echo Tried to remove the fees of integrator ${integrator_id} directly while the timelock is active and the transaction failed as expected
+
+echo
+export owner_action="Enum<1u8>(${min_launch_duration}i64, ${min_lock_duration}i64)"
+echo resim run manifests/queue_owner_action.rtm
+resim run manifests/queue_owner_action.rtm >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+export owner_action_id=1
+echo Queued the update of the time limits, action id is $owner_action_id
+grep 'Transaction Cost: ' $OUTPUTFILE
+
+echo
+echo resim call-method ${radix_pump_component} get_pending_owner_actions
+resim call-method ${radix_pump_component} get_pending_owner_actions >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+grep -A 20 '├─ Tuple(' $OUTPUTFILE
+
+echo
+echo resim run manifests/execute_owner_action.rtm
+resim run manifests/execute_owner_action.rtm >$OUTPUTFILE && ( echo "This transaction was supposed to fail!" ; cat $OUTPUTFILE ; exit 1 )
+echo Tried to execute the queued action before the timelock expired and the transaction failed as expected
+
+echo
+export unix_epoch=$(($unix_epoch + $timelock_delay))
+date=$(date -u -d @${unix_epoch} +"%Y-%m-%dT%H:%M:%SZ")
+resim set-current-time $date
+echo Date is now $unix_epoch
+
+echo
+echo resim run manifests/execute_owner_action.rtm
+resim run manifests/execute_owner_action.rtm >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+echo Executed the queued update of the time limits
+grep 'Transaction Cost: ' $OUTPUTFILE
+
+echo
+export owner_action="Enum<4u8>(0i64)"
+echo resim run manifests/queue_owner_action.rtm
+resim run manifests/queue_owner_action.rtm >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+export owner_action_id=2
+echo Queued the removal of the timelock delay, action id is $owner_action_id
+grep 'Transaction Cost: ' $OUTPUTFILE
+
+echo
+echo resim run manifests/cancel_owner_action.rtm
+resim run manifests/cancel_owner_action.rtm >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+echo Cancelled the queued action
+grep 'Transaction Cost: ' $OUTPUTFILE
+
+echo
+echo resim run manifests/execute_owner_action.rtm
+resim run manifests/execute_owner_action.rtm >$OUTPUTFILE && ( echo "This transaction was supposed to fail!" ; cat $OUTPUTFILE ; exit 1 )
+echo Tried to execute the cancelled action and the transaction failed as expected
+
+echo
+update_wallet_amounts
+export base_coin_amount=${minimum_deposit}
+export symbol=FLP
+export name=FungibleLpCoin
+export description="Quick launched coin with fungible LP units"
+export anti_sniping_window=None
+export fungible_lp=true
+echo resim run manifests/new_quick_launch.rtm
+resim run manifests/new_quick_launch.rtm >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+export fungible_lp_coin=$(grep 'Resource:' $OUTPUTFILE | head -n 1 | cut -d ' ' -f 3)
+export fungible_lp_address=$(resim call-method ${radix_pump_component} get_pool_info ${fungible_lp_coin} | grep -A 200 '├─ Tuple(' | grep resource_sim | tail -n 1 | cut -d '"' -f 2)
+echo Quick launched ${fungible_lp_coin}, its pool issues fungible LP units ${fungible_lp_address}
+grep 'Transaction Cost: ' $OUTPUTFILE
+
+echo
+update_wallet_amounts
+export coin_amount=$(resim show | grep ${fungible_lp_coin} | cut -d ' ' -f 3)
+export base_coin_amount=1000
+echo resim call-method ${radix_pump_component} add_liquidity ${base_coin}:${base_coin_amount} ${fungible_lp_coin}:${coin_amount}
+resim call-method ${radix_pump_component} add_liquidity ${base_coin}:${base_coin_amount} ${fungible_lp_coin}:${coin_amount} >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+export lp_amount=$(increase_in_wallet ${fungible_lp_address})
+echo Added liquidity to the ${fungible_lp_coin} pool and received ${lp_amount} fungible LP units
+grep 'Transaction Cost: ' $OUTPUTFILE
+
+echo
+update_wallet_amounts
+export lp_amount=$(echo "$lp_amount / 2" | bc)
+echo resim call-method ${radix_pump_component} remove_liquidity ${fungible_lp_address}:${lp_amount}
+resim call-method ${radix_pump_component} remove_liquidity ${fungible_lp_address}:${lp_amount} >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+echo Removed liquidity using ${lp_amount} fungible LP units, received $(increase_in_wallet ${base_coin}) ${base_coin} and $(increase_in_wallet ${fungible_lp_coin}) ${fungible_lp_coin}
+grep 'Transaction Cost: ' $OUTPUTFILE
+
+echo
+update_wallet_amounts
+echo resim call-method ${radix_pump_component} zap_in ${base_coin}:100 ${fungible_lp_coin} 0 0
+resim call-method ${radix_pump_component} zap_in ${base_coin}:100 ${fungible_lp_coin} 0 0 >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+echo Zapped in 100 ${base_coin} and received $(increase_in_wallet ${fungible_lp_address}) fungible LP units
+grep 'Transaction Cost: ' $OUTPUTFILE
+
+echo
+update_wallet_amounts
+export coin_amount=$(echo "$(resim show | grep ${fungible_lp_coin} | cut -d ' ' -f 3) / 10" | bc)
+echo resim call-method ${radix_pump_component} zap_in ${fungible_lp_coin}:${coin_amount} ${fungible_lp_coin} 0 0
+resim call-method ${radix_pump_component} zap_in ${fungible_lp_coin}:${coin_amount} ${fungible_lp_coin} 0 0 >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+echo Zapped in ${coin_amount} ${fungible_lp_coin} and received $(increase_in_wallet ${fungible_lp_address}) fungible LP units
+grep 'Transaction Cost: ' $OUTPUTFILE
+
+echo
+echo resim call-method ${radix_pump_component} zap_in ${base_coin}:100 ${fungible_lp_coin} 1000000000 0
+resim call-method ${radix_pump_component} zap_in ${base_coin}:100 ${fungible_lp_coin} 1000000000 0 >$OUTPUTFILE && ( echo "This transaction was supposed to fail!" ; cat $OUTPUTFILE ; exit 1 )
+echo Tried to zap in asking for too many LP units and the transaction failed as expected
+
+echo
+update_wallet_amounts
+echo resim call-method ${radix_pump_component} zap_out ${fungible_lp_address}:${lp_amount} ${base_coin} 0 0
+resim call-method ${radix_pump_component} zap_out ${fungible_lp_address}:${lp_amount} ${base_coin} 0 0 >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+echo Zapped out ${lp_amount} fungible LP units and received $(increase_in_wallet ${base_coin}) ${base_coin}
+grep 'Transaction Cost: ' $OUTPUTFILE
+
+echo
+get_pool_info ${fungible_lp_coin}
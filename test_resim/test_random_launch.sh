@@ -53,6 +53,7 @@ get_pool_info () {
         'Enum::[3],') echo pool_mode: Normal ;;
         'Enum::[4],') echo pool_mode: Liquidation ;;
         'Enum::[5],') echo pool_mode: Uninitialised ;;
+        'Enum::[6],') echo pool_mode: Refunding ;;
       esac
       read lp_resource_address
       read coin_lp_ratio
@@ -247,6 +248,9 @@ export info_url=""
 export ticket_price=10
 export winning_tickets=30
 export coins_per_winning_ticket=10
+export access_rule=None
+export max_buy_per_holder=None
+export vesting_schedule=None
 export buy_pool_fee=5
 export sell_pool_fee=0.1
 export flash_loan_pool_fee=1
@@ -268,8 +272,13 @@ get_pool_info ${random_launched_coin}
 
 echo
 export payment=1000
-echo resim call-method ${radix_pump_component} swap ${base_coin}:$payment ${random_launched_coin} ${integrator_id}
-resim call-method ${radix_pump_component} swap ${base_coin}:$payment ${random_launched_coin} ${integrator_id} >$OUTPUTFILE && ( echo "This transaction was supposed to fail!" ; cat $OUTPUTFILE ; exit 1 )
+export min_amount_out=0
+export deadline=None
+export coin1=${base_coin}
+export coin1_amount=$payment
+export coin2=${random_launched_coin}
+echo resim run manifests/swap.rtm
+resim run manifests/swap.rtm >$OUTPUTFILE && ( echo "This transaction was supposed to fail!" ; cat $OUTPUTFILE ; exit 1 )
 echo Someone tried to buy ${random_launched_coin} before it was launched, the transaction failed
 
 echo
@@ -288,14 +297,18 @@ get_pool_info ${random_launched_coin}
 echo
 export amount=50
 export payment=$(echo "${ticket_price} * ${amount} - 0.0000001" | bc)
-echo resim call-method ${radix_pump_component} buy_ticket ${random_launched_coin} $amount ${base_coin}:$payment
-resim call-method ${radix_pump_component} buy_ticket ${random_launched_coin} $amount ${base_coin}:$payment >$OUTPUTFILE && ( echo "This transaction was supposed to fail!" ; cat $OUTPUTFILE ; exit 1 )
+echo resim run manifests/buy_ticket.rtm
+resim run manifests/buy_ticket.rtm >$OUTPUTFILE && ( echo "This transaction was supposed to fail!" ; cat $OUTPUTFILE ; exit 1 )
 echo Failed attempt to buy one ticket without paying ticket_price + total_buy_fee
 
 echo
 export buy_pool_fee_percentage=0.1
 export sell_pool_fee_percentage=0.1
 export flash_loan_pool_fee=2
+export base_coin_flash_loan_pool_fee=2
+export flash_loan_pool_fee_percentage=0
+export max_flash_loan_percentage=100
+export creator_fee_percentage=0
 echo resim run manifests/update_pool_fees.rtm
 resim run manifests/update_pool_fees.rtm >$OUTPUTFILE && ( echo "This transaction was supposed to fail!" ; cat $OUTPUTFILE ; exit 1 )
 echo Failed attempt to update fees during launch phase, this is not allowed
@@ -303,9 +316,10 @@ echo Failed attempt to update fees during launch phase, this is not allowed
 echo
 update_wallet_amounts
 export bought_tickets1=20
-export payment=$(echo "${ticket_price} * ${bought_tickets1}" | bc)
-echo resim call-method ${radix_pump_component} buy_ticket ${random_launched_coin} ${bought_tickets1} ${base_coin}:$payment
-resim call-method ${radix_pump_component} buy_ticket ${random_launched_coin} ${bought_tickets1} ${base_coin}:$payment >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+export payment=$(echo "scale=6; ${ticket_price} * ${bought_tickets1} * (100 + ${buy_sell_fee_percentage}) / 100" | bc)
+export amount=${bought_tickets1}
+echo resim run manifests/buy_ticket.rtm
+resim run manifests/buy_ticket.rtm >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
 echo Bought $(increase_in_wallet ${random_ticket}) tickets for $payment $base_coin
 echo TestHook2 coin received: $(increase_in_wallet ${test_hook2_coin})
 grep 'Transaction Cost: ' $OUTPUTFILE
@@ -313,9 +327,10 @@ grep 'Transaction Cost: ' $OUTPUTFILE
 echo
 update_wallet_amounts
 export bought_tickets2=30
-export payment=$(echo "${ticket_price} * ${bought_tickets2}" | bc)
-echo resim call-method ${radix_pump_component} buy_ticket ${random_launched_coin} ${bought_tickets2} ${base_coin}:$payment
-resim call-method ${radix_pump_component} buy_ticket ${random_launched_coin} ${bought_tickets2} ${base_coin}:$payment >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+export payment=$(echo "scale=6; ${ticket_price} * ${bought_tickets2} * (100 + ${buy_sell_fee_percentage}) / 100" | bc)
+export amount=${bought_tickets2}
+echo resim run manifests/buy_ticket.rtm
+resim run manifests/buy_ticket.rtm >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
 echo Bought $(increase_in_wallet ${random_ticket}) tickets for $payment $base_coin
 echo TestHook2 coin received: $(increase_in_wallet ${test_hook2_coin})
 grep 'Transaction Cost: ' $OUTPUTFILE
@@ -360,10 +375,12 @@ echo
 export creation_fee_percentage=0.2
 export buy_sell_fee_percentage=0.2
 export flash_loan_fee=2
+export flash_loan_fee_percentage=0
 export max_buy_sell_pool_fee_percentage=5
+export max_creator_fee_percentage=1
 export minimum_deposit=10000
-echo resim call-method ${radix_pump_component} update_fees ${creation_fee_percentage} ${buy_sell_fee_percentage} ${flash_loan_fee} ${max_buy_sell_pool_fee_percentage} ${minimum_deposit} --proofs ${owner_badge}:${owner_badge_id}
-resim call-method ${radix_pump_component} update_fees ${creation_fee_percentage} ${buy_sell_fee_percentage} ${flash_loan_fee} ${max_buy_sell_pool_fee_percentage} ${minimum_deposit} --proofs ${owner_badge}:${owner_badge_id} >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+echo resim call-method ${radix_pump_component} update_fees ${creation_fee_percentage} ${buy_sell_fee_percentage} ${flash_loan_fee} ${flash_loan_fee_percentage} ${max_buy_sell_pool_fee_percentage} ${max_creator_fee_percentage} ${minimum_deposit} --proofs ${owner_badge}:${owner_badge_id}
+resim call-method ${radix_pump_component} update_fees ${creation_fee_percentage} ${buy_sell_fee_percentage} ${flash_loan_fee} ${flash_loan_fee_percentage} ${max_buy_sell_pool_fee_percentage} ${max_creator_fee_percentage} ${minimum_deposit} --proofs ${owner_badge}:${owner_badge_id} >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
 echo Updated platform fees 
 grep 'Transaction Cost: ' $OUTPUTFILE
 
@@ -374,6 +391,10 @@ echo
 export buy_pool_fee_percentage=0.1
 export sell_pool_fee_percentage=0.2
 export flash_loan_pool_fee=3
+export base_coin_flash_loan_pool_fee=2
+export flash_loan_pool_fee_percentage=0
+export max_flash_loan_percentage=100
+export creator_fee_percentage=0
 echo resim run manifests/update_pool_fees.rtm
 resim run manifests/update_pool_fees.rtm >$OUTPUTFILE && ( echo "This transaction was supposed to fail!" ; cat $OUTPUTFILE ; exit 1 )
 echo Transaction failed because the creator tried to increase sell_pool_fee_percentage
@@ -382,6 +403,10 @@ echo
 export buy_pool_fee_percentage=0.1
 export sell_pool_fee_percentage=0.1
 export flash_loan_pool_fee=3
+export base_coin_flash_loan_pool_fee=2
+export flash_loan_pool_fee_percentage=0
+export max_flash_loan_percentage=100
+export creator_fee_percentage=0
 echo resim run manifests/update_pool_fees.rtm
 resim run manifests/update_pool_fees.rtm >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
 echo Updated ${random_launched_coin} pool fees
@@ -443,6 +468,85 @@ echo "${random_launched_coin} variation in wallet: $(increase_in_wallet ${random
 echo "${base_coin} variation in wallet: $(increase_in_wallet ${base_coin}) (if both are negative the attack failed)"
 grep 'Transaction Cost: ' $OUTPUTFILE
 
+echo
+update_wallet_amounts
+export coin=${random_launched_coin}
+export loan_amount=10
+export fee=$(($flash_loan_fee + $base_coin_flash_loan_pool_fee))
+echo resim run manifests/base_coin_flash_loan.rtm
+resim run manifests/base_coin_flash_loan.rtm >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+echo "Borrowed $loan_amount ${base_coin} from the ${random_launched_coin} pool and returned them, ${base_coin} variation in wallet: $(increase_in_wallet ${base_coin}) (should be -$fee)"
+grep 'Transaction Cost: ' $OUTPUTFILE
+
+echo
+export fee=$flash_loan_fee
+echo resim run manifests/base_coin_flash_loan.rtm
+resim run manifests/base_coin_flash_loan.rtm >$OUTPUTFILE && ( echo "This transaction was supposed to fail!" ; cat $OUTPUTFILE ; exit 1 )
+echo Tried to return a base coin flash loan without paying the pool fee, the transaction failed as expected
+
+echo
+export flash_loan_pool_fee_percentage=10
+export max_flash_loan_percentage=0
+echo resim run manifests/update_pool_fees.rtm
+resim run manifests/update_pool_fees.rtm >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+echo Set a ${flash_loan_pool_fee_percentage}% flash loan pool fee and forbade borrowing ${random_launched_coin} from the pool
+grep 'Transaction Cost: ' $OUTPUTFILE
+
+echo
+export fee=$(($flash_loan_fee + $base_coin_flash_loan_pool_fee))
+echo resim run manifests/base_coin_flash_loan.rtm
+resim run manifests/base_coin_flash_loan.rtm >$OUTPUTFILE && ( echo "This transaction was supposed to fail!" ; cat $OUTPUTFILE ; exit 1 )
+echo Tried to return a base coin flash loan paying only the fixed fees, the transaction failed because of the percentage fee
+
+echo
+update_wallet_amounts
+export fee=$(($flash_loan_fee + $base_coin_flash_loan_pool_fee + $loan_amount * $flash_loan_pool_fee_percentage / 100))
+echo resim run manifests/base_coin_flash_loan.rtm
+resim run manifests/base_coin_flash_loan.rtm >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+echo "Borrowed $loan_amount ${base_coin} from the ${random_launched_coin} pool and returned them, ${base_coin} variation in wallet: $(increase_in_wallet ${base_coin}) (should be -$fee)"
+grep 'Transaction Cost: ' $OUTPUTFILE
+
+echo
+export loan_amount=9
+export sell_amount=9
+echo resim run manifests/flash_loan_attack_sell.rtm
+resim run manifests/flash_loan_attack_sell.rtm >$OUTPUTFILE && ( echo "This transaction was supposed to fail!" ; cat $OUTPUTFILE ; exit 1 )
+echo Tried to borrow ${random_launched_coin} while max_flash_loan_percentage is zero, the transaction failed as expected
+
+echo
+export creator_fee_percentage=2
+echo resim run manifests/update_pool_fees.rtm
+resim run manifests/update_pool_fees.rtm >$OUTPUTFILE && ( echo "This transaction was supposed to fail!" ; cat $OUTPUTFILE ; exit 1 )
+echo Tried to set a creator fee higher than max_creator_fee_percentage, the transaction failed as expected
+
+echo
+export creator_fee_percentage=1
+echo resim run manifests/update_pool_fees.rtm
+resim run manifests/update_pool_fees.rtm >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+echo Set a ${creator_fee_percentage}% creator fee on ${random_launched_coin}
+grep 'Transaction Cost: ' $OUTPUTFILE
+
+echo
+update_wallet_amounts
+export payment=100
+export coin1=${base_coin}
+export coin1_amount=$payment
+export coin2=${random_launched_coin}
+echo resim run manifests/swap.rtm
+resim run manifests/swap.rtm >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+echo "Received $(increase_in_wallet ${random_launched_coin}) ${random_launched_coin} for $payment ${base_coin}"
+grep 'Transaction Cost: ' $OUTPUTFILE
+
+echo
+update_wallet_amounts
+echo resim run manifests/get_creator_fees.rtm
+resim run manifests/get_creator_fees.rtm >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+echo "The creator of ${random_launched_coin} collected $(increase_in_wallet ${base_coin}) ${base_coin} of creator fees"
+grep 'Transaction Cost: ' $OUTPUTFILE
+
+echo
+get_pool_info ${random_launched_coin}
+
 echo
 export hook_name=TestHook0
 export globally_enabled_operations='"Buy"'
@@ -473,8 +577,11 @@ get_pool_info ${random_launched_coin}
 echo
 update_wallet_amounts
 export payment=1
-echo resim call-method ${radix_pump_component} swap ${base_coin}:$payment ${random_launched_coin} ${integrator_id}
-resim call-method ${radix_pump_component} swap ${base_coin}:$payment ${random_launched_coin} ${integrator_id} >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
+export coin1=${base_coin}
+export coin1_amount=$payment
+export coin2=${random_launched_coin}
+echo resim run manifests/swap.rtm
+resim run manifests/swap.rtm >$OUTPUTFILE || ( cat $OUTPUTFILE ; exit 1 )
 echo "Received $(increase_in_wallet ${random_launched_coin}) ${random_launched_coin} for $payment (+1 from TestHook0) ${base_coin}"
 echo This should call once TestHook0 and TestHook1 and twice TestHook2
 echo $(increase_in_wallet ${test_hook1_coin}) TestHook1 coin received
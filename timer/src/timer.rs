@@ -49,6 +49,7 @@ struct RegisteredHook {
     BuyTicketEvent,
     AddLiquidityEvent,
     RemoveLiquidityEvent,
+    BondingCurveGraduationEvent,
 )]
 #[types(
     u64,
@@ -445,6 +446,7 @@ mod timer {
                     AnyPoolEvent::BuyTicketEvent(event) => Runtime::emit_event(*event),
                     AnyPoolEvent::AddLiquidityEvent(event) => Runtime::emit_event(*event),
                     AnyPoolEvent::RemoveLiquidityEvent(event) => Runtime::emit_event(*event),
+                    AnyPoolEvent::BondingCurveGraduationEvent(event) => Runtime::emit_event(*event),
                     _ => Runtime::panic("The hook is not supposed to generate this event".to_string()),
                 }
             }